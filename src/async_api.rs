@@ -0,0 +1,113 @@
+/// Async wrappers around the path-based collectors (`async` feature).
+///
+/// MDBX reads are blocking, so each call runs the synchronous function on tokio's
+/// blocking thread pool via `spawn_blocking` and awaits the result. Arguments are
+/// taken by value because the work outlives the caller's borrow. Must be called
+/// from within a tokio runtime.
+use alloy_primitives::{Address, B256};
+use eyre::{eyre, Result};
+use std::path::PathBuf;
+
+use crate::{
+    events::EventScanResult,
+    types::{BlockNumber, HistoricalPoolOutput, PoolInput, PoolOutput},
+    OpenOptions,
+};
+
+/// Run blocking DB work on the blocking pool, flattening join errors into `eyre`
+async fn run_blocking<T, F>(f: F) -> Result<T>
+where
+    F: FnOnce() -> Result<T> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|e| eyre!("blocking task failed: {e}"))?
+}
+
+/// Async version of [`crate::collect_pool_data_with_options`]
+pub async fn collect_pool_data_async(
+    db_path: impl Into<PathBuf>,
+    pools: Vec<PoolInput>,
+    v4_pool_ids: Option<Vec<B256>>,
+    options: OpenOptions,
+) -> Result<Vec<PoolOutput>> {
+    let db_path = db_path.into();
+    run_blocking(move || {
+        crate::collect_pool_data_with_options(db_path, &pools, v4_pool_ids.as_deref(), &options)
+    })
+    .await
+}
+
+/// Async version of [`crate::collect_pool_data_at_block_with_options`]
+pub async fn collect_pool_data_at_block_async(
+    db_path: impl Into<PathBuf>,
+    pools: Vec<PoolInput>,
+    v4_pool_ids: Option<Vec<B256>>,
+    block_number: BlockNumber,
+    options: OpenOptions,
+) -> Result<Vec<HistoricalPoolOutput>> {
+    let db_path = db_path.into();
+    run_blocking(move || {
+        crate::collect_pool_data_at_block_with_options(
+            db_path,
+            &pools,
+            v4_pool_ids.as_deref(),
+            block_number,
+            &options,
+        )
+    })
+    .await
+}
+
+/// Async version of [`crate::scan_pool_events_with_options`]
+pub async fn scan_pool_events_async(
+    db_path: impl Into<PathBuf>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+    options: OpenOptions,
+) -> Result<EventScanResult> {
+    let db_path = db_path.into();
+    let to_block = to_block.into();
+    run_blocking(move || {
+        crate::scan_pool_events_with_options(
+            db_path,
+            pool_address,
+            from_block,
+            to_block,
+            topics,
+            &options,
+        )
+    })
+    .await
+}
+
+/// Async version of [`crate::scan_pool_events_multi`]
+pub async fn scan_pool_events_multi_async(
+    db_path: impl Into<PathBuf>,
+    pool_addresses: Vec<Address>,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+) -> Result<Vec<EventScanResult>> {
+    let db_path = db_path.into();
+    let to_block = to_block.into();
+    run_blocking(move || {
+        crate::scan_pool_events_multi(db_path, &pool_addresses, from_block, to_block, topics)
+    })
+    .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_run_blocking_propagates_results() {
+        assert_eq!(run_blocking(|| Ok(7)).await.unwrap(), 7);
+        let err = run_blocking::<(), _>(|| Err(eyre!("db closed"))).await.unwrap_err();
+        assert_eq!(err.to_string(), "db closed");
+    }
+}
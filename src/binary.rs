@@ -0,0 +1,376 @@
+/// Compact binary encoding (bincode) for pool snapshots and event scans.
+///
+/// The public types are shaped for JSON (`skip_serializing_if`, a flattened `Log`),
+/// which a non-self-describing format like bincode cannot read back. Encoding goes
+/// through private wire structs that carry every field positionally; U256 and i128
+/// values round-trip exactly.
+use alloy_primitives::{Address, Bytes, Log, LogData, B256, U256};
+use eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    events::{EventLog, EventScanResult},
+    types::{Bitmap, BlockNumber, PoolOutput, Protocol, Reserves, Slot0, Tick},
+};
+
+#[derive(Serialize, Deserialize)]
+struct WireSlot0 {
+    raw_data: Option<String>,
+    sqrt_price_x96: U256,
+    tick: i32,
+    observation_index: u16,
+    observation_cardinality: u16,
+    observation_cardinality_next: u16,
+    fee_protocol: u8,
+    unlocked: bool,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WireTick {
+    tick: i32,
+    raw_data: Option<String>,
+    liquidity_gross: u128,
+    liquidity_net: i128,
+    fee_growth_outside_0_x128: U256,
+    fee_growth_outside_1_x128: U256,
+    tick_cumulative_outside: i64,
+    seconds_per_liquidity_outside_x128: U256,
+    seconds_outside: u32,
+    initialized: bool,
+    raw_slots: Vec<(B256, U256)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WireReserves {
+    raw_data: Option<String>,
+    reserve0: u128,
+    reserve1: u128,
+    block_timestamp_last: u32,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WirePool {
+    address: Address,
+    protocol: Protocol,
+    pool_id: Option<B256>,
+    reserves: Option<WireReserves>,
+    slot0: Option<WireSlot0>,
+    liquidity: Option<u128>,
+    liquidity_upper_bits_set: bool,
+    fee: Option<u32>,
+    ticks: Vec<WireTick>,
+    bitmaps: Vec<(i16, U256)>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WireLog {
+    address: Address,
+    topics: Vec<B256>,
+    data: Bytes,
+    block_number: BlockNumber,
+    transaction_index: u64,
+    log_index: u64,
+    transaction_hash: Option<B256>,
+    matched_filter: Option<usize>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct WireScan {
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    logs: Vec<WireLog>,
+    blocks_scanned: u64,
+    blocks_skipped_by_bloom: u64,
+    blocks_false_positive: u64,
+}
+
+impl From<&Slot0> for WireSlot0 {
+    fn from(s: &Slot0) -> Self {
+        Self {
+            raw_data: s.raw_data.clone(),
+            sqrt_price_x96: s.sqrt_price_x96,
+            tick: s.tick,
+            observation_index: s.observation_index,
+            observation_cardinality: s.observation_cardinality,
+            observation_cardinality_next: s.observation_cardinality_next,
+            fee_protocol: s.fee_protocol,
+            unlocked: s.unlocked,
+        }
+    }
+}
+
+impl From<WireSlot0> for Slot0 {
+    fn from(s: WireSlot0) -> Self {
+        Self {
+            raw_data: s.raw_data,
+            sqrt_price_x96: s.sqrt_price_x96,
+            tick: s.tick,
+            observation_index: s.observation_index,
+            observation_cardinality: s.observation_cardinality,
+            observation_cardinality_next: s.observation_cardinality_next,
+            fee_protocol: s.fee_protocol,
+            unlocked: s.unlocked,
+        }
+    }
+}
+
+impl From<&Tick> for WireTick {
+    fn from(t: &Tick) -> Self {
+        Self {
+            tick: t.tick,
+            raw_data: t.raw_data.clone(),
+            liquidity_gross: t.liquidity_gross,
+            liquidity_net: t.liquidity_net,
+            fee_growth_outside_0_x128: t.fee_growth_outside_0_x128,
+            fee_growth_outside_1_x128: t.fee_growth_outside_1_x128,
+            tick_cumulative_outside: t.tick_cumulative_outside,
+            seconds_per_liquidity_outside_x128: t.seconds_per_liquidity_outside_x128,
+            seconds_outside: t.seconds_outside,
+            initialized: t.initialized,
+            raw_slots: t.raw_slots.clone(),
+        }
+    }
+}
+
+impl From<WireTick> for Tick {
+    fn from(t: WireTick) -> Self {
+        Self {
+            tick: t.tick,
+            raw_data: t.raw_data,
+            liquidity_gross: t.liquidity_gross,
+            liquidity_net: t.liquidity_net,
+            fee_growth_outside_0_x128: t.fee_growth_outside_0_x128,
+            fee_growth_outside_1_x128: t.fee_growth_outside_1_x128,
+            tick_cumulative_outside: t.tick_cumulative_outside,
+            seconds_per_liquidity_outside_x128: t.seconds_per_liquidity_outside_x128,
+            seconds_outside: t.seconds_outside,
+            initialized: t.initialized,
+            raw_slots: t.raw_slots,
+        }
+    }
+}
+
+impl From<&Reserves> for WireReserves {
+    fn from(r: &Reserves) -> Self {
+        Self {
+            raw_data: r.raw_data.clone(),
+            reserve0: r.reserve0,
+            reserve1: r.reserve1,
+            block_timestamp_last: r.block_timestamp_last,
+        }
+    }
+}
+
+impl From<WireReserves> for Reserves {
+    fn from(r: WireReserves) -> Self {
+        Self {
+            raw_data: r.raw_data,
+            reserve0: r.reserve0,
+            reserve1: r.reserve1,
+            block_timestamp_last: r.block_timestamp_last,
+        }
+    }
+}
+
+impl From<&PoolOutput> for WirePool {
+    fn from(p: &PoolOutput) -> Self {
+        Self {
+            address: p.address,
+            protocol: p.protocol,
+            pool_id: p.pool_id,
+            reserves: p.reserves.as_ref().map(Into::into),
+            slot0: p.slot0.as_ref().map(Into::into),
+            liquidity: p.liquidity,
+            liquidity_upper_bits_set: p.liquidity_upper_bits_set,
+            fee: p.fee,
+            ticks: p.ticks.iter().map(Into::into).collect(),
+            bitmaps: p.bitmaps.iter().map(|b| (b.word_pos, b.bitmap)).collect(),
+        }
+    }
+}
+
+impl From<WirePool> for PoolOutput {
+    fn from(p: WirePool) -> Self {
+        Self {
+            address: p.address,
+            protocol: p.protocol,
+            pool_id: p.pool_id,
+            reserves: p.reserves.map(Into::into),
+            slot0: p.slot0.map(Into::into),
+            liquidity: p.liquidity,
+            liquidity_upper_bits_set: p.liquidity_upper_bits_set,
+            fee: p.fee,
+            ticks: p.ticks.into_iter().map(Into::into).collect(),
+            bitmaps: p
+                .bitmaps
+                .into_iter()
+                .map(|(word_pos, bitmap)| Bitmap { word_pos, bitmap })
+                .collect(),
+        }
+    }
+}
+
+impl From<&EventLog> for WireLog {
+    fn from(e: &EventLog) -> Self {
+        Self {
+            address: e.log.address,
+            topics: e.log.data.topics().to_vec(),
+            data: e.log.data.data.clone(),
+            block_number: e.block_number,
+            transaction_index: e.transaction_index,
+            log_index: e.log_index,
+            transaction_hash: e.transaction_hash,
+            matched_filter: e.matched_filter,
+        }
+    }
+}
+
+impl From<WireLog> for EventLog {
+    fn from(e: WireLog) -> Self {
+        Self {
+            log: Log {
+                address: e.address,
+                data: LogData::new_unchecked(e.topics, e.data),
+            },
+            block_number: e.block_number,
+            transaction_index: e.transaction_index,
+            log_index: e.log_index,
+            transaction_hash: e.transaction_hash,
+            matched_filter: e.matched_filter,
+        }
+    }
+}
+
+impl From<&EventScanResult> for WireScan {
+    fn from(r: &EventScanResult) -> Self {
+        Self {
+            address: r.address,
+            from_block: r.from_block,
+            to_block: r.to_block,
+            logs: r.logs.iter().map(Into::into).collect(),
+            blocks_scanned: r.blocks_scanned,
+            blocks_skipped_by_bloom: r.blocks_skipped_by_bloom,
+            blocks_false_positive: r.blocks_false_positive,
+        }
+    }
+}
+
+impl From<WireScan> for EventScanResult {
+    fn from(r: WireScan) -> Self {
+        Self {
+            address: r.address,
+            from_block: r.from_block,
+            to_block: r.to_block,
+            logs: r.logs.into_iter().map(Into::into).collect(),
+            blocks_scanned: r.blocks_scanned,
+            blocks_skipped_by_bloom: r.blocks_skipped_by_bloom,
+            blocks_false_positive: r.blocks_false_positive,
+        }
+    }
+}
+
+impl PoolOutput {
+    /// Encode as compact bincode.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        bincode::serialize(&WirePool::from(self)).map_err(|e| eyre!("encode pool: {e}"))
+    }
+
+    /// Decode from bytes produced by [`PoolOutput::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let wire: WirePool = bincode::deserialize(bytes).map_err(|e| eyre!("decode pool: {e}"))?;
+        Ok(wire.into())
+    }
+}
+
+impl EventScanResult {
+    /// Encode as compact bincode.
+    pub fn to_bytes(&self) -> Result<Vec<u8>> {
+        bincode::serialize(&WireScan::from(self)).map_err(|e| eyre!("encode scan: {e}"))
+    }
+
+    /// Decode from bytes produced by [`EventScanResult::to_bytes`].
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let wire: WireScan = bincode::deserialize(bytes).map_err(|e| eyre!("decode scan: {e}"))?;
+        Ok(wire.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pool_round_trip() {
+        let tick = Tick {
+            tick: -887220,
+            raw_data: Some("0xdead".to_string()),
+            liquidity_gross: u128::MAX,
+            liquidity_net: i128::MIN,
+            fee_growth_outside_0_x128: U256::MAX,
+            seconds_per_liquidity_outside_x128: U256::from(1u8) << 200,
+            tick_cumulative_outside: -42,
+            initialized: true,
+            raw_slots: vec![(B256::repeat_byte(0x11), U256::from(5u8))],
+            ..Default::default()
+        };
+        let slot0 = Slot0 {
+            sqrt_price_x96: U256::from(1u8) << 96,
+            tick: -3,
+            unlocked: true,
+            ..Default::default()
+        };
+        let bitmaps = vec![Bitmap {
+            word_pos: -58,
+            bitmap: U256::MAX,
+        }];
+        let pool = PoolOutput::new_v4(
+            Address::from([0x11; 20]),
+            B256::from([0x22; 32]),
+            slot0,
+            7,
+            vec![tick],
+            bitmaps,
+        )
+        .with_fee(Some(3000));
+
+        let bytes = pool.to_bytes().unwrap();
+        let decoded = PoolOutput::from_bytes(&bytes).unwrap();
+        assert_eq!(
+            serde_json::to_string(&decoded).unwrap(),
+            serde_json::to_string(&pool).unwrap()
+        );
+        assert!(bytes.len() < serde_json::to_vec(&pool).unwrap().len());
+    }
+
+    #[test]
+    fn test_scan_round_trip() {
+        let log = EventLog {
+            log: Log::new_unchecked(
+                Address::from([0xaa; 20]),
+                vec![B256::from([0x01; 32])],
+                vec![1, 2, 3].into(),
+            ),
+            block_number: 19_000_000,
+            transaction_index: 4,
+            log_index: 9,
+            transaction_hash: None,
+            matched_filter: Some(1),
+        };
+        let scan = EventScanResult {
+            address: Address::from([0xaa; 20]),
+            from_block: 18_000_000,
+            to_block: 19_000_000,
+            logs: vec![log],
+            blocks_scanned: 10,
+            blocks_skipped_by_bloom: 8,
+            blocks_false_positive: 1,
+        };
+
+        let decoded = EventScanResult::from_bytes(&scan.to_bytes().unwrap()).unwrap();
+        assert_eq!(
+            serde_json::to_string(&decoded).unwrap(),
+            serde_json::to_string(&scan).unwrap()
+        );
+    }
+}
@@ -0,0 +1,202 @@
+/// Bounded cache of decoded slot0 values for services that poll the same pools
+/// every block.
+///
+/// The raw slot0 word is still read on every call (one seek), but decoding and
+/// validation only run when it differs from the cached word.
+use alloy_primitives::{Address, B256, U256};
+use eyre::Result;
+use reth_db::transaction::DbTx;
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{
+    readers::{decode_pool_slot0, read_slot0_raw, slot0_storage_key},
+    types::{PoolInput, Slot0},
+};
+
+/// `(contract address, slot0 storage key)`; unique per pool, including V4 pools
+/// that share a PoolManager.
+type CacheKey = (Address, B256);
+
+struct CacheEntry {
+    raw: U256,
+    slot0: Slot0,
+    last_used: u64,
+}
+
+/// [`crate::readers::read_slot0_only`] with a least-recently-used cache of decoded
+/// results, keyed by pool and invalidated whenever the stored slot0 word changes.
+///
+/// Returns the same values as the uncached reader; only the decode is skipped for
+/// unchanged pools. Keep one `CachingReader` alive across polls and pass each new
+/// transaction to [`CachingReader::read_slot0`].
+pub struct CachingReader {
+    capacity: usize,
+    entries: HashMap<CacheKey, CacheEntry>,
+    /// `last_used` stamp -> key, oldest first, for eviction
+    recency: BTreeMap<u64, CacheKey>,
+    clock: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl CachingReader {
+    /// Cache holding at most `capacity` pools (at least one).
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity: capacity.max(1),
+            entries: HashMap::new(),
+            recency: BTreeMap::new(),
+            clock: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Read a V3/V4 pool's slot0, decoding only if its stored word changed since the
+    /// cached read. V4 pools must carry their own `pool_id`.
+    pub fn read_slot0<TX: DbTx>(&mut self, tx: &TX, pool: &PoolInput) -> Result<Slot0> {
+        let key = (pool.address, slot0_storage_key(pool)?);
+        let raw = read_slot0_raw(tx, pool)?;
+        self.get_or_decode(key, raw, |value| decode_pool_slot0(pool, value))
+    }
+
+    /// [`CachingReader::read_slot0`] for each pool, in input order.
+    pub fn read_slot0s<TX: DbTx>(&mut self, tx: &TX, pools: &[PoolInput]) -> Result<Vec<Slot0>> {
+        pools.iter().map(|pool| self.read_slot0(tx, pool)).collect()
+    }
+
+    fn get_or_decode(
+        &mut self,
+        key: CacheKey,
+        raw: U256,
+        decode: impl FnOnce(U256) -> Result<Slot0>,
+    ) -> Result<Slot0> {
+        self.clock += 1;
+        let now = self.clock;
+
+        if let Some(entry) = self.entries.get_mut(&key) {
+            if entry.raw == raw {
+                self.hits += 1;
+                self.recency.remove(&entry.last_used);
+                self.recency.insert(now, key);
+                entry.last_used = now;
+                return Ok(entry.slot0.clone());
+            }
+        }
+
+        self.misses += 1;
+        // Decode errors are returned without touching a stale entry for the pool
+        let slot0 = decode(raw)?;
+
+        if let Some(old) = self.entries.remove(&key) {
+            self.recency.remove(&old.last_used);
+        } else if self.entries.len() >= self.capacity {
+            if let Some((_, oldest)) = self.recency.pop_first() {
+                self.entries.remove(&oldest);
+            }
+        }
+        self.entries.insert(
+            key,
+            CacheEntry {
+                raw,
+                slot0: slot0.clone(),
+                last_used: now,
+            },
+        );
+        self.recency.insert(now, key);
+
+        Ok(slot0)
+    }
+
+    /// Maximum number of cached pools.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of pools currently cached.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// True when nothing is cached.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Reads answered from the cache without decoding.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Reads that had to decode (new pool or changed slot0).
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Drop all cached entries; hit/miss counters are kept.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.recency.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use eyre::eyre;
+
+    fn key(byte: u8) -> CacheKey {
+        (Address::repeat_byte(byte), B256::ZERO)
+    }
+
+    fn decoded(raw: U256) -> Result<Slot0> {
+        Ok(Slot0 {
+            sqrt_price_x96: raw,
+            ..Default::default()
+        })
+    }
+
+    #[test]
+    fn test_decodes_only_on_change() {
+        let mut cache = CachingReader::new(4);
+        let mut decodes = 0;
+        let mut read = |cache: &mut CachingReader, raw: u64| {
+            cache
+                .get_or_decode(key(1), U256::from(raw), |value| {
+                    decodes += 1;
+                    decoded(value)
+                })
+                .unwrap()
+        };
+
+        assert_eq!(read(&mut cache, 7).sqrt_price_x96, U256::from(7));
+        assert_eq!(read(&mut cache, 7).sqrt_price_x96, U256::from(7));
+        assert_eq!(read(&mut cache, 9).sqrt_price_x96, U256::from(9));
+        assert_eq!(decodes, 2);
+        assert_eq!((cache.hits(), cache.misses(), cache.len()), (1, 2, 1));
+    }
+
+    #[test]
+    fn test_evicts_least_recently_used() {
+        let mut cache = CachingReader::new(2);
+        cache.get_or_decode(key(1), U256::from(1), decoded).unwrap();
+        cache.get_or_decode(key(2), U256::from(2), decoded).unwrap();
+        // Touch pool 1 so pool 2 is the oldest
+        cache.get_or_decode(key(1), U256::from(1), decoded).unwrap();
+        cache.get_or_decode(key(3), U256::from(3), decoded).unwrap();
+
+        assert_eq!(cache.len(), 2);
+        assert!(cache.entries.contains_key(&key(1)));
+        assert!(!cache.entries.contains_key(&key(2)));
+        assert!(cache.entries.contains_key(&key(3)));
+        assert_eq!(cache.recency.len(), 2);
+    }
+
+    #[test]
+    fn test_decode_error_is_not_cached() {
+        let mut cache = CachingReader::new(2);
+        let err = cache.get_or_decode(key(1), U256::from(1), |_| Err(eyre!("bad slot0")));
+        assert!(err.is_err());
+        assert!(cache.is_empty());
+    }
+}
@@ -1,8 +1,14 @@
 /// Contract type definitions using Alloy sol! macro
 /// These match the exact Solidity storage layouts for direct DB decoding
 
+use alloy_primitives::{
+    aliases::{I24, I56, U112, U160, U24, U56},
+    U256,
+};
 use alloy_sol_types::sol;
 
+use crate::types::{Reserves, Slot0, Tick};
+
 // UniswapV2 Pair contract storage
 sol! {
     /// UniswapV2 reserves are packed into a single storage slot (slot 8)
@@ -42,12 +48,160 @@ sol! {
     }
 }
 
+// UniswapV2 Pair events (decoded from receipt logs)
+sol! {
+    interface IUniswapV2PairEvents {
+        event Swap(
+            address indexed sender,
+            uint256 amount0In,
+            uint256 amount1In,
+            uint256 amount0Out,
+            uint256 amount1Out,
+            address indexed to
+        );
+
+        event Sync(uint112 reserve0, uint112 reserve1);
+    }
+}
+
+// UniswapV3 Pool events (decoded from receipt logs)
+sol! {
+    interface IUniswapV3PoolEvents {
+        event Swap(
+            address indexed sender,
+            address indexed recipient,
+            int256 amount0,
+            int256 amount1,
+            uint160 sqrtPriceX96,
+            uint128 liquidity,
+            int24 tick
+        );
+    }
+}
+
+// UniswapV4 PoolManager events (singleton: every pool's events, keyed by indexed poolId)
+sol! {
+    #[allow(clippy::too_many_arguments)]
+    interface IPoolManagerEvents {
+        event Swap(
+            bytes32 indexed id,
+            address indexed sender,
+            int128 amount0,
+            int128 amount1,
+            uint160 sqrtPriceX96,
+            uint128 liquidity,
+            int24 tick,
+            uint24 fee
+        );
+
+        event ModifyLiquidity(
+            bytes32 indexed id,
+            address indexed sender,
+            int24 tickLower,
+            int24 tickUpper,
+            int256 liquidityDelta,
+            bytes32 salt
+        );
+    }
+}
+
 // These types can be used for both storage decoding AND RPC calls
 // They provide automatic ABI encoding/decoding via alloy-sol-types
 
+// Conversions between the sol! structs (ABI path) and the decoded types
+// (bit-unpacking path in `decoding`), so either route yields the same values.
+// Values wider than the Solidity field are truncated, as storage packing would.
+
+impl From<ReservesStorage> for Reserves {
+    fn from(value: ReservesStorage) -> Self {
+        Self {
+            raw_data: None,
+            reserve0: value.reserve0.to::<u128>(),
+            reserve1: value.reserve1.to::<u128>(),
+            block_timestamp_last: value.blockTimestampLast,
+        }
+    }
+}
+
+impl From<Reserves> for ReservesStorage {
+    fn from(value: Reserves) -> Self {
+        Self {
+            reserve0: U112::wrapping_from(value.reserve0),
+            reserve1: U112::wrapping_from(value.reserve1),
+            blockTimestampLast: value.block_timestamp_last,
+        }
+    }
+}
+
+impl From<Slot0Storage> for Slot0 {
+    fn from(value: Slot0Storage) -> Self {
+        Self {
+            raw_data: None,
+            sqrt_price_x96: U256::from(value.sqrtPriceX96),
+            tick: value.tick.as_i32(),
+            observation_index: value.observationIndex,
+            observation_cardinality: value.observationCardinality,
+            observation_cardinality_next: value.observationCardinalityNext,
+            fee_protocol: value.feeProtocol,
+            unlocked: value.unlocked,
+        }
+    }
+}
+
+impl From<Slot0> for Slot0Storage {
+    fn from(value: Slot0) -> Self {
+        Self {
+            sqrtPriceX96: U160::wrapping_from(value.sqrt_price_x96),
+            tick: I24::from_raw(U24::wrapping_from(value.tick)),
+            observationIndex: value.observation_index,
+            observationCardinality: value.observation_cardinality,
+            observationCardinalityNext: value.observation_cardinality_next,
+            feeProtocol: value.fee_protocol,
+            unlocked: value.unlocked,
+        }
+    }
+}
+
+/// `TickInfo` does not carry the tick index, so it is paired with it here.
+impl From<(i32, TickInfo)> for Tick {
+    fn from((tick, value): (i32, TickInfo)) -> Self {
+        Self {
+            tick,
+            raw_data: None,
+            liquidity_gross: value.liquidityGross,
+            liquidity_net: value.liquidityNet,
+            fee_growth_outside_0_x128: value.feeGrowthOutside0X128,
+            fee_growth_outside_1_x128: value.feeGrowthOutside1X128,
+            tick_cumulative_outside: value.tickCumulativeOutside.as_i64(),
+            seconds_per_liquidity_outside_x128: U256::from(value.secondsPerLiquidityOutsideX128),
+            seconds_outside: value.secondsOutside,
+            initialized: value.initialized,
+            raw_slots: Vec::new(),
+        }
+    }
+}
+
+impl From<Tick> for TickInfo {
+    fn from(value: Tick) -> Self {
+        Self {
+            liquidityGross: value.liquidity_gross,
+            liquidityNet: value.liquidity_net,
+            feeGrowthOutside0X128: value.fee_growth_outside_0_x128,
+            feeGrowthOutside1X128: value.fee_growth_outside_1_x128,
+            tickCumulativeOutside: I56::from_raw(U56::wrapping_from(value.tick_cumulative_outside)),
+            secondsPerLiquidityOutsideX128: U160::wrapping_from(
+                value.seconds_per_liquidity_outside_x128,
+            ),
+            secondsOutside: value.seconds_outside,
+            initialized: value.initialized,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::decoding::decode_slot0;
     use alloy_sol_types::SolValue;
 
     #[test]
@@ -87,4 +241,60 @@ mod tests {
         assert_eq!(decoded.tick, alloy_primitives::Signed::<24, 1>::try_from(-100i32).unwrap());
         assert_eq!(decoded.unlocked, true);
     }
+
+    #[test]
+    fn test_slot0_bit_unpacked_matches_sol_decoded() {
+        let sqrt_price = U256::from(79228162514264337593543950336u128);
+        let packed = sqrt_price
+            | (U256::from(0xFFFF9Cu32) << 160) // tick = -100
+            | (U256::from(5u32) << 184)
+            | (U256::from(10u32) << 200)
+            | (U256::from(20u32) << 216)
+            | (U256::from(3u32) << 232)
+            | (U256::from(1u32) << 240);
+
+        let mut unpacked = decode_slot0(packed).unwrap();
+        unpacked.raw_data = None;
+
+        let storage = Slot0Storage {
+            sqrtPriceX96: U160::from(79228162514264337593543950336u128),
+            tick: I24::try_from(-100i32).unwrap(),
+            observationIndex: 5,
+            observationCardinality: 10,
+            observationCardinalityNext: 20,
+            feeProtocol: 3,
+            unlocked: true,
+        };
+        let sol_decoded: Slot0 = Slot0Storage::abi_decode(&storage.abi_encode()).unwrap().into();
+
+        assert_eq!(unpacked, sol_decoded);
+
+        // And back again
+        let round_trip = Slot0Storage::from(sol_decoded);
+        assert_eq!(round_trip.tick, storage.tick);
+        assert_eq!(round_trip.sqrtPriceX96, storage.sqrtPriceX96);
+    }
+
+    #[test]
+    fn test_reserves_and_tick_round_trip() {
+        let reserves = Reserves {
+            raw_data: None,
+            reserve0: 1000,
+            reserve1: 2000,
+            block_timestamp_last: 123456,
+        };
+        let back: Reserves = ReservesStorage::from(reserves.clone()).into();
+        assert_eq!(back, reserves);
+
+        let tick = Tick {
+            tick: -60,
+            liquidity_gross: 500,
+            liquidity_net: -500,
+            tick_cumulative_outside: -42,
+            initialized: true,
+            ..Default::default()
+        };
+        let back: Tick = (tick.tick, TickInfo::from(tick.clone())).into();
+        assert_eq!(back, tick);
+    }
 }
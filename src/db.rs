@@ -0,0 +1,131 @@
+/// Database open options for the read-only reth MDBX environment.
+///
+/// The path-based helpers in the crate root open the database with
+/// `OpenOptions::default()`; the `*_with_options` variants accept a custom
+/// configuration, which is forwarded to Reth's `DatabaseArguments`.
+/// [`check_capabilities`] reports which of the tables the crate reads are populated.
+use eyre::Result;
+use reth_db::{
+    cursor::DbCursorRO, mdbx::DatabaseArguments, open_db_read_only, tables, transaction::DbTx,
+    DatabaseEnv,
+};
+use std::path::{Path, PathBuf};
+
+/// Options used when opening the reth database read-only.
+///
+/// Unset fields keep Reth's defaults.
+///
+/// # Example
+/// ```no_run
+/// use scrape_rethdb_data::OpenOptions;
+///
+/// // Running alongside the node: allow more concurrent readers, never lock exclusively
+/// let options = OpenOptions::new().with_max_readers(1024).with_exclusive(false);
+/// let db = options.open("/path/to/reth/db").unwrap();
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct OpenOptions {
+    /// Maximum number of concurrent MDBX readers (Reth default: 32000)
+    pub max_readers: Option<u64>,
+    /// Open the environment with an exclusive lock (`MDBX_EXCLUSIVE`)
+    pub exclusive: Option<bool>,
+}
+
+impl OpenOptions {
+    /// Options with all Reth defaults
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the maximum number of concurrent readers
+    pub fn with_max_readers(mut self, max_readers: u64) -> Self {
+        self.max_readers = Some(max_readers);
+        self
+    }
+
+    /// Set whether to open the environment with an exclusive lock
+    pub fn with_exclusive(mut self, exclusive: bool) -> Self {
+        self.exclusive = Some(exclusive);
+        self
+    }
+
+    /// Convert into Reth's `DatabaseArguments`
+    pub fn database_arguments(&self) -> DatabaseArguments {
+        DatabaseArguments::default()
+            .with_max_readers(self.max_readers)
+            .with_exclusive(self.exclusive)
+    }
+
+    /// Open the database at `db_path` read-only with these options
+    pub fn open(&self, db_path: impl AsRef<Path>) -> Result<DatabaseEnv> {
+        open_db_read_only(db_path.as_ref(), self.database_arguments())
+    }
+}
+
+/// Directory of the node's static files for the MDBX database at `db_path`
+///
+/// Reth keeps them beside the database: `<datadir>/static_files` next to
+/// `<datadir>/db`. The path-based event scanners read headers and receipts from
+/// there when MDBX no longer holds them.
+pub fn static_files_dir(db_path: impl AsRef<Path>) -> PathBuf {
+    let db_path = db_path.as_ref();
+    db_path.parent().unwrap_or(db_path).join("static_files")
+}
+
+/// Which of the tables the crate reads are non-empty in a database.
+///
+/// A pruned or static-file-backed node can be missing some of them, in which case
+/// the corresponding reads return zeros or nothing rather than failing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Capabilities {
+    /// `PlainStorageState`: current pool state
+    pub plain_state: bool,
+    /// `StoragesHistory`: index of historical storage changes
+    pub storages_history: bool,
+    /// `StorageChangeSets`: pre-change values for historical reads
+    pub storage_changesets: bool,
+    /// `Headers`: block headers with log blooms, read by the event scanners
+    pub headers: bool,
+    /// `Receipts`: transaction receipts with the logs themselves
+    pub receipts: bool,
+}
+
+impl Capabilities {
+    /// Current pool state can be read (`collect_pool_data` and friends)
+    pub fn pool_state(&self) -> bool {
+        self.plain_state
+    }
+
+    /// Historical reads (`collect_pool_data_at_block`) can see past changes
+    pub fn historical(&self) -> bool {
+        self.plain_state && self.storages_history && self.storage_changesets
+    }
+
+    /// Event scans can find logs
+    pub fn events(&self) -> bool {
+        self.headers && self.receipts
+    }
+}
+
+/// Probe whether each table the crate relies on has at least one row
+pub fn check_capabilities<TX: DbTx>(tx: &TX) -> Result<Capabilities> {
+    Ok(Capabilities {
+        plain_state: tx.cursor_read::<tables::PlainStorageState>()?.first()?.is_some(),
+        storages_history: tx.cursor_read::<tables::StoragesHistory>()?.first()?.is_some(),
+        storage_changesets: tx.cursor_read::<tables::StorageChangeSets>()?.first()?.is_some(),
+        headers: tx.cursor_read::<tables::Headers>()?.first()?.is_some(),
+        receipts: tx.cursor_read::<tables::Receipts>()?.first()?.is_some(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_static_files_dir() {
+        let expected = Path::new("/data/reth/static_files");
+        assert_eq!(static_files_dir("/data/reth/db"), expected);
+        assert_eq!(static_files_dir("/data/reth/db/"), expected);
+    }
+}
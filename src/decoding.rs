@@ -4,10 +4,36 @@
 /// - Storage packing: Variables packed RIGHT to LEFT (LSB to MSB)
 /// - ABI encoding: Each value padded to 32 bytes, concatenated
 
-use alloy_primitives::U256;
-use eyre::Result;
+use alloy_primitives::{B256, U256};
+use eyre::{eyre, Result};
 
-use crate::types::{Reserves, Slot0, Tick};
+use crate::{
+    storage::V2ReserveLayout,
+    tick_math,
+    types::{Observation, Position, Reserves, Slot0, Tick},
+};
+
+/// Decode a `uint128 liquidity` slot
+///
+/// Keeps the lower 128 bits and reports whether any upper bit was set, which
+/// points to a misaligned slot or a fork packing another field above liquidity.
+pub fn decode_liquidity(storage_value: U256) -> (u128, bool) {
+    let mask = (U256::from(1u8) << 128) - U256::from(1u8);
+    let lower: U256 = storage_value & mask;
+    (lower.to::<u128>(), lower != storage_value)
+}
+
+/// Sign-extend the lowest `BITS` bits of `raw` as a two's-complement `intBITS`
+///
+/// Bits above `BITS` are ignored, so callers can pass a shifted slot without masking.
+/// `BITS` must be in `1..=128`; results for `BITS <= 32` always fit an `i32`.
+pub fn decode_signed<const BITS: usize>(raw: U256) -> i128 {
+    const { assert!(BITS > 0 && BITS <= 128) };
+    let mask = (U256::from(1u8) << BITS) - U256::from(1u8);
+    let lower: U256 = raw & mask;
+    let shift = 128 - BITS;
+    ((lower.to::<u128>() << shift) as i128) >> shift
+}
 
 /// Decode V2 reserves from packed storage
 ///
@@ -41,6 +67,41 @@ pub fn decode_v2_reserves(storage_value: U256) -> Result<Reserves> {
     })
 }
 
+/// Decode V2 reserves read from the slots of `layout` (see [`V2ReserveLayout::slots`])
+///
+/// Packed layouts decode like [`decode_v2_reserves`]. Unpacked layouts hold full
+/// `uint256` words; reserves above `u128::MAX` or a timestamp above `u32::MAX` are
+/// rejected rather than truncated, and the timestamp is zero when it has no slot.
+pub fn decode_v2_reserves_with_layout(
+    layout: V2ReserveLayout,
+    values: &[U256],
+) -> Result<Reserves> {
+    let expected = layout.slots().len();
+    if values.len() != expected {
+        return Err(eyre!("expected {expected} reserve slot values, got {}", values.len()));
+    }
+
+    match layout {
+        V2ReserveLayout::Packed { .. } => decode_v2_reserves(values[0]),
+        V2ReserveLayout::Unpacked { .. } => {
+            let reserve = |value: U256| {
+                u128::try_from(value).map_err(|_| eyre!("reserve {value} does not fit in u128"))
+            };
+            let block_timestamp_last = match values.get(2) {
+                Some(value) => u32::try_from(*value)
+                    .map_err(|_| eyre!("blockTimestampLast {value} does not fit in u32"))?,
+                None => 0,
+            };
+            Ok(Reserves {
+                raw_data: None,
+                reserve0: reserve(values[0])?,
+                reserve1: reserve(values[1])?,
+                block_timestamp_last,
+            })
+        }
+    }
+}
+
 /// Decode V3/V4 Slot0 from packed storage
 ///
 /// Solidity:
@@ -72,15 +133,7 @@ pub fn decode_slot0(storage_value: U256) -> Result<Slot0> {
     let sqrt_price_x96 = storage_value & sqrt_price_mask;
 
     // tick: bits 160-183 (24 bits, signed)
-    let tick_u256: U256 = (storage_value >> 160) & U256::from(0xFFFFFFu32);
-    let tick_raw = tick_u256.to::<u32>();
-    // Handle sign extension for int24
-    let tick = if tick_raw & 0x800000 != 0 {
-        // Negative number - sign extend
-        (tick_raw | 0xFF000000) as i32
-    } else {
-        tick_raw as i32
-    };
+    let tick = decode_signed::<24>(storage_value >> 160) as i32;
 
     // observationIndex: bits 184-199 (16 bits)
     let obs_idx_u256: U256 = (storage_value >> 184) & U256::from(0xFFFFu32);
@@ -114,6 +167,119 @@ pub fn decode_slot0(storage_value: U256) -> Result<Slot0> {
     })
 }
 
+/// Whether slot0's price and tick are within the ranges a pool can reach:
+/// `sqrtPriceX96` in `[MIN_SQRT_RATIO, MAX_SQRT_RATIO)` and tick in `[MIN_TICK, MAX_TICK]`
+pub fn slot0_in_bounds(slot0: &Slot0) -> bool {
+    (tick_math::MIN_SQRT_RATIO..tick_math::MAX_SQRT_RATIO).contains(&slot0.sqrt_price_x96)
+        && (tick_math::MIN_TICK..=tick_math::MAX_TICK).contains(&slot0.tick)
+}
+
+/// Position of a packed field within a storage word.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BitField {
+    /// Offset of the lowest bit (0 = least significant)
+    pub offset: u16,
+    /// Width in bits
+    pub bits: u16,
+}
+
+impl BitField {
+    pub const fn new(offset: u16, bits: u16) -> Self {
+        Self { offset, bits }
+    }
+
+    /// Extract this field from `value`, checking it fits in `max_bits`
+    fn extract(&self, value: U256, name: &str, max_bits: u16) -> Result<U256> {
+        if self.bits == 0 || self.bits > max_bits || self.offset as u32 + self.bits as u32 > 256 {
+            return Err(eyre!(
+                "invalid slot0 layout for {name}: offset {} bits {} (max {max_bits} bits)",
+                self.offset,
+                self.bits
+            ));
+        }
+        let mask = (U256::from(1u8) << self.bits as usize) - U256::from(1u8);
+        Ok((value >> self.offset as usize) & mask)
+    }
+}
+
+/// Bit layout of a V3-style slot0 word, for forks that reorder or omit fields.
+///
+/// Fields set to `None` are not present in the word and decode as zero/`false`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Slot0Layout {
+    pub sqrt_price_x96: BitField,
+    /// Signed; sign-extended from `bits`
+    pub tick: BitField,
+    pub observation_index: Option<BitField>,
+    pub observation_cardinality: Option<BitField>,
+    pub observation_cardinality_next: Option<BitField>,
+    pub fee_protocol: Option<BitField>,
+    pub unlocked: Option<BitField>,
+}
+
+/// Canonical Uniswap V3 slot0 packing (see [`decode_slot0`])
+pub const UNISWAP_V3_SLOT0_LAYOUT: Slot0Layout = Slot0Layout {
+    sqrt_price_x96: BitField::new(0, 160),
+    tick: BitField::new(160, 24),
+    observation_index: Some(BitField::new(184, 16)),
+    observation_cardinality: Some(BitField::new(200, 16)),
+    observation_cardinality_next: Some(BitField::new(216, 16)),
+    fee_protocol: Some(BitField::new(232, 8)),
+    unlocked: Some(BitField::new(240, 1)),
+};
+
+/// PancakeSwap V3 slot0: `uint32 feeProtocol` does not fit after bit 232, so
+/// `feeProtocol` and `unlocked` spill into the next storage slot
+pub const PANCAKESWAP_V3_SLOT0_LAYOUT: Slot0Layout = Slot0Layout {
+    fee_protocol: None,
+    unlocked: None,
+    ..UNISWAP_V3_SLOT0_LAYOUT
+};
+
+impl Default for Slot0Layout {
+    fn default() -> Self {
+        UNISWAP_V3_SLOT0_LAYOUT
+    }
+}
+
+/// Decode slot0 using an explicit bit layout
+///
+/// With [`UNISWAP_V3_SLOT0_LAYOUT`] this is equivalent to [`decode_slot0`].
+/// Errors if a field overflows the word or is wider than its `Slot0` type.
+pub fn decode_slot0_with_layout(storage_value: U256, layout: &Slot0Layout) -> Result<Slot0> {
+    let raw_hex = format!("0x{:064x}", storage_value);
+
+    let optional = |field: Option<BitField>, name: &str, max_bits: u16| -> Result<U256> {
+        field.map_or(Ok(U256::ZERO), |f| f.extract(storage_value, name, max_bits))
+    };
+
+    let sqrt_price_x96 = layout.sqrt_price_x96.extract(storage_value, "sqrt_price_x96", 256)?;
+
+    // Sign extend the tick from its declared width
+    let tick_bits = layout.tick.bits;
+    let tick_raw = layout.tick.extract(storage_value, "tick", 32)?.to::<u32>();
+    let tick = ((tick_raw << (32 - tick_bits)) as i32) >> (32 - tick_bits);
+
+    let observation_index = optional(layout.observation_index, "observation_index", 16)?;
+    let observation_cardinality =
+        optional(layout.observation_cardinality, "observation_cardinality", 16)?;
+    let observation_cardinality_next =
+        optional(layout.observation_cardinality_next, "observation_cardinality_next", 16)?;
+    let fee_protocol = optional(layout.fee_protocol, "fee_protocol", 8)?;
+    let unlocked = optional(layout.unlocked, "unlocked", 1)?;
+
+    Ok(Slot0 {
+        raw_data: Some(raw_hex),
+        sqrt_price_x96,
+        tick,
+        observation_index: observation_index.to::<u16>(),
+        observation_cardinality: observation_cardinality.to::<u16>(),
+        observation_cardinality_next: observation_cardinality_next.to::<u16>(),
+        fee_protocol: fee_protocol.to::<u8>(),
+        unlocked: unlocked != U256::ZERO,
+    })
+}
+
 /// Decode tick info from storage
 ///
 /// Uniswap V3/V4 Tick storage layout (slot 0):
@@ -133,16 +299,7 @@ pub fn decode_tick_info(tick: i32, storage_value: U256) -> Result<Tick> {
     let liquidity_gross = liquidity_gross_u256.to::<u128>();
 
     // Extract liquidityNet (upper 128 bits, signed int128)
-    let liquidity_net_u256: U256 = storage_value >> 128;
-    let liquidity_net_raw = liquidity_net_u256.to::<u128>();
-
-    // Convert to signed int128 using two's complement
-    let liquidity_net = if liquidity_net_raw > (u128::MAX / 2) {
-        // Negative number in two's complement
-        -(((!liquidity_net_raw).wrapping_add(1)) as i128)
-    } else {
-        liquidity_net_raw as i128
-    };
+    let liquidity_net = decode_signed::<128>(storage_value >> 128);
 
     Ok(Tick {
         tick,
@@ -155,13 +312,210 @@ pub fn decode_tick_info(tick: i32, storage_value: U256) -> Result<Tick> {
         seconds_per_liquidity_outside_x128: U256::ZERO,
         seconds_outside: 0,
         initialized,
+        raw_slots: Vec::new(),
     })
 }
 
+/// Decode a full tick from every slot of its `Tick.Info`, as `(slot, value)` pairs
+///
+/// Slot 0 is decoded as in [`decode_tick_info`]; then:
+/// - Slot 1: feeGrowthOutside0X128 (uint256)
+/// - Slot 2: feeGrowthOutside1X128 (uint256)
+/// - Slot 3 (V3 only):
+///   - Bits 0-55: tickCumulativeOutside (int56)
+///   - Bits 56-215: secondsPerLiquidityOutsideX128 (uint160)
+///   - Bits 216-247: secondsOutside (uint32)
+///   - Bit 248: initialized (bool)
+///
+/// V4 ticks have 3 slots, V3 ticks 4. The pairs are kept in `raw_slots`.
+pub fn decode_tick_full(tick: i32, slots: &[(B256, U256)]) -> Result<Tick> {
+    if slots.len() != 3 && slots.len() != 4 {
+        return Err(eyre!("a tick spans 3 (V4) or 4 (V3) slots, got {}", slots.len()));
+    }
+
+    let mut decoded = decode_tick_info(tick, slots[0].1)?;
+    decoded.fee_growth_outside_0_x128 = slots[1].1;
+    decoded.fee_growth_outside_1_x128 = slots[2].1;
+
+    if let Some(&(_, packed)) = slots.get(3) {
+        // tickCumulativeOutside: bits 0-55 (56 bits, signed)
+        decoded.tick_cumulative_outside = decode_signed::<56>(packed) as i64;
+
+        // secondsPerLiquidityOutsideX128: bits 56-215 (160 bits)
+        let spl_mask = (U256::from(1u128) << 160) - U256::from(1u128);
+        decoded.seconds_per_liquidity_outside_x128 = (packed >> 56) & spl_mask;
+
+        // secondsOutside: bits 216-247
+        let seconds_u256: U256 = (packed >> 216) & U256::from(u32::MAX);
+        decoded.seconds_outside = seconds_u256.to::<u32>();
+
+        // initialized: bit 248
+        let initialized_u256: U256 = (packed >> 248) & U256::from(1u32);
+        decoded.initialized = initialized_u256 != U256::ZERO;
+    }
+
+    decoded.raw_slots = slots.to_vec();
+    Ok(decoded)
+}
+
+/// Decode a V3 oracle observation from storage
+///
+/// Uniswap V3 Observation storage layout (single slot):
+/// - Bits 0-31: blockTimestamp (uint32)
+/// - Bits 32-87: tickCumulative (int56)
+/// - Bits 88-247: secondsPerLiquidityCumulativeX128 (uint160)
+/// - Bit 248: initialized (bool)
+pub fn decode_observation(index: u16, storage_value: U256) -> Result<Observation> {
+    let raw_hex = format!("0x{:064x}", storage_value);
+
+    // blockTimestamp: bits 0-31
+    let timestamp_u256: U256 = storage_value & U256::from(u32::MAX);
+    let block_timestamp = timestamp_u256.to::<u32>();
+
+    // tickCumulative: bits 32-87 (56 bits, signed)
+    let tick_cumulative = decode_signed::<56>(storage_value >> 32) as i64;
+
+    // secondsPerLiquidityCumulativeX128: bits 88-247 (160 bits)
+    let spl_mask = (U256::from(1u128) << 160) - U256::from(1u128);
+    let seconds_per_liquidity_cumulative_x128: U256 = (storage_value >> 88) & spl_mask;
+
+    // initialized: bit 248
+    let initialized_u256: U256 = (storage_value >> 248) & U256::from(1u32);
+    let initialized = initialized_u256 != U256::ZERO;
+
+    Ok(Observation {
+        index,
+        raw_data: Some(raw_hex),
+        block_timestamp,
+        tick_cumulative,
+        seconds_per_liquidity_cumulative_x128,
+        initialized,
+    })
+}
+
+/// Decode a V3 `Position.Info` from its 4 consecutive storage slots
+///
+/// Uniswap V3 Position.Info storage layout:
+/// - Slot 0: liquidity (uint128, lower 128 bits)
+/// - Slot 1: feeGrowthInside0LastX128 (uint256)
+/// - Slot 2: feeGrowthInside1LastX128 (uint256)
+/// - Slot 3: tokensOwed0 (bits 0-127) | tokensOwed1 (bits 128-255)
+pub fn decode_position(key: B256, slots: [U256; 4]) -> Position {
+    let (liquidity, _) = decode_liquidity(slots[0]);
+    let (tokens_owed_0, _) = decode_liquidity(slots[3]);
+    let (tokens_owed_1, _) = decode_liquidity(slots[3] >> 128);
+
+    Position {
+        key,
+        liquidity,
+        fee_growth_inside_0_last_x128: slots[1],
+        fee_growth_inside_1_last_x128: slots[2],
+        tokens_owed_0,
+        tokens_owed_1,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_decode_signed_boundaries() {
+        let all_ones = |bits: usize| (U256::from(1u8) << bits) - U256::from(1u8);
+
+        // int24
+        assert_eq!(decode_signed::<24>(U256::from(0x800000u32)), -8_388_608);
+        assert_eq!(decode_signed::<24>(U256::from(0x7FFFFFu32)), 8_388_607);
+        assert_eq!(decode_signed::<24>(all_ones(24)), -1);
+        assert_eq!(decode_signed::<24>(U256::ZERO), 0);
+        // Bits above the width are ignored
+        assert_eq!(decode_signed::<24>(U256::from(0x1_7FFFFFu64)), 8_388_607);
+
+        // int56
+        assert_eq!(decode_signed::<56>(U256::from(1u64 << 55)), -(1i128 << 55));
+        assert_eq!(decode_signed::<56>(all_ones(56)), -1);
+
+        // int128
+        assert_eq!(decode_signed::<128>(U256::from(1u8) << 127), i128::MIN);
+        assert_eq!(decode_signed::<128>(U256::from(i128::MAX as u128)), i128::MAX);
+        assert_eq!(decode_signed::<128>(all_ones(128)), -1);
+        assert_eq!(decode_signed::<128>(U256::MAX), -1);
+
+        // Routed through the slot decoders
+        let min_net = decode_tick_info(0, U256::from(1u8) << 255).unwrap();
+        assert_eq!(min_net.liquidity_net, i128::MIN);
+        let min_tick = decode_slot0(U256::from(0x800000u32) << 160).unwrap();
+        assert_eq!(min_tick.tick, -8_388_608);
+    }
+
+    #[test]
+    fn test_decode_v2_reserves_with_layout() {
+        let packed = U256::from(5u8) | (U256::from(7u8) << 112) | (U256::from(9u8) << 224);
+        let reserves =
+            decode_v2_reserves_with_layout(V2ReserveLayout::default(), &[packed]).unwrap();
+        assert_eq!(
+            (reserves.reserve0, reserves.reserve1, reserves.block_timestamp_last),
+            (5, 7, 9)
+        );
+
+        let unpacked = V2ReserveLayout::Unpacked {
+            reserve0_slot: 8,
+            reserve1_slot: 9,
+            timestamp_slot: Some(10),
+        };
+        let full = U256::from(u128::MAX);
+        let reserves =
+            decode_v2_reserves_with_layout(unpacked, &[full, U256::from(3u8), U256::from(4u8)])
+                .unwrap();
+        assert_eq!(reserves.reserve0, u128::MAX);
+        assert_eq!((reserves.reserve1, reserves.block_timestamp_last), (3, 4));
+
+        // Wrong slot count, and reserves that would be truncated, are rejected
+        assert!(decode_v2_reserves_with_layout(unpacked, &[full, full]).is_err());
+        let too_big = full + U256::from(1u8);
+        assert!(decode_v2_reserves_with_layout(unpacked, &[too_big, full, U256::ZERO]).is_err());
+
+        let no_timestamp = V2ReserveLayout::Unpacked {
+            reserve0_slot: 8,
+            reserve1_slot: 9,
+            timestamp_slot: None,
+        };
+        assert_eq!(no_timestamp.slots().len(), 2);
+        let reserves = decode_v2_reserves_with_layout(no_timestamp, &[full, full]).unwrap();
+        assert_eq!(reserves.block_timestamp_last, 0);
+    }
+
+    #[test]
+    fn test_decode_tick_full() {
+        let slot = |i: u8| B256::with_last_byte(i);
+        let packed = U256::from((1u64 << 56) - 42) // tickCumulativeOutside = -42
+            | (U256::from(7u8) << 56)
+            | (U256::from(300u32) << 216)
+            | (U256::from(1u8) << 248);
+        let slots = [
+            (slot(0), U256::from(500u128)),
+            (slot(1), U256::from(11u8)),
+            (slot(2), U256::from(22u8)),
+            (slot(3), packed),
+        ];
+
+        let v3 = decode_tick_full(-60, &slots).unwrap();
+        assert_eq!(v3.liquidity_gross, 500);
+        assert_eq!(v3.fee_growth_outside_0_x128, U256::from(11u8));
+        assert_eq!(v3.fee_growth_outside_1_x128, U256::from(22u8));
+        assert_eq!(v3.tick_cumulative_outside, -42);
+        assert_eq!(v3.seconds_per_liquidity_outside_x128, U256::from(7u8));
+        assert_eq!(v3.seconds_outside, 300);
+        assert!(v3.initialized);
+        assert_eq!(v3.raw_slots, slots.to_vec());
+
+        let v4 = decode_tick_full(-60, &slots[..3]).unwrap();
+        assert_eq!(v4.tick_cumulative_outside, 0);
+        assert_eq!(v4.raw_slots.len(), 3);
+
+        assert!(decode_tick_full(-60, &slots[..2]).is_err());
+    }
+
     #[test]
     fn test_v2_reserves_decoding() {
         // Example: reserve0=1000, reserve1=2000, timestamp=123456
@@ -178,6 +532,74 @@ mod tests {
         assert_eq!(decoded.block_timestamp_last, 123456);
     }
 
+    #[test]
+    fn test_decode_position() {
+        let key = B256::from([0x33; 32]);
+        let owed = U256::from(7u8) | (U256::from(u128::MAX) << 128);
+        let position =
+            decode_position(key, [U256::from(1000u64), U256::from(1u8) << 200, U256::from(5u8), owed]);
+
+        assert_eq!(position.key, key);
+        assert_eq!(position.liquidity, 1000);
+        assert_eq!(position.fee_growth_inside_0_last_x128, U256::from(1u8) << 200);
+        assert_eq!(position.fee_growth_inside_1_last_x128, U256::from(5u8));
+        assert_eq!(position.tokens_owed_0, 7);
+        assert_eq!(position.tokens_owed_1, u128::MAX);
+    }
+
+    #[test]
+    fn test_slot0_in_bounds() {
+        let slot0 = |sqrt_price_x96, tick| Slot0 { sqrt_price_x96, tick, ..Default::default() };
+
+        assert!(slot0_in_bounds(&slot0(U256::from(1u8) << 96, 0)));
+        assert!(slot0_in_bounds(&slot0(tick_math::MIN_SQRT_RATIO, tick_math::MIN_TICK)));
+        assert!(!slot0_in_bounds(&slot0(tick_math::MAX_SQRT_RATIO, 0)));
+        assert!(!slot0_in_bounds(&slot0(U256::ZERO, 0)));
+        assert!(!slot0_in_bounds(&slot0(U256::from(1u8) << 96, tick_math::MAX_TICK + 1)));
+    }
+
+    #[test]
+    fn test_slot0_with_layout() {
+        let packed = U256::from(1000u128)
+            | (U256::from(0xFFFF9Cu32) << 160) // tick -100
+            | (U256::from(5u32) << 184)
+            | (U256::from(10u32) << 200)
+            | (U256::from(20u32) << 216)
+            | (U256::from(3u32) << 232)
+            | (U256::from(1u32) << 240);
+
+        let canonical = decode_slot0_with_layout(packed, &UNISWAP_V3_SLOT0_LAYOUT).unwrap();
+        assert_eq!(canonical, decode_slot0(packed).unwrap());
+        assert_eq!(canonical.tick, -100);
+
+        // PancakeSwap layout ignores the bits past observationCardinalityNext
+        let pancake = decode_slot0_with_layout(packed, &PANCAKESWAP_V3_SLOT0_LAYOUT).unwrap();
+        assert_eq!(pancake.observation_cardinality_next, 20);
+        assert_eq!(pancake.fee_protocol, 0);
+        assert!(!pancake.unlocked);
+
+        let bad = Slot0Layout { tick: BitField::new(250, 24), ..Default::default() };
+        assert!(decode_slot0_with_layout(packed, &bad).is_err());
+    }
+
+    #[test]
+    fn test_observation_decoding() {
+        // tickCumulative = -1000 as int56 two's complement
+        let tick_cumulative = U256::from((-1000i64 as u64) & ((1u64 << 56) - 1)) << 32;
+        let packed = U256::from(1_700_000_000u32)
+            | tick_cumulative
+            | (U256::from(12345u64) << 88)
+            | (U256::from(1u8) << 248);
+
+        let decoded = decode_observation(7, packed).unwrap();
+
+        assert_eq!(decoded.index, 7);
+        assert_eq!(decoded.block_timestamp, 1_700_000_000);
+        assert_eq!(decoded.tick_cumulative, -1000);
+        assert_eq!(decoded.seconds_per_liquidity_cumulative_x128, U256::from(12345u64));
+        assert!(decoded.initialized);
+    }
+
     #[test]
     fn test_slot0_decoding() {
         // Create a test slot0 value
@@ -215,4 +637,13 @@ mod tests {
 
         assert_eq!(decoded.tick, -100);
     }
+
+    #[test]
+    fn test_decode_liquidity_masks_upper_bits() {
+        assert_eq!(decode_liquidity(U256::from(12345u64)), (12345, false));
+        assert_eq!(decode_liquidity(U256::from(u128::MAX)), (u128::MAX, false));
+
+        let packed = (U256::from(7u8) << 200) | U256::from(42u8);
+        assert_eq!(decode_liquidity(packed), (42, true));
+    }
 }
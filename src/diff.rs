@@ -0,0 +1,196 @@
+/// Snapshot diffing for incremental indexing.
+///
+/// Compares two `PoolOutput`s of the same pool (typically at two blocks) and
+/// reports which ticks and bitmap words changed plus the scalar state deltas.
+use alloy_primitives::{I256, U256};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+use crate::types::{PoolOutput, Tick};
+
+/// A tick present in both snapshots whose data changed.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickChange {
+    pub before: Tick,
+    pub after: Tick,
+}
+
+/// A bitmap word whose value changed (a missing word counts as zero).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BitmapChange {
+    pub word_pos: i16,
+    pub before: U256,
+    pub after: U256,
+}
+
+/// Difference between two snapshots of a pool, from `self` (before) to `other` (after).
+///
+/// Tick and bitmap lists are sorted by tick index / word position. Scalar deltas are
+/// `after - before` and `None` when either snapshot lacks the field (e.g. V2 pools).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PoolDiff {
+    pub added_ticks: Vec<Tick>,
+    pub removed_ticks: Vec<Tick>,
+    pub modified_ticks: Vec<TickChange>,
+    pub changed_bitmaps: Vec<BitmapChange>,
+    pub tick_delta: Option<i32>,
+    pub liquidity_delta: Option<I256>,
+    pub sqrt_price_x96_delta: Option<I256>,
+}
+
+impl PoolDiff {
+    /// True when nothing changed between the two snapshots.
+    pub fn is_empty(&self) -> bool {
+        self.added_ticks.is_empty()
+            && self.removed_ticks.is_empty()
+            && self.modified_ticks.is_empty()
+            && self.changed_bitmaps.is_empty()
+            && self.tick_delta.unwrap_or(0) == 0
+            && self.liquidity_delta.unwrap_or(I256::ZERO).is_zero()
+            && self.sqrt_price_x96_delta.unwrap_or(I256::ZERO).is_zero()
+    }
+}
+
+impl PoolOutput {
+    /// Compare this snapshot (before) with `other` (after).
+    pub fn diff(&self, other: &PoolOutput) -> PoolDiff {
+        let before: BTreeMap<i32, &Tick> = self.ticks.iter().map(|t| (t.tick, t)).collect();
+        let after: BTreeMap<i32, &Tick> = other.ticks.iter().map(|t| (t.tick, t)).collect();
+
+        let mut diff = PoolDiff::default();
+        for (tick, old) in &before {
+            match after.get(tick) {
+                None => diff.removed_ticks.push((*old).clone()),
+                Some(new) if new != old => diff.modified_ticks.push(TickChange {
+                    before: (*old).clone(),
+                    after: (*new).clone(),
+                }),
+                Some(_) => {}
+            }
+        }
+        diff.added_ticks = after
+            .iter()
+            .filter(|(tick, _)| !before.contains_key(tick))
+            .map(|(_, t)| (*t).clone())
+            .collect();
+
+        let before_words: BTreeMap<i16, U256> = self
+            .bitmaps
+            .iter()
+            .map(|b| (b.word_pos, b.bitmap))
+            .collect();
+        let after_words: BTreeMap<i16, U256> = other
+            .bitmaps
+            .iter()
+            .map(|b| (b.word_pos, b.bitmap))
+            .collect();
+        let mut word_positions: Vec<i16> = before_words
+            .keys()
+            .chain(after_words.keys())
+            .copied()
+            .collect();
+        word_positions.sort_unstable();
+        word_positions.dedup();
+        for word_pos in word_positions {
+            let old = before_words.get(&word_pos).copied().unwrap_or_default();
+            let new = after_words.get(&word_pos).copied().unwrap_or_default();
+            if old != new {
+                diff.changed_bitmaps.push(BitmapChange {
+                    word_pos,
+                    before: old,
+                    after: new,
+                });
+            }
+        }
+
+        if let (Some(old), Some(new)) = (&self.slot0, &other.slot0) {
+            diff.tick_delta = Some(new.tick - old.tick);
+            diff.sqrt_price_x96_delta = Some(signed_delta(old.sqrt_price_x96, new.sqrt_price_x96));
+        }
+        if let (Some(old), Some(new)) = (self.liquidity, other.liquidity) {
+            diff.liquidity_delta = Some(signed_delta(U256::from(old), U256::from(new)));
+        }
+
+        diff
+    }
+}
+
+/// `after - before` for values below 2^160 (sqrtPriceX96, liquidity), so the
+/// result always fits in an `I256`.
+fn signed_delta(before: U256, after: U256) -> I256 {
+    I256::from_raw(after).wrapping_sub(I256::from_raw(before))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Bitmap, Slot0};
+    use alloy_primitives::Address;
+
+    fn tick(index: i32, liquidity_net: i128) -> Tick {
+        Tick {
+            tick: index,
+            liquidity_gross: liquidity_net.unsigned_abs(),
+            liquidity_net,
+            initialized: true,
+            ..Default::default()
+        }
+    }
+
+    fn snapshot(
+        tick_index: i32,
+        liquidity: u128,
+        ticks: Vec<Tick>,
+        words: &[(i16, u64)],
+    ) -> PoolOutput {
+        let slot0 = Slot0 {
+            sqrt_price_x96: U256::from(1000u64 + tick_index as u64),
+            tick: tick_index,
+            ..Default::default()
+        };
+        let bitmaps = words
+            .iter()
+            .map(|(word_pos, bits)| Bitmap {
+                word_pos: *word_pos,
+                bitmap: U256::from(*bits),
+            })
+            .collect();
+        PoolOutput::new_v3(Address::ZERO, slot0, liquidity, ticks, bitmaps)
+    }
+
+    #[test]
+    fn test_diff_reports_tick_bitmap_and_scalar_changes() {
+        let a = snapshot(
+            10,
+            500,
+            vec![tick(-60, 100), tick(0, 50), tick(60, -150)],
+            &[(-1, 1), (0, 3)],
+        );
+        let b = snapshot(
+            12,
+            400,
+            vec![tick(-60, 100), tick(0, 70), tick(120, -170)],
+            &[(0, 5), (1, 1)],
+        );
+
+        let diff = a.diff(&b);
+        assert_eq!(diff.added_ticks, vec![tick(120, -170)]);
+        assert_eq!(diff.removed_ticks, vec![tick(60, -150)]);
+        assert_eq!(
+            diff.modified_ticks,
+            vec![TickChange {
+                before: tick(0, 50),
+                after: tick(0, 70)
+            }]
+        );
+        let words: Vec<i16> = diff.changed_bitmaps.iter().map(|b| b.word_pos).collect();
+        assert_eq!(words, vec![-1, 0, 1]);
+        assert_eq!(diff.changed_bitmaps[0].after, U256::ZERO);
+        assert_eq!(diff.tick_delta, Some(2));
+        assert_eq!(diff.liquidity_delta, Some(I256::try_from(-100).unwrap()));
+        assert_eq!(diff.sqrt_price_x96_delta, Some(I256::try_from(2).unwrap()));
+        assert!(!diff.is_empty());
+
+        assert!(a.diff(&a).is_empty());
+    }
+}
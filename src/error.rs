@@ -0,0 +1,120 @@
+/// Typed errors for conditions callers may want to handle programmatically.
+///
+/// Public functions return `eyre::Result`; these variants are wrapped in the
+/// `eyre::Report` and can be recovered with `report.downcast_ref::<ScrapeError>()`.
+use alloy_primitives::{Address, B256, U256};
+use thiserror::Error;
+
+use crate::types::BlockNumber;
+
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ScrapeError {
+    /// `StoragesHistory` records a change to `slot` at `block`, but the matching
+    /// `StorageChangeSets` row is missing (typically a node that is still syncing).
+    /// Falling back to plain state here would return the current value, not the
+    /// historical one.
+    #[error("changeset gap: history index has a change to slot {slot} at block {block}, but no changeset row")]
+    ChangesetGap { block: BlockNumber, slot: B256 },
+    /// Same as [`ScrapeError::ChangesetGap`] for account state: `AccountsHistory`
+    /// records a change to `address` at `block`, but the `AccountChangeSets` row is
+    /// missing.
+    #[error("changeset gap: account history has a change to {address} at block {block}, but no changeset row")]
+    AccountChangesetGap { block: BlockNumber, address: Address },
+    /// Bits set in the tick bitmap point at ticks with no stored data. With a correct
+    /// `tick_spacing` every set bit is an initialized tick, so this almost always means
+    /// the configured spacing differs from the pool's.
+    #[error("tick spacing mismatch for pool {pool}: {missing} of {expected} bitmap ticks have no data (is tick_spacing {tick_spacing} correct?)")]
+    TickSpacingMismatch {
+        pool: Address,
+        tick_spacing: i32,
+        missing: usize,
+        expected: usize,
+    },
+    /// Decoded slot0 is outside the range any pool can reach: `sqrtPriceX96` not in
+    /// `[MIN_SQRT_RATIO, MAX_SQRT_RATIO)` or tick not in `[MIN_TICK, MAX_TICK]`.
+    /// Usually the slot was read from a non-pool address or with the wrong layout.
+    #[error("implausible slot0 for pool {pool}: sqrtPriceX96 {sqrt_price_x96}, tick {tick} (wrong address or storage layout?)")]
+    ImplausibleSlot0 {
+        pool: Address,
+        sqrt_price_x96: U256,
+        tick: i32,
+    },
+    /// `tick_spacing` must be positive; zero would divide by zero when mapping
+    /// ticks to bitmap words.
+    #[error("invalid tick_spacing {tick_spacing} for pool {pool}: must be greater than zero")]
+    InvalidTickSpacing { pool: Address, tick_spacing: i32 },
+    /// `BlockBodyIndices` has `block`, but its `table` data is in neither MDBX nor the
+    /// static files the scan was given. Newer Reth nodes move headers and receipts
+    /// into static files (or prune receipts); scanning anyway would report zero logs
+    /// instead of failing.
+    #[error("block {block} has no {table} data in MDBX or the given static files (scan through a StaticFileTx, or the data was pruned)")]
+    MissingBlockData { block: BlockNumber, table: &'static str },
+    /// `from_block > to_block`, or the range starts past the newest block in the
+    /// database (`tip`). Scanning it would silently return nothing.
+    #[error("invalid block range {from_block}..={to_block} (database tip is block {tip})")]
+    InvalidBlockRange {
+        from_block: BlockNumber,
+        to_block: BlockNumber,
+        tip: BlockNumber,
+    },
+    /// Reading `pool` failed with an untyped error (reported by
+    /// `collect_pool_data_lenient`); `reason` is the full error chain.
+    #[error("pool {pool}: {reason}")]
+    PoolFailed { pool: Address, reason: String },
+}
+
+impl ScrapeError {
+    /// Typed error behind `report`, or [`ScrapeError::PoolFailed`] for `pool`.
+    pub(crate) fn for_pool(pool: Address, report: eyre::Report) -> Self {
+        match report.downcast_ref::<ScrapeError>() {
+            Some(err) => err.clone(),
+            None => ScrapeError::PoolFailed { pool, reason: format!("{report:#}") },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_downcast_from_report() {
+        let err: eyre::Report = ScrapeError::ChangesetGap { block: 42, slot: B256::ZERO }.into();
+        assert_eq!(
+            err.downcast_ref::<ScrapeError>(),
+            Some(&ScrapeError::ChangesetGap { block: 42, slot: B256::ZERO })
+        );
+    }
+
+    #[test]
+    fn test_for_pool_keeps_typed_errors() {
+        let pool = Address::repeat_byte(0x11);
+        let typed: eyre::Report = ScrapeError::InvalidTickSpacing { pool, tick_spacing: 0 }.into();
+        assert_eq!(
+            ScrapeError::for_pool(pool, typed),
+            ScrapeError::InvalidTickSpacing { pool, tick_spacing: 0 }
+        );
+
+        let untyped = eyre::eyre!("no tick_spacing").wrap_err("reading V3 pool");
+        assert_eq!(
+            ScrapeError::for_pool(pool, untyped),
+            ScrapeError::PoolFailed { pool, reason: "reading V3 pool: no tick_spacing".into() }
+        );
+    }
+
+    #[test]
+    fn test_account_changeset_gap_message() {
+        let err = ScrapeError::AccountChangesetGap { block: 7, address: Address::ZERO };
+        assert_eq!(
+            err.to_string(),
+            "changeset gap: account history has a change to \
+             0x0000000000000000000000000000000000000000 at block 7, but no changeset row"
+        );
+    }
+
+    #[test]
+    fn test_invalid_block_range_message() {
+        let err = ScrapeError::InvalidBlockRange { from_block: 10, to_block: 5, tip: 100 };
+        assert_eq!(err.to_string(), "invalid block range 10..=5 (database tip is block 100)");
+    }
+}
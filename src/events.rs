@@ -1,17 +1,29 @@
 /// Event log queries from Reth database
 ///
 /// This module provides efficient event log scanning using:
-/// - Direct access to Receipts table
+/// - Direct access to the MDBX Headers and Receipts tables, and to Reth's static files
+///   for headers and receipts that have moved there (see [`StaticFileTx`])
 /// - Bloom filter optimization to skip irrelevant blocks
 /// - Parallel block processing capabilities
 
-use alloy_primitives::{Address, BloomInput, Log, B256};
-
-#[cfg(test)]
-use alloy_primitives::Bloom;
-use eyre::Result;
+use alloy_primitives::{Address, Bloom, BloomInput, Log, B256, U256};
+use alloy_sol_types::SolEvent;
+use eyre::{eyre, Result};
 use reth_db::{cursor::DbCursorRO, tables, transaction::DbTx};
+use reth_ethereum_primitives::EthPrimitives;
+use reth_provider::{
+    providers::StaticFileProvider, HeaderProvider, ReceiptProvider, StaticFileSegment,
+};
 use serde::{Deserialize, Serialize};
+use std::{
+    collections::{BTreeMap, HashMap},
+    ops::RangeInclusive,
+    path::Path,
+};
+
+use crate::contracts::{IPoolManagerEvents, IUniswapV2PairEvents, IUniswapV3PoolEvents};
+use crate::error::ScrapeError;
+use crate::topics;
 
 // BlockNumber is just u64 in Reth
 type BlockNumber = u64;
@@ -25,9 +37,36 @@ pub struct EventLog {
     pub block_number: BlockNumber,
     /// Transaction index within the block
     pub transaction_index: u64,
+    /// Index of the log within the block (counting every log, not just matches)
+    #[serde(default)]
+    pub log_index: u64,
     /// Transaction hash (if available)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_hash: Option<B256>,
+    /// Index of the topic set that matched (only for `scan_events_any`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub matched_filter: Option<usize>,
+}
+
+impl EventLog {
+    /// Render as an `eth_getLogs`-shaped JSON object
+    ///
+    /// Numbers are 0x-prefixed hex quantities as in the JSON-RPC spec. `blockHash`
+    /// is not tracked by the scanner and `transactionHash` is only known when the
+    /// scan resolved it, so both may be `null`.
+    pub fn to_rpc_log(&self) -> serde_json::Value {
+        serde_json::json!({
+            "address": self.log.address,
+            "topics": self.log.data.topics(),
+            "data": self.log.data.data,
+            "blockNumber": format!("{:#x}", self.block_number),
+            "blockHash": null,
+            "transactionHash": self.transaction_hash,
+            "transactionIndex": format!("{:#x}", self.transaction_index),
+            "logIndex": format!("{:#x}", self.log_index),
+            "removed": false,
+        })
+    }
 }
 
 /// Result of scanning for events in a block range
@@ -45,6 +84,788 @@ pub struct EventScanResult {
     pub blocks_scanned: u64,
     /// Number of blocks skipped by bloom filter
     pub blocks_skipped_by_bloom: u64,
+    /// Number of blocks that passed the bloom filter but had no matching logs (zero
+    /// when the bloom filter is disabled, see [`ScanConfig::use_bloom`])
+    #[serde(default)]
+    pub blocks_false_positive: u64,
+}
+
+impl EventScanResult {
+    /// Append another scan of the same address, e.g. the next chunk of a range
+    ///
+    /// Logs are kept in (block, log index) order, re-sorting when `other` overlaps or
+    /// precedes `self`; the block range widens to cover both and the block counters
+    /// are summed. Fails if the addresses differ.
+    pub fn merge(&mut self, other: EventScanResult) -> Result<()> {
+        if self.address != other.address {
+            return Err(eyre!(
+                "cannot merge scans of different addresses: {} and {}",
+                self.address,
+                other.address
+            ));
+        }
+
+        let in_order = match (self.logs.last(), other.logs.first()) {
+            (Some(last), Some(first)) => {
+                (last.block_number, last.log_index) <= (first.block_number, first.log_index)
+            }
+            _ => true,
+        };
+        self.logs.extend(other.logs);
+        if !in_order {
+            self.logs.sort_by_key(|log| (log.block_number, log.log_index));
+        }
+
+        self.from_block = self.from_block.min(other.from_block);
+        self.to_block = self.to_block.max(other.to_block);
+        self.blocks_scanned += other.blocks_scanned;
+        self.blocks_skipped_by_bloom += other.blocks_skipped_by_bloom;
+        self.blocks_false_positive += other.blocks_false_positive;
+        Ok(())
+    }
+}
+
+/// Result of counting events in a block range (no log bodies retained)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EventCountResult {
+    /// Address that was queried
+    pub address: Address,
+    /// Start block (inclusive)
+    pub from_block: BlockNumber,
+    /// End block (inclusive)
+    pub to_block: BlockNumber,
+    /// Number of matching logs
+    pub count: u64,
+    /// Number of blocks scanned
+    pub blocks_scanned: u64,
+    /// Number of blocks skipped by bloom filter
+    pub blocks_skipped_by_bloom: u64,
+}
+
+/// A transaction with at least one matching log, without the logs themselves
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TouchingTx {
+    pub block_number: BlockNumber,
+    /// Index of the transaction within its block
+    pub transaction_index: u64,
+    /// Number of matching logs the transaction emitted
+    pub log_count: u64,
+}
+
+/// Aggregated V3 swap activity within one time window (an OHLC-style candle in ticks)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SwapBucket {
+    /// Bucket start (unix seconds, aligned to the window size)
+    pub start_time: u64,
+    /// Number of swaps in the bucket
+    pub swap_count: u64,
+    /// Sum of absolute token0 amounts
+    pub volume0: U256,
+    /// Sum of absolute token1 amounts
+    pub volume1: U256,
+    /// Tick after the first swap in the bucket
+    pub open_tick: i32,
+    /// Tick after the last swap in the bucket
+    pub close_tick: i32,
+    /// Highest post-swap tick in the bucket
+    pub high_tick: i32,
+    /// Lowest post-swap tick in the bucket
+    pub low_tick: i32,
+}
+
+/// Tuning knobs for [`scan_events_with_config`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanConfig {
+    /// Skip blocks whose header bloom rules out a match (default). When false every
+    /// block's receipts are read and `blocks_skipped_by_bloom` and
+    /// `blocks_false_positive` stay zero; slower, but a ground truth for auditing
+    /// suspected bloom mismatches.
+    pub use_bloom: bool,
+}
+
+impl Default for ScanConfig {
+    fn default() -> Self {
+        Self { use_bloom: true }
+    }
+}
+
+/// Running totals for one address across every run of a resumable scan
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanCounters {
+    /// Number of matching logs found
+    pub logs_found: u64,
+    /// Number of blocks scanned
+    pub blocks_scanned: u64,
+    /// Number of blocks skipped by bloom filter
+    pub blocks_skipped_by_bloom: u64,
+    /// Number of blocks that passed the bloom filter but had no matching logs
+    pub blocks_false_positive: u64,
+}
+
+/// Resumable position of an incremental scan
+///
+/// Created with [`ScanState::new`] and advanced by [`resume_scan`]. Serialize it to
+/// persist progress between runs; counters keep accumulating across resumes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanState {
+    addresses: Vec<Address>,
+    topics: Option<Vec<B256>>,
+    /// Block the scan started at (states saved before this field read as 0)
+    #[serde(default)]
+    from_block: BlockNumber,
+    next_block: BlockNumber,
+    counters: BTreeMap<Address, ScanCounters>,
+}
+
+impl ScanState {
+    /// Start a scan of `addresses` at `from_block`
+    pub fn new(addresses: Vec<Address>, from_block: BlockNumber, topics: Option<Vec<B256>>) -> Self {
+        let (addresses, _) = dedup_addresses(&addresses);
+        let counters = addresses
+            .iter()
+            .map(|addr| (*addr, ScanCounters::default()))
+            .collect();
+        Self {
+            addresses,
+            topics,
+            from_block,
+            next_block: from_block,
+            counters,
+        }
+    }
+
+    /// Addresses being scanned (deduplicated, in first-seen order)
+    pub fn addresses(&self) -> &[Address] {
+        &self.addresses
+    }
+
+    /// First block the next [`resume_scan`] will read
+    pub fn next_block(&self) -> BlockNumber {
+        self.next_block
+    }
+
+    /// Last block already processed, or `None` before the first run
+    pub fn last_block(&self) -> Option<BlockNumber> {
+        (self.next_block > self.from_block).then(|| self.next_block - 1)
+    }
+
+    /// Cumulative counters for `address`
+    pub fn counters(&self, address: &Address) -> Option<&ScanCounters> {
+        self.counters.get(address)
+    }
+
+    /// Fold one run's results (aligned with `addresses`) into the state
+    fn record(&mut self, results: &[EventScanResult], to_block: BlockNumber) {
+        for result in results {
+            let counters = self.counters.entry(result.address).or_default();
+            counters.logs_found += result.logs.len() as u64;
+            counters.blocks_scanned += result.blocks_scanned;
+            counters.blocks_skipped_by_bloom += result.blocks_skipped_by_bloom;
+            counters.blocks_false_positive += result.blocks_false_positive;
+        }
+        self.next_block = to_block + 1;
+    }
+}
+
+/// Bloom hit-rate sample from [`estimate_scan_blocks_with_logs`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ScanEstimate {
+    /// Blocks with a header in the sample range
+    pub blocks_sampled: u64,
+    /// Sampled blocks whose bloom may contain the address (receipt reads needed)
+    pub bloom_hits: u64,
+}
+
+impl ScanEstimate {
+    /// Fraction of sampled blocks that passed the bloom filter (0 when nothing was sampled)
+    pub fn hit_rate(&self) -> f64 {
+        if self.blocks_sampled == 0 {
+            return 0.0;
+        }
+        self.bloom_hits as f64 / self.blocks_sampled as f64
+    }
+
+    /// Expected number of blocks needing receipt reads in a range of `range_blocks` blocks
+    pub fn estimate_blocks_with_logs(&self, range_blocks: u64) -> u64 {
+        (self.hit_rate() * range_blocks as f64).ceil() as u64
+    }
+}
+
+/// Check whether every topic in the filter may be present in a block's bloom
+///
+/// An empty filter always matches.
+fn bloom_contains_topics(bloom: &Bloom, topics: &[B256]) -> bool {
+    topics
+        .iter()
+        .all(|topic| bloom.contains_input(BloomInput::Raw(topic.as_slice())))
+}
+
+/// Check whether a log's topics match the filter positionally (topic0, topic1, ...)
+///
+/// An empty filter always matches.
+fn log_matches_topics(log: &Log, topics: &[B256]) -> bool {
+    let log_topics = log.data.topics();
+    topics.len() <= log_topics.len() && topics.iter().zip(log_topics).all(|(a, b)| a == b)
+}
+
+/// Group logs by block number, keeping their original (in-block) order
+fn group_logs_by_block(logs: Vec<EventLog>) -> BTreeMap<BlockNumber, Vec<EventLog>> {
+    let mut grouped: BTreeMap<BlockNumber, Vec<EventLog>> = BTreeMap::new();
+    for log in logs {
+        grouped.entry(log.block_number).or_default().push(log);
+    }
+    grouped
+}
+
+/// Whether `address` may be present in a block's bloom
+fn bloom_contains_address(bloom: &Bloom, address: &Address) -> bool {
+    bloom.contains_input(BloomInput::Raw(address.as_slice()))
+}
+
+/// Where a block walk reads headers, block bodies and receipts from
+trait BlockSource {
+    /// Logs bloom from the header of `block`, `None` if the header is missing
+    fn bloom(&mut self, block: BlockNumber) -> Result<Option<Bloom>>;
+    /// `(first_tx_num, tx_count)` of `block`, `None` if the body is missing
+    fn body(&mut self, block: BlockNumber) -> Result<Option<(u64, u64)>>;
+    /// Logs of global transaction `tx_num`, `None` if the receipt is missing
+    fn receipt_logs(&mut self, tx_num: u64) -> Result<Option<Vec<Log>>>;
+}
+
+/// [`BlockSource`] over the MDBX `Headers`, `BlockBodyIndices` and `Receipts` tables
+struct MdbxBlocks<TX: DbTx> {
+    headers: TX::Cursor<tables::Headers>,
+    bodies: TX::Cursor<tables::BlockBodyIndices>,
+    receipts: TX::Cursor<tables::Receipts>,
+}
+
+impl<TX: DbTx> MdbxBlocks<TX> {
+    fn new(tx: &TX) -> Result<Self> {
+        Ok(Self {
+            headers: tx.cursor_read::<tables::Headers>()?,
+            bodies: tx.cursor_read::<tables::BlockBodyIndices>()?,
+            receipts: tx.cursor_read::<tables::Receipts>()?,
+        })
+    }
+
+    /// Timestamp from the header of `block`, `None` if the header is missing
+    fn timestamp(&mut self, block: BlockNumber) -> Result<Option<u64>> {
+        Ok(self.headers.seek_exact(block)?.map(|(_, header)| header.timestamp))
+    }
+}
+
+impl<TX: DbTx> BlockSource for MdbxBlocks<TX> {
+    fn bloom(&mut self, block: BlockNumber) -> Result<Option<Bloom>> {
+        Ok(self.headers.seek_exact(block)?.map(|(_, header)| header.logs_bloom))
+    }
+
+    fn body(&mut self, block: BlockNumber) -> Result<Option<(u64, u64)>> {
+        Ok(self
+            .bodies
+            .seek_exact(block)?
+            .map(|(_, body)| (body.first_tx_num, body.tx_count)))
+    }
+
+    fn receipt_logs(&mut self, tx_num: u64) -> Result<Option<Vec<Log>>> {
+        Ok(self.receipts.seek_exact(tx_num)?.map(|(_, receipt)| receipt.logs))
+    }
+}
+
+/// Database access of the event scanners: an MDBX transaction and, on nodes that keep
+/// headers and receipts in static files, Reth's [`StaticFileProvider`]
+///
+/// Every [`DbTx`] is a `ScanTx` that reads MDBX only; wrap it in [`StaticFileTx`] to
+/// read static files too.
+pub trait ScanTx {
+    type Tx: DbTx;
+
+    /// The MDBX transaction (block bodies, senders and the canonical tip)
+    fn mdbx(&self) -> &Self::Tx;
+
+    /// Static files holding the headers and receipts MDBX no longer has
+    fn static_files(&self) -> Option<&StaticFileProvider<EthPrimitives>>;
+}
+
+impl<TX: DbTx> ScanTx for TX {
+    type Tx = TX;
+
+    fn mdbx(&self) -> &TX {
+        self
+    }
+
+    fn static_files(&self) -> Option<&StaticFileProvider<EthPrimitives>> {
+        None
+    }
+}
+
+/// An MDBX read transaction paired with the node's static files
+///
+/// Reth writes headers (and, depending on the node, receipts) to static files and
+/// only keeps recent blocks in MDBX. Scanning through a `StaticFileTx` reads each
+/// header and receipt from MDBX if it is there and from the static files otherwise;
+/// `BlockBodyIndices` and `TransactionSenders` are always read from MDBX.
+#[derive(Debug)]
+pub struct StaticFileTx<TX> {
+    tx: TX,
+    static_files: Option<StaticFileProvider<EthPrimitives>>,
+}
+
+impl<TX: DbTx> StaticFileTx<TX> {
+    /// Pair `tx` with already opened static files; `None` reads MDBX only
+    pub fn new(tx: TX, static_files: Option<StaticFileProvider<EthPrimitives>>) -> Self {
+        Self { tx, static_files }
+    }
+
+    /// Pair `tx` with the static files in `dir`; see [`open_static_files`]
+    pub fn open(tx: TX, dir: impl AsRef<Path>) -> Result<Self> {
+        Ok(Self::new(tx, open_static_files(dir)?))
+    }
+}
+
+/// Open the static files in `dir` (`<datadir>/static_files`) read-only, `None` if
+/// there is no such directory
+pub fn open_static_files(
+    dir: impl AsRef<Path>,
+) -> Result<Option<StaticFileProvider<EthPrimitives>>> {
+    let dir = dir.as_ref();
+    if !dir.is_dir() {
+        return Ok(None);
+    }
+    Ok(Some(StaticFileProvider::read_only(dir, false)?))
+}
+
+impl<TX: DbTx> ScanTx for StaticFileTx<TX> {
+    type Tx = TX;
+
+    fn mdbx(&self) -> &TX {
+        &self.tx
+    }
+
+    fn static_files(&self) -> Option<&StaticFileProvider<EthPrimitives>> {
+        self.static_files.as_ref()
+    }
+}
+
+/// [`BlockSource`] over MDBX backed by Reth's static files: headers and receipts are
+/// read from MDBX when present and from the static files otherwise, block bodies
+/// always from MDBX
+struct StaticFileBlocks<'a, TX: DbTx> {
+    mdbx: MdbxBlocks<TX>,
+    static_files: &'a StaticFileProvider<EthPrimitives>,
+}
+
+impl<TX: DbTx> StaticFileBlocks<'_, TX> {
+    fn timestamp(&mut self, block: BlockNumber) -> Result<Option<u64>> {
+        if let Some(timestamp) = self.mdbx.timestamp(block)? {
+            return Ok(Some(timestamp));
+        }
+        Ok(self.static_files.header_by_number(block)?.map(|header| header.timestamp))
+    }
+}
+
+impl<TX: DbTx> BlockSource for StaticFileBlocks<'_, TX> {
+    fn bloom(&mut self, block: BlockNumber) -> Result<Option<Bloom>> {
+        if let Some(bloom) = self.mdbx.bloom(block)? {
+            return Ok(Some(bloom));
+        }
+        Ok(self.static_files.header_by_number(block)?.map(|header| header.logs_bloom))
+    }
+
+    fn body(&mut self, block: BlockNumber) -> Result<Option<(u64, u64)>> {
+        self.mdbx.body(block)
+    }
+
+    fn receipt_logs(&mut self, tx_num: u64) -> Result<Option<Vec<Log>>> {
+        if let Some(logs) = self.mdbx.receipt_logs(tx_num)? {
+            return Ok(Some(logs));
+        }
+        Ok(self.static_files.receipt(tx_num)?.map(|receipt| receipt.logs))
+    }
+}
+
+/// The [`BlockSource`] of a [`ScanTx`]: MDBX alone, or backed by static files
+enum TxBlocks<'a, TX: DbTx> {
+    Mdbx(MdbxBlocks<TX>),
+    StaticFiles(StaticFileBlocks<'a, TX>),
+}
+
+impl<'a, TX: DbTx> TxBlocks<'a, TX> {
+    fn new<T: ScanTx<Tx = TX>>(tx: &'a T) -> Result<Self> {
+        let mdbx = MdbxBlocks::new(tx.mdbx())?;
+        Ok(match tx.static_files() {
+            Some(static_files) => Self::StaticFiles(StaticFileBlocks { mdbx, static_files }),
+            None => Self::Mdbx(mdbx),
+        })
+    }
+
+    /// Timestamp from the header of `block`, `None` if the header is missing
+    fn timestamp(&mut self, block: BlockNumber) -> Result<Option<u64>> {
+        match self {
+            Self::Mdbx(source) => source.timestamp(block),
+            Self::StaticFiles(source) => source.timestamp(block),
+        }
+    }
+}
+
+impl<TX: DbTx> BlockSource for TxBlocks<'_, TX> {
+    fn bloom(&mut self, block: BlockNumber) -> Result<Option<Bloom>> {
+        match self {
+            Self::Mdbx(source) => source.bloom(block),
+            Self::StaticFiles(source) => source.bloom(block),
+        }
+    }
+
+    fn body(&mut self, block: BlockNumber) -> Result<Option<(u64, u64)>> {
+        match self {
+            Self::Mdbx(source) => source.body(block),
+            Self::StaticFiles(source) => source.body(block),
+        }
+    }
+
+    fn receipt_logs(&mut self, tx_num: u64) -> Result<Option<Vec<Log>>> {
+        match self {
+            Self::Mdbx(source) => source.receipt_logs(tx_num),
+            Self::StaticFiles(source) => source.receipt_logs(tx_num),
+        }
+    }
+}
+
+/// Where a receipt log sits in the chain
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct LogPosition {
+    block_number: BlockNumber,
+    /// Index of the transaction within its block
+    transaction_index: u64,
+    /// Global transaction number (the `Receipts` / `TransactionSenders` key)
+    tx_num: u64,
+    /// Index of the log within the block, counting every log
+    log_index: u64,
+}
+
+impl LogPosition {
+    fn event_log(self, log: Log) -> EventLog {
+        EventLog {
+            log,
+            block_number: self.block_number,
+            transaction_index: self.transaction_index,
+            log_index: self.log_index,
+            transaction_hash: None, // We'd need TransactionBlocks table for this
+            matched_filter: None,
+        }
+    }
+}
+
+/// What a block walk looks for; every scan variant is one of these
+trait LogVisitor {
+    /// Whether a block with this bloom may hold a log the visitor matches
+    fn bloom_may_match(&self, bloom: &Bloom) -> bool;
+    /// Inspect one log of a block whose receipts are read; `true` if it matched
+    fn visit(&mut self, position: LogPosition, log: Log) -> Result<bool>;
+    /// Called after each block whose receipts were read, with the bloom it passed;
+    /// `None` when the prefilter is off, so a block without matches is no false positive
+    fn end_block(&mut self, _bloom: Option<&Bloom>) {}
+}
+
+/// [`LogVisitor`] made of a bloom check and a per-log closure
+struct FnVisitor<B, F> {
+    bloom: B,
+    visit: F,
+}
+
+impl<B, F> LogVisitor for FnVisitor<B, F>
+where
+    B: Fn(&Bloom) -> bool,
+    F: FnMut(LogPosition, Log) -> Result<bool>,
+{
+    fn bloom_may_match(&self, bloom: &Bloom) -> bool {
+        (self.bloom)(bloom)
+    }
+
+    fn visit(&mut self, position: LogPosition, log: Log) -> Result<bool> {
+        (self.visit)(position, log)
+    }
+}
+
+/// Block counters of one [`walk_blocks`] pass
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+struct WalkStats {
+    /// Every block in the range, whether or not it has a header
+    blocks_scanned: u64,
+    blocks_skipped_by_bloom: u64,
+    /// Blocks that passed the bloom check, but whose receipts had no match
+    blocks_false_positive: u64,
+    /// Logs the visitor matched
+    logs_matched: u64,
+}
+
+/// The block walk behind every scan
+///
+/// For each block in `[from_block, to_block]`: report `progress`, skip it if the
+/// header bloom rules out the visitor (unless `config.use_bloom` is off), then hand
+/// every log of its receipts to `visitor` in block order. Blocks without a header are
+/// skipped when the bloom is in use. Range checks are up to the caller.
+#[cfg_attr(
+    feature = "tracing",
+    tracing::instrument(
+        level = "debug",
+        skip_all,
+        fields(
+            from_block,
+            to_block,
+            blocks_scanned = tracing::field::Empty,
+            blocks_skipped_by_bloom = tracing::field::Empty,
+            logs = tracing::field::Empty,
+        )
+    )
+)]
+fn walk_blocks(
+    source: &mut impl BlockSource,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    config: &ScanConfig,
+    mut progress: Option<&mut dyn FnMut(BlockNumber)>,
+    visitor: &mut impl LogVisitor,
+) -> Result<WalkStats> {
+    let mut stats = WalkStats::default();
+
+    for block_num in from_block..=to_block {
+        #[cfg(feature = "tracing")]
+        let _block_span = tracing::trace_span!("scan_block", block = block_num).entered();
+
+        if let Some(progress) = progress.as_mut() {
+            progress(block_num);
+        }
+        stats.blocks_scanned += 1;
+
+        // Ground-truth mode reads every block's receipts
+        let bloom = if config.use_bloom {
+            let Some(bloom) = source.bloom(block_num)? else {
+                continue;
+            };
+            if !visitor.bloom_may_match(&bloom) {
+                stats.blocks_skipped_by_bloom += 1;
+                continue;
+            }
+            Some(bloom)
+        } else {
+            None
+        };
+
+        let mut block_matched = false;
+        if let Some((first_tx_num, tx_count)) = source.body(block_num)? {
+            let mut log_index = 0u64;
+            for transaction_index in 0..tx_count {
+                let tx_num = first_tx_num + transaction_index;
+                let Some(logs) = source.receipt_logs(tx_num)? else {
+                    continue;
+                };
+
+                for log in logs {
+                    let position = LogPosition {
+                        block_number: block_num,
+                        transaction_index,
+                        tx_num,
+                        log_index,
+                    };
+                    log_index += 1;
+                    if visitor.visit(position, log)? {
+                        block_matched = true;
+                        stats.logs_matched += 1;
+                    }
+                }
+            }
+        }
+
+        visitor.end_block(bloom.as_ref());
+        if bloom.is_some() && !block_matched {
+            stats.blocks_false_positive += 1;
+        }
+    }
+
+    #[cfg(feature = "tracing")]
+    tracing::Span::current()
+        .record("blocks_scanned", stats.blocks_scanned)
+        .record("blocks_skipped_by_bloom", stats.blocks_skipped_by_bloom)
+        .record("logs", stats.logs_matched);
+
+    Ok(stats)
+}
+
+/// Check the range like every public scan, then walk it over `tx`'s blocks
+fn scan_blocks<TX: ScanTx>(
+    tx: &TX,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    config: &ScanConfig,
+    progress: Option<&mut dyn FnMut(BlockNumber)>,
+    visitor: &mut impl LogVisitor,
+) -> Result<WalkStats> {
+    ensure_block_data(tx, from_block, to_block)?;
+    validate_block_range(tx, from_block, to_block)?;
+    walk_blocks(&mut TxBlocks::new(tx)?, from_block, to_block, config, progress, visitor)
+}
+
+/// [`EventScanResult`] of one walk over `[from_block, to_block]`
+fn scan_result(
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    logs: Vec<EventLog>,
+    stats: WalkStats,
+) -> EventScanResult {
+    EventScanResult {
+        address,
+        from_block,
+        to_block,
+        logs,
+        blocks_scanned: stats.blocks_scanned,
+        blocks_skipped_by_bloom: stats.blocks_skipped_by_bloom,
+        blocks_false_positive: stats.blocks_false_positive,
+    }
+}
+
+/// Scan for event logs like [`scan_events`], grouped by block
+///
+/// Each block's logs keep their order within the block; blocks without matching
+/// logs are absent from the map.
+pub fn scan_events_by_block<TX: ScanTx>(
+    tx: &TX,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+) -> Result<BTreeMap<BlockNumber, Vec<EventLog>>> {
+    let result = scan_events(tx, address, from_block, to_block, topics)?;
+    Ok(group_logs_by_block(result.logs))
+}
+
+/// Blocks in `[from_block, to_block]` whose header bloom may contain logs from `address`
+/// (and every topic in `topics`, if given)
+///
+/// Only reads headers, never receipts, so it is a cheap prefilter before an expensive
+/// scan. Bloom filters have false positives but no false negatives: every block with a
+/// matching log is returned, plus possibly some without one. Blocks with no header
+/// are omitted; like the scanners, this fails with [`ScrapeError::MissingBlockData`]
+/// if the range's headers are in neither MDBX nor `tx`'s static files.
+pub fn bloom_candidate_blocks<TX: ScanTx>(
+    tx: &TX,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+) -> Result<Vec<BlockNumber>> {
+    probe_block_data(tx, from_block, to_block, false)?;
+    validate_block_range(tx, from_block, to_block)?;
+
+    let topic_filter = topics.as_deref().unwrap_or_default();
+    let mut candidates = Vec::new();
+
+    let mut source = TxBlocks::new(tx)?;
+    for block_num in from_block..=to_block.min(chain_tip(tx)?) {
+        let Some(bloom) = source.bloom(block_num)? else {
+            continue;
+        };
+        if bloom_contains_address(&bloom, &address) && bloom_contains_topics(&bloom, topic_filter)
+        {
+            candidates.push(block_num);
+        }
+    }
+
+    Ok(candidates)
+}
+
+/// Highest canonical block number present in the database
+pub fn latest_block<TX: DbTx>(tx: &TX) -> Result<BlockNumber> {
+    let mut cursor = tx.cursor_read::<tables::CanonicalHeaders>()?;
+    cursor
+        .last()?
+        .map(|(block_number, _)| block_number)
+        .ok_or_else(|| eyre!("no canonical headers in database"))
+}
+
+/// Highest block `tx` has a header for: the MDBX canonical tip, or the last block in
+/// the static files if those go further
+fn chain_tip<TX: ScanTx>(tx: &TX) -> Result<BlockNumber> {
+    let static_tip = tx.static_files().and_then(|static_files| {
+        static_files.get_highest_static_file_block(StaticFileSegment::Headers)
+    });
+    match (latest_block(tx.mdbx()), static_tip) {
+        (Ok(tip), static_tip) => Ok(static_tip.map_or(tip, |static_tip| tip.max(static_tip))),
+        (Err(_), Some(static_tip)) => Ok(static_tip),
+        (Err(err), None) => Err(err),
+    }
+}
+
+/// Check that `tx` holds the headers and receipts an event scan over the range reads.
+///
+/// `BlockBodyIndices` stays in MDBX, while newer Reth nodes keep headers and receipts
+/// in static files. Read through a plain MDBX transaction (or with the receipts
+/// pruned), such a range would find no headers or receipts and report zero logs, so
+/// this fails with [`ScrapeError::MissingBlockData`] instead; a [`StaticFileTx`] also
+/// looks in the static files. Only the first block with a body (and the first with
+/// transactions) is probed; a range with no bodies passes.
+pub fn ensure_block_data<TX: ScanTx>(
+    tx: &TX,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<()> {
+    probe_block_data(tx, from_block, to_block, true)
+}
+
+/// [`ensure_block_data`], skipping the receipt probe when `receipts` is false
+/// (for header-only readers such as [`bloom_candidate_blocks`])
+fn probe_block_data<TX: ScanTx>(
+    tx: &TX,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    receipts: bool,
+) -> Result<()> {
+    let mut source = TxBlocks::new(tx)?;
+    let mut body_cursor = tx.mdbx().cursor_read::<tables::BlockBodyIndices>()?;
+    let mut header_checked = false;
+    for entry in body_cursor.walk_range(from_block..=to_block)? {
+        let (block, body) = entry?;
+        if !header_checked {
+            if source.bloom(block)?.is_none() {
+                return Err(ScrapeError::MissingBlockData { block, table: "Headers" }.into());
+            }
+            header_checked = true;
+            if !receipts {
+                break;
+            }
+        }
+        if body.tx_count > 0 {
+            if source.receipt_logs(body.first_tx_num)?.is_none() {
+                return Err(ScrapeError::MissingBlockData { block, table: "Receipts" }.into());
+            }
+            break;
+        }
+    }
+    Ok(())
+}
+
+/// Fail with [`ScrapeError::InvalidBlockRange`] unless `from_block <= to_block` and
+/// `from_block` is at or below the latest block in the database (or its static files).
+///
+/// `to_block` may lie past the tip; the scan simply stops finding blocks there.
+pub fn validate_block_range<TX: ScanTx>(
+    tx: &TX,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<()> {
+    let tip = chain_tip(tx)?;
+    if from_block > to_block || from_block > tip {
+        return Err(ScrapeError::InvalidBlockRange { from_block, to_block, tip }.into());
+    }
+    Ok(())
+}
+
+/// `to_block` if given, otherwise the latest block in the database (or its static files)
+pub fn resolve_to_block<TX: ScanTx>(tx: &TX, to_block: Option<BlockNumber>) -> Result<BlockNumber> {
+    match to_block {
+        Some(block) => Ok(block),
+        None => chain_tip(tx),
+    }
 }
 
 /// Scan for event logs from a specific address within a block range
@@ -54,300 +875,832 @@ pub struct EventScanResult {
 /// 2. Uses bloom filters to skip blocks without relevant logs
 /// 3. Reads receipts only for potentially relevant blocks
 /// 4. Filters logs by address and topic (if specified)
-pub fn scan_events<TX: DbTx>(
+pub fn scan_events<TX: ScanTx>(
     tx: &TX,
     address: Address,
     from_block: BlockNumber,
     to_block: BlockNumber,
     topics: Option<Vec<B256>>, // Optional topic filters (topic0, topic1, etc.)
 ) -> Result<EventScanResult> {
+    scan_events_with_progress(tx, address, from_block, to_block, topics, None)
+}
+
+/// [`scan_events`] reporting progress: `progress` is called with each block number
+/// before that block is scanned (including blocks later skipped by the bloom filter)
+pub fn scan_events_with_progress<TX: ScanTx>(
+    tx: &TX,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+    progress: Option<&mut dyn FnMut(BlockNumber)>,
+) -> Result<EventScanResult> {
+    ensure_block_data(tx, from_block, to_block)?;
+    validate_block_range(tx, from_block, to_block)?;
+    scan_events_impl(tx, address, from_block, to_block, topics, &ScanConfig::default(), progress)
+}
+
+/// [`scan_events`] with explicit [`ScanConfig`], e.g. with the bloom prefilter disabled
+/// to audit the optimized path against every block's receipts
+pub fn scan_events_with_config<TX: ScanTx>(
+    tx: &TX,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+    config: &ScanConfig,
+) -> Result<EventScanResult> {
+    ensure_block_data(tx, from_block, to_block)?;
+    validate_block_range(tx, from_block, to_block)?;
+    scan_events_impl(tx, address, from_block, to_block, topics, config, None)
+}
+
+/// Body of [`scan_events`] without the range checks, which callers run first.
+/// [`crate::parallel`] checks the whole range once and then scans chunks with this,
+/// so chunks past the tip count their blocks like a sequential scan does.
+pub(crate) fn scan_events_impl<TX: ScanTx>(
+    tx: &TX,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+    config: &ScanConfig,
+    progress: Option<&mut dyn FnMut(BlockNumber)>,
+) -> Result<EventScanResult> {
+    let topic_filter = topics.as_deref().unwrap_or_default();
     let mut logs = Vec::new();
-    let mut blocks_scanned = 0u64;
-    let mut blocks_skipped_by_bloom = 0u64;
+    let mut visitor = FnVisitor {
+        bloom: |bloom: &Bloom| {
+            bloom_contains_address(bloom, &address) && bloom_contains_topics(bloom, topic_filter)
+        },
+        visit: |position: LogPosition, log: Log| {
+            let matched = log.address == address && log_matches_topics(&log, topic_filter);
+            if matched {
+                logs.push(position.event_log(log));
+            }
+            Ok(matched)
+        },
+    };
+    let mut source = TxBlocks::new(tx)?;
+    let stats = walk_blocks(&mut source, from_block, to_block, config, progress, &mut visitor)?;
 
-    // Cursors for reading data
-    let mut header_cursor = tx.cursor_read::<tables::Headers>()?;
-    let mut body_cursor = tx.cursor_read::<tables::BlockBodyIndices>()?;
-    let mut receipt_cursor = tx.cursor_read::<tables::Receipts>()?;
+    Ok(scan_result(address, from_block, to_block, logs, stats))
+}
 
-    // Iterate through each block in the range
-    for block_num in from_block..=to_block {
-        blocks_scanned += 1;
-
-        // Step 1: Check bloom filter in block header
-        if let Some((_, header)) = header_cursor.seek_exact(block_num)? {
-            // Check if the bloom filter contains our address
-            if !header.logs_bloom.contains_input(BloomInput::Raw(address.as_slice())) {
-                // Bloom filter says this block definitely doesn't have logs from this address
-                blocks_skipped_by_bloom += 1;
-                continue;
+/// Scan for event logs matching ANY of several topic filters in one pass
+///
+/// Each entry of `topic_sets` is a positional filter like the `topics`
+/// argument of `scan_events` (AND within a set). A log is returned if it
+/// matches at least one set (OR across sets), and `matched_filter` records the
+/// index of the first set it matched. This collects e.g. Mint, Burn and Swap
+/// together with a single block/receipt walk instead of three scans.
+pub fn scan_events_any<TX: ScanTx>(
+    tx: &TX,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topic_sets: &[Vec<B256>],
+) -> Result<EventScanResult> {
+    if topic_sets.is_empty() {
+        return Err(eyre!("topic_sets must contain at least one filter"));
+    }
+
+    let mut logs = Vec::new();
+    let mut visitor = FnVisitor {
+        // Skip unless the address and at least one complete topic set may be present
+        bloom: |bloom: &Bloom| {
+            bloom_contains_address(bloom, &address)
+                && topic_sets.iter().any(|set| bloom_contains_topics(bloom, set))
+        },
+        visit: |position: LogPosition, log: Log| {
+            if log.address != address {
+                return Ok(false);
             }
+            let Some(matched) = topic_sets.iter().position(|set| log_matches_topics(&log, set))
+            else {
+                return Ok(false);
+            };
+            logs.push(EventLog {
+                matched_filter: Some(matched),
+                ..position.event_log(log)
+            });
+            Ok(true)
+        },
+    };
+    let stats = scan_blocks(tx, from_block, to_block, &ScanConfig::default(), None, &mut visitor)?;
+
+    Ok(scan_result(address, from_block, to_block, logs, stats))
+}
+
+/// Scan for events with multiple addresses (e.g., all pools) - OPTIMIZED
+///
+/// This function scans each block only ONCE and checks bloom filters for all addresses
+/// at once, making it much more efficient than calling scan_events multiple times.
+///
+/// Performance improvement: If you have N addresses, this scans each block once instead
+/// of N times, reducing database reads by ~N times.
+pub fn scan_events_multi_address<TX: ScanTx>(
+    tx: &TX,
+    addresses: &[Address],
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+) -> Result<Vec<EventScanResult>> {
+    scan_events_multi_address_with_progress(tx, addresses, from_block, to_block, topics, None)
+}
+
+/// [`scan_events_multi_address`] reporting progress: `progress` is called with each
+/// block number before that block is scanned
+pub fn scan_events_multi_address_with_progress<TX: ScanTx>(
+    tx: &TX,
+    addresses: &[Address],
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+    progress: Option<&mut dyn FnMut(BlockNumber)>,
+) -> Result<Vec<EventScanResult>> {
+    // Scan each address once; repeated inputs get a copy of the same result
+    let (unique, positions) = dedup_addresses(addresses);
+    let results = scan_unique_addresses(tx, &unique, from_block, to_block, topics, progress)?;
+    Ok(positions.into_iter().map(|i| results[i].clone()).collect())
+}
+
+/// Like [`scan_events_multi_address`], but keyed by address instead of aligned with
+/// the input order. Duplicate addresses are scanned once.
+pub fn scan_events_by_address<TX: ScanTx>(
+    tx: &TX,
+    addresses: &[Address],
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+) -> Result<HashMap<Address, EventScanResult>> {
+    let (unique, _) = dedup_addresses(addresses);
+    let results = scan_unique_addresses(tx, &unique, from_block, to_block, topics, None)?;
+    Ok(unique.into_iter().zip(results).collect())
+}
+
+/// Continue an incremental scan from `state.next_block()` through `to_block`
+///
+/// `to_block` defaults to the latest block and is capped there, so blocks imported
+/// after this run are left for the next one. Returns this run's results aligned with
+/// `state.addresses()` and advances `state`; when there are no new blocks the
+/// results are empty and `state` is unchanged.
+pub fn resume_scan<TX: ScanTx>(
+    tx: &TX,
+    state: &mut ScanState,
+    to_block: Option<BlockNumber>,
+) -> Result<Vec<EventScanResult>> {
+    let Some((from_block, to_block)) = resume_range(state, to_block, chain_tip(tx)?) else {
+        return Ok(Vec::new());
+    };
+
+    let results = scan_unique_addresses(
+        tx,
+        &state.addresses,
+        from_block,
+        to_block,
+        state.topics.clone(),
+        None,
+    )?;
+    state.record(&results, to_block);
+    Ok(results)
+}
+
+/// Blocks the next run of `state` covers: from `next_block` through `to_block`
+/// capped at `tip`, or `None` if there are no new blocks
+fn resume_range(
+    state: &ScanState,
+    to_block: Option<BlockNumber>,
+    tip: BlockNumber,
+) -> Option<(BlockNumber, BlockNumber)> {
+    let to_block = to_block.map_or(tip, |to_block| to_block.min(tip));
+    (to_block >= state.next_block).then_some((state.next_block, to_block))
+}
+
+/// Scan many addresses in one block walk, bucketing logs by address and then topic0
+///
+/// A block is read only if its bloom may contain one of `addresses` and one of
+/// `topic0s`. Every requested (address, topic0) pair has a bucket, empty if nothing
+/// matched; logs within a bucket are in block order. With an empty `topic0s`, every
+/// log of the addresses is bucketed under its own topic0 (logs without topics are dropped).
+pub fn scan_events_multi_address_by_topic<TX: ScanTx>(
+    tx: &TX,
+    addresses: &[Address],
+    topic0s: &[B256],
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<HashMap<Address, HashMap<B256, Vec<EventLog>>>> {
+    let mut buckets: HashMap<Address, HashMap<B256, Vec<EventLog>>> = addresses
+        .iter()
+        .map(|addr| {
+            let by_topic = topic0s.iter().map(|topic| (*topic, Vec::new())).collect();
+            (*addr, by_topic)
+        })
+        .collect();
+    if buckets.is_empty() {
+        return Ok(buckets);
+    }
+
+    let mut visitor = FnVisitor {
+        bloom: |bloom: &Bloom| {
+            addresses.iter().any(|addr| bloom_contains_address(bloom, addr))
+                && (topic0s.is_empty()
+                    || topic0s
+                        .iter()
+                        .any(|topic| bloom.contains_input(BloomInput::Raw(topic.as_slice()))))
+        },
+        visit: |position: LogPosition, log: Log| {
+            let Some(by_topic) = buckets.get_mut(&log.address) else {
+                return Ok(false);
+            };
+            let Some(topic0) = log.data.topics().first().copied() else {
+                return Ok(false);
+            };
+            let bucket = if topic0s.is_empty() {
+                by_topic.entry(topic0).or_default()
+            } else {
+                match by_topic.get_mut(&topic0) {
+                    Some(bucket) => bucket,
+                    None => return Ok(false),
+                }
+            };
+            bucket.push(position.event_log(log));
+            Ok(true)
+        },
+    };
+    scan_blocks(tx, from_block, to_block, &ScanConfig::default(), None, &mut visitor)?;
+
+    Ok(buckets)
+}
+
+/// Unique addresses in first-seen order, and for each input position the index of
+/// its address in the unique list
+fn dedup_addresses(addresses: &[Address]) -> (Vec<Address>, Vec<usize>) {
+    let mut index: HashMap<Address, usize> = HashMap::new();
+    let mut unique = Vec::new();
+    let positions = addresses
+        .iter()
+        .map(|addr| {
+            *index.entry(*addr).or_insert_with(|| {
+                unique.push(*addr);
+                unique.len() - 1
+            })
+        })
+        .collect();
+    (unique, positions)
+}
+
+/// Single-pass multi-address scan; `addresses` must not contain duplicates
+fn scan_unique_addresses<TX: ScanTx>(
+    tx: &TX,
+    addresses: &[Address],
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+    progress: Option<&mut dyn FnMut(BlockNumber)>,
+) -> Result<Vec<EventScanResult>> {
+    if addresses.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let mut visitor = MultiAddressVisitor {
+        topics: topics.as_deref().unwrap_or_default(),
+        index: addresses.iter().enumerate().map(|(i, addr)| (*addr, i)).collect(),
+        results: addresses
+            .iter()
+            .map(|addr| scan_result(*addr, from_block, to_block, Vec::new(), WalkStats::default()))
+            .collect(),
+        matched: vec![false; addresses.len()],
+    };
+    let config = ScanConfig::default();
+    let stats = scan_blocks(tx, from_block, to_block, &config, progress, &mut visitor)?;
+
+    // The combined bloom check scans or skips every block for all addresses alike
+    let mut results = visitor.results;
+    for result in results.iter_mut() {
+        result.blocks_scanned = stats.blocks_scanned;
+        result.blocks_skipped_by_bloom = stats.blocks_skipped_by_bloom;
+    }
+    Ok(results)
+}
+
+/// [`LogVisitor`] of [`scan_unique_addresses`]: one result per address, false
+/// positives counted per address
+struct MultiAddressVisitor<'a> {
+    topics: &'a [B256],
+    index: HashMap<Address, usize>,
+    results: Vec<EventScanResult>,
+    /// Whether each address matched in the current block
+    matched: Vec<bool>,
+}
+
+impl LogVisitor for MultiAddressVisitor<'_> {
+    fn bloom_may_match(&self, bloom: &Bloom) -> bool {
+        self.results
+            .iter()
+            .any(|result| bloom_contains_address(bloom, &result.address))
+            && bloom_contains_topics(bloom, self.topics)
+    }
+
+    fn visit(&mut self, position: LogPosition, log: Log) -> Result<bool> {
+        let Some(&i) = self.index.get(&log.address) else {
+            return Ok(false);
+        };
+        if !log_matches_topics(&log, self.topics) {
+            return Ok(false);
+        }
+        self.results[i].logs.push(position.event_log(log));
+        self.matched[i] = true;
+        Ok(true)
+    }
+
+    fn end_block(&mut self, bloom: Option<&Bloom>) {
+        // Per address: the bloom held the address, but the block had none of its logs.
+        // Another address passing the combined check is no false positive for this one
+        for (result, matched) in self.results.iter_mut().zip(self.matched.iter_mut()) {
+            if !std::mem::take(matched)
+                && bloom.is_some_and(|bloom| bloom_contains_address(bloom, &result.address))
+            {
+                result.blocks_false_positive += 1;
+            }
+        }
+    }
+}
+
+/// Count event logs from a specific address within a block range
+///
+/// Same bloom/receipt walk as `scan_events`, but only the number of matching
+/// logs is kept, so no `Log` is cloned and no `Vec<EventLog>` is allocated.
+pub fn count_events<TX: ScanTx>(
+    tx: &TX,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+) -> Result<EventCountResult> {
+    let topic_filter = topics.as_deref().unwrap_or_default();
+    let mut visitor = FnVisitor {
+        bloom: |bloom: &Bloom| {
+            bloom_contains_address(bloom, &address) && bloom_contains_topics(bloom, topic_filter)
+        },
+        visit: |_, log: Log| Ok(log.address == address && log_matches_topics(&log, topic_filter)),
+    };
+    let stats = scan_blocks(tx, from_block, to_block, &ScanConfig::default(), None, &mut visitor)?;
+
+    Ok(EventCountResult {
+        address,
+        from_block,
+        to_block,
+        count: stats.logs_matched,
+        blocks_scanned: stats.blocks_scanned,
+        blocks_skipped_by_bloom: stats.blocks_skipped_by_bloom,
+    })
+}
+
+/// Transactions in a block range that emitted matching logs from `address`
+///
+/// Between `count_events` and `scan_events`: the same bloom/receipt walk, but only
+/// `(block, transaction index, matching log count)` is kept per transaction, so no
+/// `Log` is cloned. Enough to pick out touching transactions and fetch their
+/// traces elsewhere. Results are in block/transaction order.
+pub fn scan_touching_transactions<TX: ScanTx>(
+    tx: &TX,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+) -> Result<Vec<TouchingTx>> {
+    let topic_filter = topics.as_deref().unwrap_or_default();
+    let mut touching: Vec<TouchingTx> = Vec::new();
+    let mut visitor = FnVisitor {
+        bloom: |bloom: &Bloom| {
+            bloom_contains_address(bloom, &address) && bloom_contains_topics(bloom, topic_filter)
+        },
+        visit: |position: LogPosition, log: Log| {
+            if log.address != address || !log_matches_topics(&log, topic_filter) {
+                return Ok(false);
+            }
+            // A transaction's logs arrive together
+            match touching.last_mut() {
+                Some(last)
+                    if last.block_number == position.block_number
+                        && last.transaction_index == position.transaction_index =>
+                {
+                    last.log_count += 1;
+                }
+                _ => touching.push(TouchingTx {
+                    block_number: position.block_number,
+                    transaction_index: position.transaction_index,
+                    log_count: 1,
+                }),
+            }
+            Ok(true)
+        },
+    };
+    scan_blocks(tx, from_block, to_block, &ScanConfig::default(), None, &mut visitor)?;
+
+    Ok(touching)
+}
+
+/// Scan for event logs from `address` emitted by transactions sent by `sender`
+///
+/// The transaction sender is not part of a log, so each transaction with matching
+/// logs is joined to its recovered sender in `TransactionSenders` (keyed by the same
+/// global transaction number as `Receipts`). Senders are only looked up for
+/// transactions that already matched `address` and `topics`, so the bloom and
+/// receipt walk costs the same as `scan_events`. `sender` is the EOA that signed the
+/// transaction, not an intermediate contract.
+///
+/// Fails with [`ScrapeError::MissingBlockData`] if a matching transaction has no
+/// sender row (pruned, or kept in static files by newer nodes), rather than
+/// silently dropping its logs.
+pub fn scan_events_by_sender<TX: ScanTx>(
+    tx: &TX,
+    address: Address,
+    sender: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+) -> Result<EventScanResult> {
+    let topic_filter = topics.as_deref().unwrap_or_default();
+    let mut logs = Vec::new();
+    let mut sender_cursor = tx.mdbx().cursor_read::<tables::TransactionSenders>()?;
+    // Sender of the last transaction looked up; a transaction's logs arrive together
+    let mut last_sender: Option<(u64, Address)> = None;
 
-            // If topics are specified, also check bloom for topics
-            if let Some(ref topic_list) = topics {
-                let mut has_all_topics = true;
-                for topic in topic_list {
-                    if !header.logs_bloom.contains_input(BloomInput::Raw(topic.as_slice())) {
-                        has_all_topics = false;
-                        break;
-                    }
-                }
-                if !has_all_topics {
-                    blocks_skipped_by_bloom += 1;
-                    continue;
-                }
+    let mut visitor = FnVisitor {
+        bloom: |bloom: &Bloom| {
+            bloom_contains_address(bloom, &address) && bloom_contains_topics(bloom, topic_filter)
+        },
+        visit: |position: LogPosition, log: Log| {
+            if log.address != address || !log_matches_topics(&log, topic_filter) {
+                return Ok(false);
             }
-        } else {
-            // Block header not found, skip
-            continue;
-        }
-
-        // Step 2: Get transaction range for this block
-        if let Some((_, body_indices)) = body_cursor.seek_exact(block_num)? {
-            // Step 3: Read receipts for all transactions in this block
-            for tx_index in 0..body_indices.tx_count {
-                let tx_num = body_indices.first_tx_num + tx_index;
-
-                if let Some((_, receipt)) = receipt_cursor.seek_exact(tx_num)? {
-
-                    // Step 4: Filter logs by address and topics
-                    for log in receipt.logs {
-                        // Check if log is from the target address
-                        if log.address != address {
-                            continue;
-                        }
-
-                        // Check topics if specified
-                        if let Some(ref topic_list) = topics {
-                            let mut matches_topics = true;
-                            for (i, required_topic) in topic_list.iter().enumerate() {
-                                if i >= log.data.topics().len() {
-                                    matches_topics = false;
-                                    break;
-                                }
-                                if &log.data.topics()[i] != required_topic {
-                                    matches_topics = false;
-                                    break;
-                                }
-                            }
-                            if !matches_topics {
-                                continue;
-                            }
-                        }
-
-                        // This log matches our filters
-                        logs.push(EventLog {
-                            log: log.clone(),
-                            block_number: block_num,
-                            transaction_index: tx_index,
-                            transaction_hash: None, // We'd need TransactionBlocks table for this
-                        });
-                    }
+            let tx_sender = match last_sender {
+                Some((tx_num, tx_sender)) if tx_num == position.tx_num => tx_sender,
+                _ => {
+                    let (_, tx_sender) = sender_cursor.seek_exact(position.tx_num)?.ok_or(
+                        ScrapeError::MissingBlockData {
+                            block: position.block_number,
+                            table: "TransactionSenders",
+                        },
+                    )?;
+                    last_sender = Some((position.tx_num, tx_sender));
+                    tx_sender
                 }
+            };
+            if tx_sender != sender {
+                return Ok(false);
             }
-        }
-    }
+            logs.push(position.event_log(log));
+            Ok(true)
+        },
+    };
+    let stats = scan_blocks(tx, from_block, to_block, &ScanConfig::default(), None, &mut visitor)?;
 
-    Ok(EventScanResult {
-        address,
-        from_block,
-        to_block,
-        logs,
-        blocks_scanned,
-        blocks_skipped_by_bloom,
-    })
+    Ok(scan_result(address, from_block, to_block, logs, stats))
 }
 
-/// Scan for events with multiple addresses (e.g., all pools) - OPTIMIZED
-///
-/// This function scans each block only ONCE and checks bloom filters for all addresses
-/// at once, making it much more efficient than calling scan_events multiple times.
+/// Count events for multiple addresses in a single pass
 ///
-/// Performance improvement: If you have N addresses, this scans each block once instead
-/// of N times, reducing database reads by ~N times.
-pub fn scan_events_multi_address<TX: DbTx>(
+/// Count-only counterpart of `scan_events_multi_address`. Returns one
+/// `EventCountResult` per address, in the same order; repeated addresses are
+/// counted once and each copy gets the same result.
+pub fn count_events_multi_address<TX: ScanTx>(
     tx: &TX,
     addresses: &[Address],
     from_block: BlockNumber,
     to_block: BlockNumber,
     topics: Option<Vec<B256>>,
-) -> Result<Vec<EventScanResult>> {
+) -> Result<Vec<EventCountResult>> {
+    let (unique, positions) = dedup_addresses(addresses);
+    let results = count_unique_addresses(tx, &unique, from_block, to_block, topics)?;
+    Ok(positions.into_iter().map(|i| results[i].clone()).collect())
+}
+
+/// Single-pass multi-address count; `addresses` must not contain duplicates
+fn count_unique_addresses<TX: ScanTx>(
+    tx: &TX,
+    addresses: &[Address],
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+) -> Result<Vec<EventCountResult>> {
     if addresses.is_empty() {
         return Ok(Vec::new());
     }
 
-    // Initialize result tracking for each address
-    let mut results: Vec<EventScanResult> = addresses
+    let topic_filter = topics.as_deref().unwrap_or_default();
+    let index: HashMap<Address, usize> =
+        addresses.iter().enumerate().map(|(i, addr)| (*addr, i)).collect();
+    let mut counts = vec![0u64; addresses.len()];
+    let mut visitor = FnVisitor {
+        bloom: |bloom: &Bloom| {
+            addresses.iter().any(|addr| bloom_contains_address(bloom, addr))
+                && bloom_contains_topics(bloom, topic_filter)
+        },
+        visit: |_, log: Log| {
+            let Some(&i) = index.get(&log.address) else {
+                return Ok(false);
+            };
+            if !log_matches_topics(&log, topic_filter) {
+                return Ok(false);
+            }
+            counts[i] += 1;
+            Ok(true)
+        },
+    };
+    let stats = scan_blocks(tx, from_block, to_block, &ScanConfig::default(), None, &mut visitor)?;
+
+    Ok(addresses
         .iter()
-        .map(|addr| EventScanResult {
+        .zip(counts)
+        .map(|(addr, count)| EventCountResult {
             address: *addr,
             from_block,
             to_block,
-            logs: Vec::new(),
-            blocks_scanned: 0,
-            blocks_skipped_by_bloom: 0,
+            count,
+            blocks_scanned: stats.blocks_scanned,
+            blocks_skipped_by_bloom: stats.blocks_skipped_by_bloom,
         })
-        .collect();
+        .collect())
+}
 
-    // Cursors for reading data
-    let mut header_cursor = tx.cursor_read::<tables::Headers>()?;
-    let mut body_cursor = tx.cursor_read::<tables::BlockBodyIndices>()?;
-    let mut receipt_cursor = tx.cursor_read::<tables::Receipts>()?;
+/// Get all Uniswap V2 Swap events for a pair
+///
+/// Swap event signature: Swap(address,uint256,uint256,uint256,uint256,address)
+pub fn get_v2_swap_events<TX: ScanTx>(
+    tx: &TX,
+    pair_address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<EventScanResult> {
+    scan_events(tx, pair_address, from_block, to_block, Some(vec![topics::SWAP_V2]))
+}
 
-    // Iterate through each block in the range ONCE
-    for block_num in from_block..=to_block {
-        // Step 1: Check bloom filter for ANY of the addresses
-        if let Some((_, header)) = header_cursor.seek_exact(block_num)? {
-            // Check if bloom filter contains ANY of our addresses
-            let mut has_any_address = false;
-            for addr in addresses {
-                if header.logs_bloom.contains_input(BloomInput::Raw(addr.as_slice())) {
-                    has_any_address = true;
-                    break; // Early exit - at least one address might be present
-                }
-            }
+/// Get all Uniswap V2 Sync events for a pair
+///
+/// Sync event signature: Sync(uint112,uint112). Emitted after every reserve update,
+/// so the last Sync in a block holds the pair's reserves at the end of that block.
+pub fn get_v2_sync_events<TX: ScanTx>(
+    tx: &TX,
+    pair_address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<EventScanResult> {
+    scan_events(tx, pair_address, from_block, to_block, Some(vec![topics::SYNC_V2]))
+}
 
-            if !has_any_address {
-                // None of the addresses are in this block - skip it for all addresses
-                for result in results.iter_mut() {
-                    result.blocks_scanned += 1;
-                    result.blocks_skipped_by_bloom += 1;
-                }
-                continue;
-            }
+/// Decode a Uniswap V2 Swap log
+pub fn decode_v2_swap(log: &Log) -> Result<IUniswapV2PairEvents::Swap> {
+    IUniswapV2PairEvents::Swap::decode_log_data(&log.data)
+        .map_err(|e| eyre!("failed to decode V2 Swap log: {e}"))
+}
 
-            // If topics are specified, also check bloom for topics
-            if let Some(ref topic_list) = topics {
-                let mut has_all_topics = true;
-                for topic in topic_list {
-                    if !header.logs_bloom.contains_input(BloomInput::Raw(topic.as_slice())) {
-                        has_all_topics = false;
-                        break;
-                    }
-                }
-                if !has_all_topics {
-                    for result in results.iter_mut() {
-                        result.blocks_scanned += 1;
-                        result.blocks_skipped_by_bloom += 1;
-                    }
-                    continue;
-                }
+/// Decode a Uniswap V2 Sync log
+pub fn decode_v2_sync(log: &Log) -> Result<IUniswapV2PairEvents::Sync> {
+    IUniswapV2PairEvents::Sync::decode_log_data(&log.data)
+        .map_err(|e| eyre!("failed to decode V2 Sync log: {e}"))
+}
+
+/// Get all Uniswap V3 Swap events for a pool
+///
+/// Swap event signature: Swap(address,address,int256,int256,uint160,uint128,int24)
+/// Topic0: keccak256("Swap(address,address,int256,int256,uint160,uint128,int24)")
+pub fn get_v3_swap_events<TX: ScanTx>(
+    tx: &TX,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<EventScanResult> {
+    scan_events(tx, pool_address, from_block, to_block, Some(vec![topics::SWAP_V3]))
+}
+
+/// Decode a Uniswap V3 Swap log
+pub fn decode_v3_swap(log: &Log) -> Result<IUniswapV3PoolEvents::Swap> {
+    IUniswapV3PoolEvents::Swap::decode_log_data(&log.data)
+        .map_err(|e| eyre!("failed to decode V3 Swap log: {e}"))
+}
+
+/// Scan a V3 pool's swaps and aggregate them into fixed time windows
+///
+/// Each swap is assigned to the bucket containing its block timestamp
+/// (`timestamp - timestamp % window_secs`). Only windows that contain at
+/// least one swap are returned, in ascending time order.
+pub fn scan_swaps_bucketed<TX: ScanTx>(
+    tx: &TX,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    window_secs: u32,
+) -> Result<Vec<SwapBucket>> {
+    if window_secs == 0 {
+        return Err(eyre!("window_secs must be greater than zero"));
+    }
+
+    let swaps = get_v3_swap_events(tx, pool_address, from_block, to_block)?;
+
+    let mut source = TxBlocks::new(tx)?;
+    let mut timed_swaps = Vec::with_capacity(swaps.logs.len());
+    let mut cached_timestamp: Option<(BlockNumber, u64)> = None;
+
+    for event in &swaps.logs {
+        // Logs arrive in block order, so one header read per block is enough
+        let timestamp = match cached_timestamp {
+            Some((block, timestamp)) if block == event.block_number => timestamp,
+            _ => {
+                let timestamp = source
+                    .timestamp(event.block_number)?
+                    .ok_or_else(|| eyre!("missing header for block {}", event.block_number))?;
+                cached_timestamp = Some((event.block_number, timestamp));
+                timestamp
             }
-        } else {
-            // Block header not found, skip
-            continue;
-        }
+        };
+        timed_swaps.push((timestamp, decode_v3_swap(&event.log)?));
+    }
 
-        // Step 2: Block might contain logs from at least one address - scan it
-        for result in results.iter_mut() {
-            result.blocks_scanned += 1;
-        }
+    Ok(bucket_swaps(timed_swaps, window_secs))
+}
 
-        if let Some((_, body_indices)) = body_cursor.seek_exact(block_num)? {
-            // Step 3: Read receipts for all transactions in this block
-            for tx_index in 0..body_indices.tx_count {
-                let tx_num = body_indices.first_tx_num + tx_index;
-
-                if let Some((_, receipt)) = receipt_cursor.seek_exact(tx_num)? {
-                    // Step 4: Filter logs by addresses and topics
-                    for log in receipt.logs {
-                        // Check if log matches any of our target addresses
-                        for (i, addr) in addresses.iter().enumerate() {
-                            if log.address != *addr {
-                                continue;
-                            }
-
-                            // Check topics if specified
-                            if let Some(ref topic_list) = topics {
-                                let mut matches_topics = true;
-                                for (topic_idx, required_topic) in topic_list.iter().enumerate() {
-                                    if topic_idx >= log.data.topics().len() {
-                                        matches_topics = false;
-                                        break;
-                                    }
-                                    if &log.data.topics()[topic_idx] != required_topic {
-                                        matches_topics = false;
-                                        break;
-                                    }
-                                }
-                                if !matches_topics {
-                                    continue;
-                                }
-                            }
-
-                            // This log matches this address's filters
-                            results[i].logs.push(EventLog {
-                                log: log.clone(),
-                                block_number: block_num,
-                                transaction_index: tx_index,
-                                transaction_hash: None,
-                            });
-                            break; // Move to next log (one address matched)
-                        }
-                    }
-                }
+/// Fold `(timestamp, swap)` pairs, in time order, into `window_secs` buckets
+///
+/// The bucketing half of [`scan_swaps_bucketed`]; `window_secs` must be non-zero.
+fn bucket_swaps(
+    swaps: impl IntoIterator<Item = (u64, IUniswapV3PoolEvents::Swap)>,
+    window_secs: u32,
+) -> Vec<SwapBucket> {
+    let mut buckets: Vec<SwapBucket> = Vec::new();
+
+    for (timestamp, swap) in swaps {
+        let tick = swap.tick.as_i32();
+        let amount0 = swap.amount0.unsigned_abs();
+        let amount1 = swap.amount1.unsigned_abs();
+        let start_time = timestamp - timestamp % u64::from(window_secs);
+
+        match buckets.last_mut() {
+            Some(bucket) if bucket.start_time == start_time => {
+                bucket.swap_count += 1;
+                bucket.volume0 = bucket.volume0.saturating_add(amount0);
+                bucket.volume1 = bucket.volume1.saturating_add(amount1);
+                bucket.close_tick = tick;
+                bucket.high_tick = bucket.high_tick.max(tick);
+                bucket.low_tick = bucket.low_tick.min(tick);
             }
+            _ => buckets.push(SwapBucket {
+                start_time,
+                swap_count: 1,
+                volume0: amount0,
+                volume1: amount1,
+                open_tick: tick,
+                close_tick: tick,
+                high_tick: tick,
+                low_tick: tick,
+            }),
         }
     }
 
-    Ok(results)
+    buckets
 }
 
-/// Get all Uniswap V3 Swap events for a pool
+/// Scan a single V4 pool's events from the PoolManager
 ///
-/// Swap event signature: Swap(address,address,int256,int256,uint160,uint128,int24)
-/// Topic0: keccak256("Swap(address,address,int256,int256,uint160,uint128,int24)")
-pub fn get_v3_swap_events<TX: DbTx>(
+/// V4 pools share the singleton `PoolManager`, which emits every pool's events
+/// with the `poolId` as the first indexed topic (topic1). This filters by both
+/// the manager address and `pool_id`, returning all of that pool's events
+/// (Swap, ModifyLiquidity, Initialize, Donate) in block order.
+pub fn scan_v4_pool_events<TX: ScanTx>(
     tx: &TX,
-    pool_address: Address,
+    pool_manager: Address,
+    pool_id: B256,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<EventScanResult> {
+    let mut logs = Vec::new();
+    let mut visitor = FnVisitor {
+        // Both the manager address and the poolId topic must be in the bloom
+        bloom: |bloom: &Bloom| {
+            bloom_contains_address(bloom, &pool_manager) && bloom_contains_topics(bloom, &[pool_id])
+        },
+        visit: |position: LogPosition, log: Log| {
+            let matched =
+                log.address == pool_manager && log.data.topics().get(1) == Some(&pool_id);
+            if matched {
+                logs.push(position.event_log(log));
+            }
+            Ok(matched)
+        },
+    };
+    let stats = scan_blocks(tx, from_block, to_block, &ScanConfig::default(), None, &mut visitor)?;
+
+    Ok(scan_result(pool_manager, from_block, to_block, logs, stats))
+}
+
+/// Decode a Uniswap V4 PoolManager Swap log
+pub fn decode_v4_swap(log: &Log) -> Result<IPoolManagerEvents::Swap> {
+    IPoolManagerEvents::Swap::decode_log_data(&log.data)
+        .map_err(|e| eyre!("failed to decode V4 Swap log: {e}"))
+}
+
+/// Decode a Uniswap V4 PoolManager ModifyLiquidity log
+pub fn decode_v4_modify_liquidity(log: &Log) -> Result<IPoolManagerEvents::ModifyLiquidity> {
+    IPoolManagerEvents::ModifyLiquidity::decode_log_data(&log.data)
+        .map_err(|e| eyre!("failed to decode V4 ModifyLiquidity log: {e}"))
+}
+
+/// Get all Uniswap V4 Swap events for a single pool
+pub fn get_v4_swap_events<TX: ScanTx>(
+    tx: &TX,
+    pool_manager: Address,
+    pool_id: B256,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<EventScanResult> {
+    scan_events(
+        tx,
+        pool_manager,
+        from_block,
+        to_block,
+        Some(vec![topics::SWAP_V4, pool_id]),
+    )
+}
+
+/// Get all Uniswap V4 ModifyLiquidity events for a single pool
+pub fn get_v4_modify_liquidity_events<TX: ScanTx>(
+    tx: &TX,
+    pool_manager: Address,
+    pool_id: B256,
     from_block: BlockNumber,
     to_block: BlockNumber,
 ) -> Result<EventScanResult> {
-    // Swap event topic0
-    let swap_topic = B256::from_slice(
-        &hex::decode("c42079f94a6350d7e6235f29174924f928cc2ac818eb64fed8004e115fbcca67")
-            .expect("valid hex"),
-    );
+    scan_events(
+        tx,
+        pool_manager,
+        from_block,
+        to_block,
+        Some(vec![topics::MODIFY_LIQUIDITY_V4, pool_id]),
+    )
+}
 
-    scan_events(tx, pool_address, from_block, to_block, Some(vec![swap_topic]))
+/// Ticks referenced by a V4 pool's ModifyLiquidity events, with the net change of
+/// `liquidityGross` each received over `[from_block, to_block]`
+///
+/// Every position adds its `liquidityDelta` to the gross liquidity of both its
+/// bounds, so folding the events from the pool's creation leaves the currently
+/// initialized ticks with a positive total. Over a partial range the totals are only
+/// deltas, but the key set is still every tick that may have changed.
+pub fn v4_touched_ticks<TX: ScanTx>(
+    tx: &TX,
+    pool_manager: Address,
+    pool_id: B256,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<BTreeMap<i32, i128>> {
+    let events = get_v4_modify_liquidity_events(tx, pool_manager, pool_id, from_block, to_block)?;
+    fold_liquidity_gross(events.logs.iter().map(|event| &event.log))
+}
+
+/// Sum each ModifyLiquidity `liquidityDelta` into the gross liquidity of both bounds
+fn fold_liquidity_gross<'a>(
+    logs: impl IntoIterator<Item = &'a Log>,
+) -> Result<BTreeMap<i32, i128>> {
+    let mut gross: BTreeMap<i32, i128> = BTreeMap::new();
+    for log in logs {
+        let modify = decode_v4_modify_liquidity(log)?;
+        let delta = i128::try_from(modify.liquidityDelta).map_err(|_| {
+            eyre!("liquidityDelta {} does not fit in i128", modify.liquidityDelta)
+        })?;
+        for tick in [modify.tickLower.as_i32(), modify.tickUpper.as_i32()] {
+            *gross.entry(tick).or_default() += delta;
+        }
+    }
+
+    Ok(gross)
 }
 
 /// Get all Uniswap V3 Mint events for a pool
 ///
 /// Mint event signature: Mint(address,address,int24,int24,uint128,uint256,uint256)
 /// Topic0: keccak256("Mint(address,address,int24,int24,uint128,uint256,uint256)")
-pub fn get_v3_mint_events<TX: DbTx>(
+pub fn get_v3_mint_events<TX: ScanTx>(
     tx: &TX,
     pool_address: Address,
     from_block: BlockNumber,
     to_block: BlockNumber,
 ) -> Result<EventScanResult> {
-    // Mint event topic0
-    let mint_topic = B256::from_slice(
-        &hex::decode("7a53080ba414158be7ec69b987b5fb7d07dee101fe85488f0853ae16239d0bde")
-            .expect("valid hex"),
-    );
-
-    scan_events(tx, pool_address, from_block, to_block, Some(vec![mint_topic]))
+    scan_events(tx, pool_address, from_block, to_block, Some(vec![topics::MINT_V3]))
 }
 
 /// Get all Uniswap V3 Burn events for a pool
 ///
 /// Burn event signature: Burn(address,int24,int24,uint128,uint256,uint256)
 /// Topic0: keccak256("Burn(address,int24,int24,uint128,uint256,uint256)")
-pub fn get_v3_burn_events<TX: DbTx>(
+pub fn get_v3_burn_events<TX: ScanTx>(
     tx: &TX,
     pool_address: Address,
     from_block: BlockNumber,
     to_block: BlockNumber,
 ) -> Result<EventScanResult> {
-    // Burn event topic0
-    let burn_topic = B256::from_slice(
-        &hex::decode("0c396cd989a39f4459b5fa1aed6a9a8dcdbc45908acfd67e028cd568da98982c")
-            .expect("valid hex"),
-    );
-
-    scan_events(tx, pool_address, from_block, to_block, Some(vec![burn_topic]))
+    scan_events(tx, pool_address, from_block, to_block, Some(vec![topics::BURN_V3]))
 }
 
 /// Estimate the number of blocks that can be scanned efficiently
@@ -389,6 +1742,40 @@ pub fn suggest_block_chunk_size<TX: DbTx>(
     Ok(chunk_size.max(1000).min(50_000))
 }
 
+/// Sample the bloom hit-rate of `address` over `sample_range` (headers only)
+///
+/// Use [`ScanEstimate::estimate_blocks_with_logs`] to predict how many blocks of a
+/// larger range will need receipt reads; together with [`suggest_block_chunk_size`]
+/// this sizes and schedules scans before running them. Fails with
+/// [`ScrapeError::MissingBlockData`] if the sample's headers are in neither MDBX nor
+/// `tx`'s static files, which would otherwise look like an empty sample.
+pub fn estimate_scan_blocks_with_logs<TX: ScanTx>(
+    tx: &TX,
+    address: Address,
+    sample_range: RangeInclusive<BlockNumber>,
+) -> Result<ScanEstimate> {
+    let (from_block, to_block) = sample_range.into_inner();
+    probe_block_data(tx, from_block, to_block, false)?;
+
+    let mut estimate = ScanEstimate {
+        blocks_sampled: 0,
+        bloom_hits: 0,
+    };
+
+    let mut source = TxBlocks::new(tx)?;
+    for block_num in from_block..=to_block.min(chain_tip(tx)?) {
+        let Some(bloom) = source.bloom(block_num)? else {
+            continue;
+        };
+        estimate.blocks_sampled += 1;
+        if bloom_contains_address(&bloom, &address) {
+            estimate.bloom_hits += 1;
+        }
+    }
+
+    Ok(estimate)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -410,6 +1797,576 @@ mod tests {
         assert!(!bloom.contains_input(BloomInput::Raw(other_address.as_slice())));
     }
 
+    /// In-memory [`BlockSource`]: per block an optional header bloom and each
+    /// transaction's logs. Transaction numbers are `block * 1000 + index`.
+    #[derive(Default)]
+    struct TestBlocks {
+        blocks: BTreeMap<BlockNumber, (Option<Bloom>, Vec<Vec<Log>>)>,
+    }
+
+    impl TestBlocks {
+        /// Add `block` with a bloom over `bloom_items` (`None` for a missing header)
+        fn block(
+            mut self,
+            block: BlockNumber,
+            bloom_items: Option<&[&[u8]]>,
+            txs: Vec<Vec<Log>>,
+        ) -> Self {
+            let bloom = bloom_items.map(|items| {
+                let mut bloom = Bloom::ZERO;
+                for item in items {
+                    bloom.accrue(BloomInput::Raw(item));
+                }
+                bloom
+            });
+            self.blocks.insert(block, (bloom, txs));
+            self
+        }
+    }
+
+    impl BlockSource for TestBlocks {
+        fn bloom(&mut self, block: BlockNumber) -> Result<Option<Bloom>> {
+            Ok(self.blocks.get(&block).and_then(|(bloom, _)| *bloom))
+        }
+
+        fn body(&mut self, block: BlockNumber) -> Result<Option<(u64, u64)>> {
+            Ok(self.blocks.get(&block).map(|(_, txs)| (block * 1000, txs.len() as u64)))
+        }
+
+        fn receipt_logs(&mut self, tx_num: u64) -> Result<Option<Vec<Log>>> {
+            let (_, txs) = &self.blocks[&(tx_num / 1000)];
+            Ok(txs.get((tx_num % 1000) as usize).cloned())
+        }
+    }
+
+    /// Visitor collecting the positions of logs from `address`
+    fn position_visitor(
+        address: Address,
+        found: &mut Vec<LogPosition>,
+    ) -> FnVisitor<impl Fn(&Bloom) -> bool, impl FnMut(LogPosition, Log) -> Result<bool> + '_> {
+        FnVisitor {
+            bloom: move |bloom: &Bloom| bloom_contains_address(bloom, &address),
+            visit: move |position: LogPosition, log: Log| {
+                let matched = log.address == address;
+                if matched {
+                    found.push(position);
+                }
+                Ok(matched)
+            },
+        }
+    }
+
+    #[test]
+    fn test_walk_blocks() {
+        let target = Address::from([0x42; 20]);
+        let other = Address::from([0x99; 20]);
+        let log = |address| Log::new_unchecked(address, Vec::new(), Default::default());
+        let mut source = TestBlocks::default()
+            // Two matches after a log from another address
+            .block(
+                10,
+                Some(&[target.as_slice()]),
+                vec![vec![log(other), log(target)], vec![log(target)]],
+            )
+            // Bloom rules the address out
+            .block(11, Some(&[other.as_slice()]), vec![vec![log(other)]])
+            // Missing header
+            .block(12, None, vec![vec![log(target)]])
+            // Bloom passes, but nothing matches
+            .block(13, Some(&[target.as_slice(), other.as_slice()]), vec![vec![log(other)]]);
+
+        let mut found = Vec::new();
+        let mut progress_blocks = Vec::new();
+        let mut progress = |block| progress_blocks.push(block);
+        let stats = walk_blocks(
+            &mut source,
+            10,
+            14,
+            &ScanConfig::default(),
+            Some(&mut progress),
+            &mut position_visitor(target, &mut found),
+        )
+        .unwrap();
+
+        assert_eq!(
+            stats,
+            WalkStats {
+                blocks_scanned: 5,
+                blocks_skipped_by_bloom: 1,
+                blocks_false_positive: 1,
+                logs_matched: 2,
+            }
+        );
+        assert_eq!(progress_blocks, vec![10, 11, 12, 13, 14]);
+        let position = |transaction_index, log_index| LogPosition {
+            block_number: 10,
+            transaction_index,
+            tx_num: 10_000 + transaction_index,
+            log_index,
+        };
+        assert_eq!(found, vec![position(0, 1), position(1, 2)]);
+
+        // Without the bloom every block is read, including the one without a header
+        let mut found = Vec::new();
+        let config = ScanConfig { use_bloom: false };
+        let mut visitor = position_visitor(target, &mut found);
+        let stats = walk_blocks(&mut source, 10, 13, &config, None, &mut visitor).unwrap();
+        drop(visitor);
+        assert_eq!(stats.blocks_skipped_by_bloom, 0);
+        assert_eq!(stats.logs_matched, 3);
+        // No bloom was checked, so block 13 is no false positive
+        assert_eq!(stats.blocks_false_positive, 0);
+        assert_eq!(found.last().map(|p| p.block_number), Some(12));
+    }
+
+    #[test]
+    fn test_blocks_false_positive_counter() {
+        let target = Address::from([0x42; 20]);
+        let other = Address::from([0x99; 20]);
+        let log = |address| Log::new_unchecked(address, Vec::new(), Default::default());
+        let both: &[&[u8]] = &[target.as_slice(), other.as_slice()];
+        let mut source = TestBlocks::default()
+            .block(1, Some(both), vec![vec![log(target)]])
+            .block(2, Some(both), vec![vec![log(other)]])
+            .block(3, Some(&[other.as_slice()]), vec![vec![log(other)]])
+            .block(4, Some(both), Vec::new());
+
+        let mut found = Vec::new();
+        let mut visitor = position_visitor(target, &mut found);
+        let stats = walk_blocks(&mut source, 1, 4, &ScanConfig::default(), None, &mut visitor)
+            .unwrap();
+        drop(visitor);
+        let mut result = scan_result(target, 1, 4, Vec::new(), stats);
+        // Blocks 2 and 4 passed the bloom without a match; 3 was skipped
+        assert_eq!(result.blocks_false_positive, 2);
+        assert_eq!(result.blocks_skipped_by_bloom, 1);
+
+        result.merge(scan_result(target, 5, 5, Vec::new(), stats)).unwrap();
+        assert_eq!(result.blocks_false_positive, 4);
+
+        // Results serialized before the counter existed still load, with zero
+        let mut json = serde_json::to_value(&result).unwrap();
+        json.as_object_mut().unwrap().remove("blocks_false_positive");
+        let old: EventScanResult = serde_json::from_value(json).unwrap();
+        assert_eq!(old.blocks_false_positive, 0);
+        assert_eq!(old.blocks_scanned, result.blocks_scanned);
+    }
+
+    #[test]
+    fn test_false_positives_need_a_bloom_check() {
+        let a = Address::from([0x0a; 20]);
+        let b = Address::from([0x0b; 20]);
+        let log = Log::new_unchecked(a, Vec::new(), Default::default());
+        let mut source =
+            TestBlocks::default().block(1, Some(&[a.as_slice(), b.as_slice()]), vec![vec![log]]);
+
+        for (use_bloom, b_false_positives) in [(true, 1), (false, 0)] {
+            let mut visitor = MultiAddressVisitor {
+                topics: &[],
+                index: HashMap::from([(a, 0), (b, 1)]),
+                results: [a, b]
+                    .into_iter()
+                    .map(|addr| scan_result(addr, 1, 1, Vec::new(), WalkStats::default()))
+                    .collect(),
+                matched: vec![false; 2],
+            };
+            let config = ScanConfig { use_bloom };
+            let stats = walk_blocks(&mut source, 1, 1, &config, None, &mut visitor).unwrap();
+
+            // The block matched `a`, so it is no false positive for the walk or for `a`
+            assert_eq!(stats.blocks_false_positive, 0);
+            assert_eq!(visitor.results[0].blocks_false_positive, 0);
+            assert_eq!(visitor.results[1].blocks_false_positive, b_false_positives);
+        }
+    }
+
+    #[test]
+    fn test_false_positives_count_only_the_addresses_in_the_bloom() {
+        let a = Address::from([0x0a; 20]);
+        let b = Address::from([0x0b; 20]);
+        let c = Address::from([0x0c; 20]);
+        let log = || Log::new_unchecked(a, Vec::new(), Default::default());
+        // Block 1 only holds `a`'s bit; block 2 also sets `b`'s without a log of `b`
+        let mut source = TestBlocks::default()
+            .block(1, Some(&[a.as_slice()]), vec![vec![log()]])
+            .block(2, Some(&[a.as_slice(), b.as_slice()]), vec![vec![log()]]);
+
+        let mut visitor = MultiAddressVisitor {
+            topics: &[],
+            index: HashMap::from([(a, 0), (b, 1), (c, 2)]),
+            results: [a, b, c]
+                .into_iter()
+                .map(|addr| scan_result(addr, 1, 2, Vec::new(), WalkStats::default()))
+                .collect(),
+            matched: vec![false; 3],
+        };
+        let config = ScanConfig { use_bloom: true };
+        walk_blocks(&mut source, 1, 2, &config, None, &mut visitor).unwrap();
+
+        let false_positives: Vec<_> =
+            visitor.results.iter().map(|result| result.blocks_false_positive).collect();
+        assert_eq!(false_positives, [0, 1, 0]);
+        assert_eq!(visitor.results[0].logs.len(), 2);
+    }
+
+    #[test]
+    fn test_log_matches_topics() {
+        let topic0 = B256::from([0x01; 32]);
+        let topic1 = B256::from([0x02; 32]);
+        let log = Log::new_unchecked(Address::ZERO, vec![topic0, topic1], Default::default());
+
+        assert!(log_matches_topics(&log, &[]));
+        assert!(log_matches_topics(&log, &[topic0]));
+        assert!(log_matches_topics(&log, &[topic0, topic1]));
+        assert!(!log_matches_topics(&log, &[topic1]));
+        assert!(!log_matches_topics(&log, &[topic0, topic1, topic0]));
+    }
+
+    #[test]
+    fn test_group_logs_by_block() {
+        let event = |block_number: BlockNumber, transaction_index: u64| EventLog {
+            log: Log::new_unchecked(Address::ZERO, vec![], Default::default()),
+            block_number,
+            transaction_index,
+            log_index: 0,
+            transaction_hash: None,
+            matched_filter: None,
+        };
+
+        let grouped = group_logs_by_block(vec![event(10, 0), event(10, 3), event(12, 1)]);
+        assert_eq!(grouped.keys().copied().collect::<Vec<_>>(), vec![10, 12]);
+        let in_block: Vec<u64> = grouped[&10].iter().map(|l| l.transaction_index).collect();
+        assert_eq!(in_block, vec![0, 3]);
+    }
+
+    #[test]
+    fn test_scan_state_accumulates_counters() {
+        let a = Address::from([0x01; 20]);
+        let mut state = ScanState::new(vec![a, a], 100, None);
+        assert_eq!(state.addresses(), &[a]);
+        assert_eq!(state.last_block(), None);
+
+        let run = |from_block, to_block, logs: usize| EventScanResult {
+            address: a,
+            from_block,
+            to_block,
+            logs: vec![
+                EventLog {
+                    log: Log::new_unchecked(a, vec![], Default::default()),
+                    block_number: from_block,
+                    transaction_index: 0,
+                    log_index: 0,
+                    transaction_hash: None,
+                    matched_filter: None,
+                };
+                logs
+            ],
+            blocks_scanned: to_block - from_block + 1,
+            blocks_skipped_by_bloom: 3,
+            blocks_false_positive: 1,
+        };
+        state.record(&[run(100, 109, 2)], 109);
+        assert_eq!(state.last_block(), Some(109));
+        state.record(&[run(110, 119, 1)], 119);
+
+        assert_eq!(state.next_block(), 120);
+        assert_eq!(
+            state.counters(&a),
+            Some(&ScanCounters {
+                logs_found: 3,
+                blocks_scanned: 20,
+                blocks_skipped_by_bloom: 6,
+                blocks_false_positive: 2,
+            })
+        );
+
+        let json = serde_json::to_string(&state).unwrap();
+        assert_eq!(serde_json::from_str::<ScanState>(&json).unwrap(), state);
+    }
+
+    #[test]
+    fn test_resume_range_stops_at_tip() {
+        let state = ScanState::new(vec![Address::ZERO], 100, None);
+        assert_eq!(resume_range(&state, None, 150), Some((100, 150)));
+        assert_eq!(resume_range(&state, Some(120), 150), Some((100, 120)));
+        // A to_block past the tip must not skip blocks imported later
+        assert_eq!(resume_range(&state, Some(500), 150), Some((100, 150)));
+        assert_eq!(resume_range(&state, None, 99), None);
+    }
+
+    #[test]
+    fn test_merge_scan_results() {
+        let a = Address::from([0x01; 20]);
+        let scan = |address, from_block: BlockNumber, to_block: BlockNumber| EventScanResult {
+            address,
+            from_block,
+            to_block,
+            logs: vec![EventLog {
+                log: Log::new_unchecked(address, vec![], Default::default()),
+                block_number: from_block,
+                transaction_index: 0,
+                log_index: 0,
+                transaction_hash: None,
+                matched_filter: None,
+            }],
+            blocks_scanned: to_block - from_block + 1,
+            blocks_skipped_by_bloom: 2,
+            blocks_false_positive: 1,
+        };
+
+        // Merged out of order: logs are re-sorted and the range widens
+        let mut merged = scan(a, 20, 29);
+        merged.merge(scan(a, 0, 9)).unwrap();
+        merged.merge(scan(a, 10, 19)).unwrap();
+        let blocks: Vec<BlockNumber> = merged.logs.iter().map(|l| l.block_number).collect();
+        assert_eq!(blocks, vec![0, 10, 20]);
+        assert_eq!((merged.from_block, merged.to_block), (0, 29));
+        assert_eq!(merged.blocks_scanned, 30);
+        assert_eq!(merged.blocks_skipped_by_bloom, 6);
+        assert_eq!(merged.blocks_false_positive, 3);
+
+        assert!(merged.merge(scan(Address::from([0x02; 20]), 30, 39)).is_err());
+    }
+
+    #[test]
+    fn test_scan_estimate() {
+        let estimate = ScanEstimate {
+            blocks_sampled: 1000,
+            bloom_hits: 25,
+        };
+        assert_eq!(estimate.hit_rate(), 0.025);
+        assert_eq!(estimate.estimate_blocks_with_logs(100_000), 2500);
+
+        let empty = ScanEstimate {
+            blocks_sampled: 0,
+            bloom_hits: 0,
+        };
+        assert_eq!(empty.estimate_blocks_with_logs(100_000), 0);
+    }
+
+    #[test]
+    fn test_dedup_addresses() {
+        let a = Address::from([0x01; 20]);
+        let b = Address::from([0x02; 20]);
+
+        let (unique, positions) = dedup_addresses(&[a, b, a, a]);
+        assert_eq!(unique, vec![a, b]);
+        assert_eq!(positions, vec![0, 1, 0, 0]);
+
+        let (unique, positions) = dedup_addresses(&[]);
+        assert!(unique.is_empty() && positions.is_empty());
+    }
+
+    #[test]
+    fn test_to_rpc_log() {
+        let topic0 = B256::from([0x01; 32]);
+        let event = EventLog {
+            log: Log::new_unchecked(Address::from([0xaa; 20]), vec![topic0], vec![0xbe, 0xef].into()),
+            block_number: 255,
+            transaction_index: 2,
+            log_index: 17,
+            transaction_hash: None,
+            matched_filter: None,
+        };
+
+        let rpc = event.to_rpc_log();
+        assert_eq!(rpc["address"], format!("{}", Address::from([0xaa; 20])).to_lowercase());
+        assert_eq!(rpc["topics"][0], topic0.to_string());
+        assert_eq!(rpc["data"], "0xbeef");
+        assert_eq!(rpc["blockNumber"], "0xff");
+        assert_eq!(rpc["transactionIndex"], "0x2");
+        assert_eq!(rpc["logIndex"], "0x11");
+        assert!(rpc["transactionHash"].is_null());
+    }
+
+    #[test]
+    fn test_decode_v2_events() {
+        let swap = IUniswapV2PairEvents::Swap {
+            sender: Address::from([0x11; 20]),
+            amount0In: U256::from(1000),
+            amount1In: U256::ZERO,
+            amount0Out: U256::ZERO,
+            amount1Out: U256::from(1990),
+            to: Address::from([0x22; 20]),
+        };
+        let log = Log {
+            address: Address::from([0x42; 20]),
+            data: swap.encode_log_data(),
+        };
+        assert_eq!(log.data.topics()[0], topics::SWAP_V2);
+
+        let decoded = decode_v2_swap(&log).unwrap();
+        assert_eq!(decoded.sender, swap.sender);
+        assert_eq!(decoded.to, swap.to);
+        assert_eq!(decoded.amount0In, U256::from(1000));
+        assert_eq!(decoded.amount1Out, U256::from(1990));
+        assert!(decode_v2_sync(&log).is_err());
+
+        let sync = IUniswapV2PairEvents::Sync {
+            reserve0: alloy_primitives::aliases::U112::from(5_000u64),
+            reserve1: alloy_primitives::aliases::U112::from(7_000u64),
+        };
+        let log = Log {
+            address: Address::from([0x42; 20]),
+            data: sync.encode_log_data(),
+        };
+        assert_eq!(log.data.topics()[0], topics::SYNC_V2);
+
+        let decoded = decode_v2_sync(&log).unwrap();
+        assert_eq!(decoded.reserve0.to::<u128>(), 5_000);
+        assert_eq!(decoded.reserve1.to::<u128>(), 7_000);
+    }
+
+    #[test]
+    fn test_decode_v3_swap() {
+        let swap = IUniswapV3PoolEvents::Swap {
+            sender: Address::from([0x11; 20]),
+            recipient: Address::from([0x22; 20]),
+            amount0: alloy_primitives::I256::try_from(-1000i64).unwrap(),
+            amount1: alloy_primitives::I256::try_from(2000i64).unwrap(),
+            sqrtPriceX96: alloy_primitives::aliases::U160::from(1u64 << 32),
+            liquidity: 5000,
+            tick: alloy_primitives::aliases::I24::try_from(-60i32).unwrap(),
+        };
+        let log = Log {
+            address: Address::from([0x42; 20]),
+            data: swap.encode_log_data(),
+        };
+
+        let decoded = decode_v3_swap(&log).unwrap();
+        assert_eq!(decoded.sender, swap.sender);
+        assert_eq!(decoded.amount0.unsigned_abs(), U256::from(1000));
+        assert_eq!(decoded.liquidity, 5000);
+        assert_eq!(decoded.tick.as_i32(), -60);
+    }
+
+    #[test]
+    fn test_bucket_swaps() {
+        let swap = |amount0: i64, amount1: i64, tick: i32| IUniswapV3PoolEvents::Swap {
+            sender: Address::ZERO,
+            recipient: Address::ZERO,
+            amount0: alloy_primitives::I256::try_from(amount0).unwrap(),
+            amount1: alloy_primitives::I256::try_from(amount1).unwrap(),
+            sqrtPriceX96: Default::default(),
+            liquidity: 0,
+            tick: alloy_primitives::aliases::I24::try_from(tick).unwrap(),
+        };
+        let swaps = vec![
+            (1020, swap(10, -20, 5)),
+            (1030, swap(-30, 40, 12)),
+            (1050, swap(5, -5, -4)),
+            // Last second of the [1020, 1080) window
+            (1079, swap(1, -1, 3)),
+            // First second of the next one
+            (1080, swap(-7, 8, 100)),
+            // Windows without swaps are not returned
+            (1200, swap(2, -2, 50)),
+        ];
+
+        let buckets = bucket_swaps(swaps, 60);
+        assert_eq!(
+            buckets,
+            vec![
+                SwapBucket {
+                    start_time: 1020,
+                    swap_count: 4,
+                    volume0: U256::from(46),
+                    volume1: U256::from(66),
+                    open_tick: 5,
+                    close_tick: 3,
+                    high_tick: 12,
+                    low_tick: -4,
+                },
+                SwapBucket {
+                    start_time: 1080,
+                    swap_count: 1,
+                    volume0: U256::from(7),
+                    volume1: U256::from(8),
+                    open_tick: 100,
+                    close_tick: 100,
+                    high_tick: 100,
+                    low_tick: 100,
+                },
+                SwapBucket {
+                    start_time: 1200,
+                    swap_count: 1,
+                    volume0: U256::from(2),
+                    volume1: U256::from(2),
+                    open_tick: 50,
+                    close_tick: 50,
+                    high_tick: 50,
+                    low_tick: 50,
+                },
+            ]
+        );
+        assert!(bucket_swaps(Vec::new(), 60).is_empty());
+    }
+
+    #[test]
+    fn test_decode_v4_events() {
+        let pool_id = B256::from([0x42; 32]);
+        let swap = IPoolManagerEvents::Swap {
+            id: pool_id,
+            sender: Address::from([0x11; 20]),
+            amount0: -1000,
+            amount1: 2000,
+            sqrtPriceX96: alloy_primitives::aliases::U160::from(1u64 << 32),
+            liquidity: 5000,
+            tick: alloy_primitives::aliases::I24::try_from(120i32).unwrap(),
+            fee: alloy_primitives::aliases::U24::from(3000u32),
+        };
+        let log = Log {
+            address: Address::from([0x99; 20]),
+            data: swap.encode_log_data(),
+        };
+
+        // poolId is the first indexed topic
+        assert_eq!(log.data.topics()[1], pool_id);
+
+        let decoded = decode_v4_swap(&log).unwrap();
+        assert_eq!(decoded.id, pool_id);
+        assert_eq!(decoded.amount0, -1000);
+        assert_eq!(decoded.tick.as_i32(), 120);
+
+        let modify = IPoolManagerEvents::ModifyLiquidity {
+            id: pool_id,
+            sender: Address::from([0x11; 20]),
+            tickLower: alloy_primitives::aliases::I24::try_from(-60i32).unwrap(),
+            tickUpper: alloy_primitives::aliases::I24::try_from(60i32).unwrap(),
+            liquidityDelta: alloy_primitives::I256::try_from(-500i64).unwrap(),
+            salt: B256::ZERO,
+        };
+        let log = Log {
+            address: Address::from([0x99; 20]),
+            data: modify.encode_log_data(),
+        };
+        let decoded = decode_v4_modify_liquidity(&log).unwrap();
+        assert_eq!(decoded.tickLower.as_i32(), -60);
+        assert_eq!(decoded.tickUpper.as_i32(), 60);
+        assert!(decode_v4_swap(&log).is_err());
+    }
+
+    #[test]
+    fn test_fold_liquidity_gross() {
+        let modify = |lower: i32, upper: i32, delta: i64| Log {
+            address: Address::from([0x99; 20]),
+            data: IPoolManagerEvents::ModifyLiquidity {
+                id: B256::repeat_byte(0x42),
+                sender: Address::from([0x11; 20]),
+                tickLower: alloy_primitives::aliases::I24::try_from(lower).unwrap(),
+                tickUpper: alloy_primitives::aliases::I24::try_from(upper).unwrap(),
+                liquidityDelta: alloy_primitives::I256::try_from(delta).unwrap(),
+                salt: B256::ZERO,
+            }
+            .encode_log_data(),
+        };
+        // Two positions share tick 0; the first is later closed
+        let logs = [modify(-60, 0, 100), modify(0, 120, 40), modify(-60, 0, -100)];
+
+        let gross = fold_liquidity_gross(&logs).unwrap();
+        assert_eq!(gross, BTreeMap::from([(-60, 0), (0, 40), (120, 40)]));
+    }
+
     #[test]
     #[ignore] // Requires real database
     fn test_event_scan() {
@@ -0,0 +1,553 @@
+/// Flat tabular export of collected pool data (CSV, columnar JSON, and Parquet behind
+/// the `parquet` feature), plus compressed JSON snapshots behind `gzip`/`zstd`.
+///
+/// Pools are split into two tables:
+/// - `pools`: one row per pool with reserves (V2) or slot0 + liquidity (V3/V4)
+/// - `ticks`: one row per initialized tick, keyed by pool address/protocol/pool_id
+///
+/// `U256` values are written as decimal strings. `u128`/`i128` values are written
+/// as integers in CSV and as decimal strings in JSON/Parquet (neither `serde_json::Value`
+/// nor Arrow has a 128-bit integer type).
+use eyre::Result;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::{fs, io::Write, path::Path};
+
+use crate::types::{PoolOutput, Protocol};
+
+/// Pool-level row: V2 columns are empty for V3/V4 pools and vice versa.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PoolRow {
+    pub address: String,
+    pub protocol: Protocol,
+    pub pool_id: Option<String>,
+    pub reserve0: Option<u128>,
+    pub reserve1: Option<u128>,
+    pub block_timestamp_last: Option<u32>,
+    pub sqrt_price_x96: Option<String>,
+    pub tick: Option<i32>,
+    pub liquidity: Option<u128>,
+    pub fee: Option<u32>,
+    pub observation_index: Option<u16>,
+    pub observation_cardinality: Option<u16>,
+    pub observation_cardinality_next: Option<u16>,
+    pub fee_protocol: Option<u8>,
+    pub unlocked: Option<bool>,
+}
+
+/// Tick-level row, one per initialized tick.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct TickRow {
+    pub pool_address: String,
+    pub protocol: Protocol,
+    pub pool_id: Option<String>,
+    pub tick: i32,
+    pub liquidity_gross: u128,
+    pub liquidity_net: i128,
+    pub fee_growth_outside_0_x128: String,
+    pub fee_growth_outside_1_x128: String,
+    pub tick_cumulative_outside: i64,
+    pub seconds_per_liquidity_outside_x128: String,
+    pub seconds_outside: u32,
+    pub initialized: bool,
+}
+
+/// Flatten pools into pool-level rows
+pub fn pool_rows(pools: &[PoolOutput]) -> Vec<PoolRow> {
+    pools
+        .iter()
+        .map(|pool| PoolRow {
+            address: pool.address.to_string(),
+            protocol: pool.protocol,
+            pool_id: pool.pool_id.map(|id| id.to_string()),
+            reserve0: pool.reserves.as_ref().map(|r| r.reserve0),
+            reserve1: pool.reserves.as_ref().map(|r| r.reserve1),
+            block_timestamp_last: pool.reserves.as_ref().map(|r| r.block_timestamp_last),
+            sqrt_price_x96: pool.slot0.as_ref().map(|s| s.sqrt_price_x96.to_string()),
+            tick: pool.slot0.as_ref().map(|s| s.tick),
+            liquidity: pool.liquidity,
+            fee: pool.fee,
+            observation_index: pool.slot0.as_ref().map(|s| s.observation_index),
+            observation_cardinality: pool.slot0.as_ref().map(|s| s.observation_cardinality),
+            observation_cardinality_next: pool
+                .slot0
+                .as_ref()
+                .map(|s| s.observation_cardinality_next),
+            fee_protocol: pool.slot0.as_ref().map(|s| s.fee_protocol),
+            unlocked: pool.slot0.as_ref().map(|s| s.unlocked),
+        })
+        .collect()
+}
+
+/// Flatten pools into one row per tick
+pub fn tick_rows(pools: &[PoolOutput]) -> Vec<TickRow> {
+    pools
+        .iter()
+        .flat_map(|pool| {
+            pool.ticks.iter().map(move |tick| TickRow {
+                pool_address: pool.address.to_string(),
+                protocol: pool.protocol,
+                pool_id: pool.pool_id.map(|id| id.to_string()),
+                tick: tick.tick,
+                liquidity_gross: tick.liquidity_gross,
+                liquidity_net: tick.liquidity_net,
+                fee_growth_outside_0_x128: tick.fee_growth_outside_0_x128.to_string(),
+                fee_growth_outside_1_x128: tick.fee_growth_outside_1_x128.to_string(),
+                tick_cumulative_outside: tick.tick_cumulative_outside,
+                seconds_per_liquidity_outside_x128: tick
+                    .seconds_per_liquidity_outside_x128
+                    .to_string(),
+                seconds_outside: tick.seconds_outside,
+                initialized: tick.initialized,
+            })
+        })
+        .collect()
+}
+
+/// Columnar JSON layout: parallel arrays per column instead of arrays of objects.
+///
+/// Produces `{"pools": {column: [...]}, "ticks": {column: [...]}}` with the same columns
+/// as the CSV tables, ready for e.g. `pandas.DataFrame(data["ticks"])`. The nested
+/// `PoolOutput` serialization remains the default elsewhere.
+pub fn to_flat_json(pools: &[PoolOutput]) -> Value {
+    let p = pool_rows(pools);
+    let t = tick_rows(pools);
+
+    fn col<R, T: Serialize>(rows: &[R], f: impl Fn(&R) -> T) -> Value {
+        Value::from_iter(rows.iter().map(|r| json!(f(r))))
+    }
+    let str128 = |v: Option<u128>| v.map(|v| v.to_string());
+
+    json!({
+        "pools": {
+            "address": col(&p, |r| r.address.clone()),
+            "protocol": col(&p, |r| r.protocol),
+            "pool_id": col(&p, |r| r.pool_id.clone()),
+            "reserve0": col(&p, |r| str128(r.reserve0)),
+            "reserve1": col(&p, |r| str128(r.reserve1)),
+            "block_timestamp_last": col(&p, |r| r.block_timestamp_last),
+            "sqrt_price_x96": col(&p, |r| r.sqrt_price_x96.clone()),
+            "tick": col(&p, |r| r.tick),
+            "liquidity": col(&p, |r| str128(r.liquidity)),
+            "fee": col(&p, |r| r.fee),
+            "observation_index": col(&p, |r| r.observation_index),
+            "observation_cardinality": col(&p, |r| r.observation_cardinality),
+            "observation_cardinality_next": col(&p, |r| r.observation_cardinality_next),
+            "fee_protocol": col(&p, |r| r.fee_protocol),
+            "unlocked": col(&p, |r| r.unlocked),
+        },
+        "ticks": {
+            "pool_address": col(&t, |r| r.pool_address.clone()),
+            "protocol": col(&t, |r| r.protocol),
+            "pool_id": col(&t, |r| r.pool_id.clone()),
+            "tick": col(&t, |r| r.tick),
+            "liquidity_gross": col(&t, |r| r.liquidity_gross.to_string()),
+            "liquidity_net": col(&t, |r| r.liquidity_net.to_string()),
+            "fee_growth_outside_0_x128": col(&t, |r| r.fee_growth_outside_0_x128.clone()),
+            "fee_growth_outside_1_x128": col(&t, |r| r.fee_growth_outside_1_x128.clone()),
+            "tick_cumulative_outside": col(&t, |r| r.tick_cumulative_outside),
+            "seconds_per_liquidity_outside_x128": col(&t, |r| r.seconds_per_liquidity_outside_x128.clone()),
+            "seconds_outside": col(&t, |r| r.seconds_outside),
+            "initialized": col(&t, |r| r.initialized),
+        },
+    })
+}
+
+fn write_rows_csv<W: Write, R: Serialize>(rows: &[R], writer: W) -> Result<()> {
+    let mut csv_writer = csv::Writer::from_writer(writer);
+    for row in rows {
+        csv_writer.serialize(row)?;
+    }
+    csv_writer.flush()?;
+    Ok(())
+}
+
+/// Write the pool-level table as CSV
+pub fn write_pools_csv<W: Write>(pools: &[PoolOutput], writer: W) -> Result<()> {
+    write_rows_csv(&pool_rows(pools), writer)
+}
+
+/// Write the tick-level table as CSV
+pub fn write_ticks_csv<W: Write>(pools: &[PoolOutput], writer: W) -> Result<()> {
+    write_rows_csv(&tick_rows(pools), writer)
+}
+
+/// Write `pools.csv` and `ticks.csv` into `dir` (created if missing)
+pub fn write_csv(pools: &[PoolOutput], dir: impl AsRef<Path>) -> Result<()> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    write_pools_csv(pools, fs::File::create(dir.join("pools.csv"))?)?;
+    write_ticks_csv(pools, fs::File::create(dir.join("ticks.csv"))?)?;
+    Ok(())
+}
+
+/// Compression codec for [`write_json_compressed`]
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsonCodec {
+    /// gzip (`.json.gz`), requires the `gzip` feature
+    #[cfg(feature = "gzip")]
+    Gzip,
+    /// Zstandard (`.json.zst`), requires the `zstd` feature
+    #[cfg(feature = "zstd")]
+    Zstd,
+}
+
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+impl JsonCodec {
+    /// Conventional file extension, e.g. `"json.gz"`
+    pub fn extension(&self) -> &'static str {
+        match self {
+            #[cfg(feature = "gzip")]
+            JsonCodec::Gzip => "json.gz",
+            #[cfg(feature = "zstd")]
+            JsonCodec::Zstd => "json.zst",
+        }
+    }
+}
+
+/// Write `pools` as compressed JSON (the same document `serde_json` produces for the
+/// slice) to `path`
+///
+/// The path is used as given; see [`JsonCodec::extension`] for the usual suffix.
+#[cfg(any(feature = "gzip", feature = "zstd"))]
+pub fn write_json_compressed(
+    pools: &[PoolOutput],
+    path: impl AsRef<Path>,
+    codec: JsonCodec,
+) -> Result<()> {
+    let file = std::io::BufWriter::new(fs::File::create(path)?);
+    match codec {
+        #[cfg(feature = "gzip")]
+        JsonCodec::Gzip => {
+            let mut encoder = flate2::write::GzEncoder::new(file, flate2::Compression::default());
+            serde_json::to_writer(&mut encoder, pools)?;
+            encoder.finish()?.flush()?;
+        }
+        #[cfg(feature = "zstd")]
+        JsonCodec::Zstd => {
+            let mut encoder = zstd::Encoder::new(file, 0)?;
+            serde_json::to_writer(&mut encoder, pools)?;
+            encoder.finish()?.flush()?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+fn protocol_name(protocol: Protocol) -> &'static str {
+    match protocol {
+        Protocol::UniswapV2 => "uniswapv2",
+        Protocol::UniswapV3 => "uniswapv3",
+        Protocol::UniswapV4 => "uniswapv4",
+    }
+}
+
+#[cfg(feature = "parquet")]
+fn write_batch_parquet(batch: arrow_array::RecordBatch, path: &Path) -> Result<()> {
+    let file = fs::File::create(path)?;
+    let mut writer = parquet::arrow::ArrowWriter::try_new(file, batch.schema(), None)?;
+    writer.write(&batch)?;
+    writer.close()?;
+    Ok(())
+}
+
+/// Write `pools.parquet` and `ticks.parquet` into `dir` (created if missing)
+#[cfg(feature = "parquet")]
+pub fn write_parquet(pools: &[PoolOutput], dir: impl AsRef<Path>) -> Result<()> {
+    use arrow_array::{
+        ArrayRef, BooleanArray, Int32Array, Int64Array, RecordBatch, StringArray, UInt16Array,
+        UInt32Array, UInt8Array,
+    };
+    use std::sync::Arc;
+
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+
+    let rows = pool_rows(pools);
+    let pools_batch = RecordBatch::try_from_iter(vec![
+        (
+            "address",
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|r| r.address.as_str()),
+            )) as ArrayRef,
+        ),
+        (
+            "protocol",
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|r| protocol_name(r.protocol)),
+            )) as ArrayRef,
+        ),
+        (
+            "pool_id",
+            Arc::new(StringArray::from_iter(
+                rows.iter().map(|r| r.pool_id.as_deref()),
+            )) as ArrayRef,
+        ),
+        (
+            "reserve0",
+            Arc::new(StringArray::from_iter(
+                rows.iter().map(|r| r.reserve0.map(|v| v.to_string())),
+            )) as ArrayRef,
+        ),
+        (
+            "reserve1",
+            Arc::new(StringArray::from_iter(
+                rows.iter().map(|r| r.reserve1.map(|v| v.to_string())),
+            )) as ArrayRef,
+        ),
+        (
+            "block_timestamp_last",
+            Arc::new(UInt32Array::from_iter(
+                rows.iter().map(|r| r.block_timestamp_last),
+            )) as ArrayRef,
+        ),
+        (
+            "sqrt_price_x96",
+            Arc::new(StringArray::from_iter(
+                rows.iter().map(|r| r.sqrt_price_x96.as_deref()),
+            )) as ArrayRef,
+        ),
+        (
+            "tick",
+            Arc::new(Int32Array::from_iter(rows.iter().map(|r| r.tick))) as ArrayRef,
+        ),
+        (
+            "liquidity",
+            Arc::new(StringArray::from_iter(
+                rows.iter().map(|r| r.liquidity.map(|v| v.to_string())),
+            )) as ArrayRef,
+        ),
+        (
+            "fee",
+            Arc::new(UInt32Array::from_iter(rows.iter().map(|r| r.fee))) as ArrayRef,
+        ),
+        (
+            "observation_index",
+            Arc::new(UInt16Array::from_iter(
+                rows.iter().map(|r| r.observation_index),
+            )) as ArrayRef,
+        ),
+        (
+            "observation_cardinality",
+            Arc::new(UInt16Array::from_iter(
+                rows.iter().map(|r| r.observation_cardinality),
+            )) as ArrayRef,
+        ),
+        (
+            "observation_cardinality_next",
+            Arc::new(UInt16Array::from_iter(
+                rows.iter().map(|r| r.observation_cardinality_next),
+            )) as ArrayRef,
+        ),
+        (
+            "fee_protocol",
+            Arc::new(UInt8Array::from_iter(rows.iter().map(|r| r.fee_protocol))) as ArrayRef,
+        ),
+        (
+            "unlocked",
+            Arc::new(BooleanArray::from_iter(rows.iter().map(|r| r.unlocked))) as ArrayRef,
+        ),
+    ])?;
+    write_batch_parquet(pools_batch, &dir.join("pools.parquet"))?;
+
+    let rows = tick_rows(pools);
+    let ticks_batch = RecordBatch::try_from_iter(vec![
+        (
+            "pool_address",
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|r| r.pool_address.as_str()),
+            )) as ArrayRef,
+        ),
+        (
+            "protocol",
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|r| protocol_name(r.protocol)),
+            )) as ArrayRef,
+        ),
+        (
+            "pool_id",
+            Arc::new(StringArray::from_iter(
+                rows.iter().map(|r| r.pool_id.as_deref()),
+            )) as ArrayRef,
+        ),
+        (
+            "tick",
+            Arc::new(Int32Array::from_iter_values(rows.iter().map(|r| r.tick))) as ArrayRef,
+        ),
+        (
+            "liquidity_gross",
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|r| r.liquidity_gross.to_string()),
+            )) as ArrayRef,
+        ),
+        (
+            "liquidity_net",
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|r| r.liquidity_net.to_string()),
+            )) as ArrayRef,
+        ),
+        (
+            "fee_growth_outside_0_x128",
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|r| r.fee_growth_outside_0_x128.as_str()),
+            )) as ArrayRef,
+        ),
+        (
+            "fee_growth_outside_1_x128",
+            Arc::new(StringArray::from_iter_values(
+                rows.iter().map(|r| r.fee_growth_outside_1_x128.as_str()),
+            )) as ArrayRef,
+        ),
+        (
+            "tick_cumulative_outside",
+            Arc::new(Int64Array::from_iter_values(
+                rows.iter().map(|r| r.tick_cumulative_outside),
+            )) as ArrayRef,
+        ),
+        (
+            "seconds_per_liquidity_outside_x128",
+            Arc::new(StringArray::from_iter_values(
+                rows.iter()
+                    .map(|r| r.seconds_per_liquidity_outside_x128.as_str()),
+            )) as ArrayRef,
+        ),
+        (
+            "seconds_outside",
+            Arc::new(UInt32Array::from_iter_values(
+                rows.iter().map(|r| r.seconds_outside),
+            )) as ArrayRef,
+        ),
+        (
+            "initialized",
+            Arc::new(BooleanArray::from_iter(
+                rows.iter().map(|r| Some(r.initialized)),
+            )) as ArrayRef,
+        ),
+    ])?;
+    write_batch_parquet(ticks_batch, &dir.join("ticks.parquet"))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Reserves, Slot0, Tick};
+    use alloy_primitives::{Address, U256};
+
+    fn sample_pools() -> Vec<PoolOutput> {
+        let v2 = PoolOutput::new_v2(
+            Address::repeat_byte(0x22),
+            Reserves {
+                raw_data: None,
+                reserve0: 1_000,
+                reserve1: 2_000,
+                block_timestamp_last: 1_700_000_000,
+            },
+        );
+
+        let tick = |tick: i32, net: i128| Tick {
+            tick,
+            raw_data: None,
+            liquidity_gross: net.unsigned_abs(),
+            liquidity_net: net,
+            fee_growth_outside_0_x128: U256::from(7u8),
+            fee_growth_outside_1_x128: U256::ZERO,
+            tick_cumulative_outside: -5,
+            seconds_per_liquidity_outside_x128: U256::ZERO,
+            seconds_outside: 0,
+            initialized: true,
+            raw_slots: Vec::new(),
+        };
+        let v3 = PoolOutput::new_v3(
+            Address::repeat_byte(0x33),
+            Slot0 {
+                raw_data: None,
+                sqrt_price_x96: U256::from(1u8) << 96,
+                tick: 0,
+                observation_index: 0,
+                observation_cardinality: 1,
+                observation_cardinality_next: 1,
+                fee_protocol: 0,
+                unlocked: true,
+            },
+            500,
+            vec![tick(-60, 500), tick(60, -500)],
+            vec![],
+        );
+
+        vec![v2, v3]
+    }
+
+    #[test]
+    fn test_write_csv_tables() {
+        let pools = sample_pools();
+
+        let mut buf = Vec::new();
+        write_pools_csv(&pools, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("address,protocol,pool_id,reserve0,reserve1"));
+        assert!(lines[1].contains("uniswapv2,,1000,2000,1700000000,"));
+        assert!(lines[2].contains("79228162514264337593543950336"));
+
+        let mut buf = Vec::new();
+        write_ticks_csv(&pools, &mut buf).unwrap();
+        let text = String::from_utf8(buf).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        // header + one row per tick; the V2 pool contributes none
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].contains(",uniswapv3,,-60,500,500,7,0,-5,0,0,true"));
+        assert!(lines[2].contains(",60,500,-500,"));
+    }
+
+    #[test]
+    fn test_to_flat_json_columns() {
+        let flat = to_flat_json(&sample_pools());
+
+        assert_eq!(flat["pools"]["protocol"], json!(["uniswapv2", "uniswapv3"]));
+        assert_eq!(flat["pools"]["reserve0"], json!(["1000", null]));
+        assert_eq!(flat["pools"]["tick"], json!([null, 0]));
+
+        assert_eq!(flat["ticks"]["tick"], json!([-60, 60]));
+        assert_eq!(flat["ticks"]["liquidity_gross"], json!(["500", "500"]));
+        assert_eq!(flat["ticks"]["liquidity_net"], json!(["500", "-500"]));
+        assert_eq!(flat["ticks"]["pool_address"].as_array().unwrap().len(), 2);
+    }
+
+    #[cfg(any(feature = "gzip", feature = "zstd"))]
+    fn assert_compressed_round_trip(
+        codec: JsonCodec,
+        decode: impl Fn(fs::File) -> std::io::Result<Vec<u8>>,
+    ) {
+        let pools = sample_pools();
+        let path = std::env::temp_dir()
+            .join(format!("scrape_rethdb_export_{}.{}", std::process::id(), codec.extension()));
+
+        write_json_compressed(&pools, &path, codec).unwrap();
+        let json = decode(fs::File::open(&path).unwrap()).unwrap();
+        fs::remove_file(&path).unwrap();
+
+        let decoded: Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(decoded, serde_json::to_value(&pools).unwrap());
+    }
+
+    #[cfg(feature = "gzip")]
+    #[test]
+    fn test_write_json_gzip() {
+        use std::io::Read;
+        assert_compressed_round_trip(JsonCodec::Gzip, |file| {
+            let mut json = Vec::new();
+            flate2::read::GzDecoder::new(file).read_to_end(&mut json)?;
+            Ok(json)
+        });
+    }
+
+    #[cfg(feature = "zstd")]
+    #[test]
+    fn test_write_json_zstd() {
+        assert_compressed_round_trip(JsonCodec::Zstd, zstd::decode_all);
+    }
+}
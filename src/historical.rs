@@ -12,11 +12,103 @@ use reth_db::{
 
 use crate::{
     decoding,
-    storage::{self, v2, v3_slots_for_factory},
+    error::ScrapeError,
+    events::validate_block_range,
+    readers::{self, check_bitmap_ticks_present, decode_pool_slot0, pool_tick_spacing},
+    storage::{self, v2},
     tick_math,
-    types::{Bitmap, BlockNumber, PoolInput, PoolOutput},
+    types::{AccountInfo, Bitmap, BlockNumber, PoolInput, PoolOutput, Reserves},
 };
 
+/// Cursors used by historical storage lookups.
+///
+/// Opening cursors is cheap but not free; a batch of pools read at the same block
+/// (see [`crate::collect_pool_data_at_block`]) creates them once and passes them to
+/// the `*_with_cursors` readers instead of reopening them for every slot.
+pub struct HistoricalCursors<TX: DbTx> {
+    history: TX::Cursor<tables::StoragesHistory>,
+    changesets: TX::DupCursor<tables::StorageChangeSets>,
+    plain_state: TX::DupCursor<tables::PlainStorageState>,
+    headers: TX::Cursor<tables::Headers>,
+}
+
+impl<TX: DbTx> HistoricalCursors<TX> {
+    /// Open the history, changeset, plain-state and header cursors on `tx`.
+    pub fn new(tx: &TX) -> Result<Self> {
+        Ok(Self {
+            history: tx.cursor_read::<tables::StoragesHistory>()?,
+            changesets: tx.cursor_dup_read::<tables::StorageChangeSets>()?,
+            plain_state: tx.cursor_dup_read::<tables::PlainStorageState>()?,
+            headers: tx.cursor_read::<tables::Headers>()?,
+        })
+    }
+
+    /// Query storage value at a specific block number; see [`get_storage_at_block`].
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "trace", skip(self), fields(%address, %storage_key))
+    )]
+    pub fn storage_at(
+        &mut self,
+        address: Address,
+        storage_key: B256,
+        block_number: BlockNumber,
+    ) -> Result<U256> {
+        use reth_db::models::storage_sharded_key::StorageShardedKey;
+
+        // Step 1: Use StoragesHistory index to find blocks where this slot changed
+        let history_key = StorageShardedKey::new(address, storage_key, block_number);
+
+        if let Some((key, block_list)) = self.history.seek(history_key)? {
+            // Verify this is the correct storage slot
+            if key.address == address && key.sharded_key.key == storage_key {
+                // Step 2: Find first changeset block STRICTLY GREATER than target
+                let rank = block_list.rank(block_number);
+                let change_block = block_list.select(rank);
+
+                // Step 3: If found, read the "before" value from that changeset
+                if let Some(change_block) = change_block {
+                    if let Some(entry) = self
+                        .changesets
+                        .seek_by_key_subkey((change_block, address).into(), storage_key)?
+                    {
+                        if entry.key == storage_key {
+                            return Ok(entry.value);
+                        }
+                    }
+
+                    // The index says the slot changed here but the changeset row is missing;
+                    // PlainState would silently give the current value instead
+                    return Err(ScrapeError::ChangesetGap {
+                        block: change_block,
+                        slot: storage_key,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        // Step 4: No future change found - value hasn't changed since target block
+        // Use current PlainState
+        if let Some(entry) = self.plain_state.seek_by_key_subkey(address, storage_key)? {
+            if entry.key == storage_key {
+                return Ok(entry.value);
+            }
+        }
+
+        // Slot was never set
+        Ok(U256::ZERO)
+    }
+
+    /// Get the timestamp (unix seconds) of a block from the `Headers` table.
+    pub fn block_timestamp(&mut self, block_number: BlockNumber) -> Result<u64> {
+        self.headers
+            .seek_exact(block_number)?
+            .map(|(_, header)| header.timestamp)
+            .ok_or_else(|| eyre!("header not found for block {block_number}"))
+    }
+}
+
 /// Query storage value at a specific block number using changesets.
 ///
 /// Returns the storage state AFTER the block executes (end of block), matching
@@ -27,6 +119,12 @@ use crate::{
 /// 2. That changeset contains the "before" value = state at our target block
 /// 3. If no such changeset exists, use current `PlainState` (value hasn't changed since)
 ///
+/// Returns [`ScrapeError::ChangesetGap`] if the history index lists a change whose
+/// changeset row is missing, rather than falling back to (wrong) current state.
+///
+/// Returns [`ScrapeError::InvalidBlockRange`] if `block_number` is past the database tip,
+/// where plain state would otherwise be returned as if it were that block's state.
+///
 /// Performance: O(log n) where n = number of changes to this slot
 pub fn get_storage_at_block<TX: DbTx>(
     tx: &TX,
@@ -34,45 +132,44 @@ pub fn get_storage_at_block<TX: DbTx>(
     storage_key: B256,
     block_number: BlockNumber,
 ) -> Result<U256> {
-    use reth_db::models::storage_sharded_key::StorageShardedKey;
-
-    // Step 1: Use StoragesHistory index to find blocks where this slot changed
-    let history_key = StorageShardedKey::new(address, storage_key, block_number);
-    let mut history_cursor = tx.cursor_read::<tables::StoragesHistory>()?;
-
-    if let Some((key, block_list)) = history_cursor.seek(history_key)? {
-        // Verify this is the correct storage slot
-        if key.address == address && key.sharded_key.key == storage_key {
-            // Step 2: Find first changeset block STRICTLY GREATER than target
-            let rank = block_list.rank(block_number);
-            let change_block = block_list.select(rank);
+    validate_block_range(tx, block_number, block_number)?;
+    HistoricalCursors::new(tx)?.storage_at(address, storage_key, block_number)
+}
 
-            // Step 3: If found, read the "before" value from that changeset
-            if let Some(change_block) = change_block {
-                let mut changeset_cursor = tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+/// Resolve a block hash to its number via the `HeaderNumbers` table.
+pub fn block_number_for_hash<TX: DbTx>(tx: &TX, block_hash: B256) -> Result<BlockNumber> {
+    tx.get::<tables::HeaderNumbers>(block_hash)?
+        .ok_or_else(|| eyre!("block hash {block_hash} not found in HeaderNumbers"))
+}
 
-                if let Some(entry) = changeset_cursor
-                    .seek_by_key_subkey((change_block, address).into(), storage_key)?
-                {
-                    if entry.key == storage_key {
-                        return Ok(entry.value);
-                    }
-                }
-            }
-        }
-    }
+/// Latest finalized block recorded by the node (`ChainState::LastFinalizedBlock`).
+///
+/// Reth persists this from the consensus client's forkchoice updates, so it is
+/// missing on a node that has never received one (e.g. still in initial sync).
+pub fn finalized_block<TX: DbTx>(tx: &TX) -> Result<BlockNumber> {
+    tx.get::<tables::ChainState>(tables::ChainStateKey::LastFinalizedBlock)?
+        .ok_or_else(|| eyre!("no finalized block recorded in ChainState"))
+}
 
-    // Step 4: No future change found - value hasn't changed since target block
-    // Use current PlainState
-    let mut storage_cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
-    if let Some(entry) = storage_cursor.seek_by_key_subkey(address, storage_key)? {
-        if entry.key == storage_key {
-            return Ok(entry.value);
-        }
-    }
+/// Query storage value at the block with hash `block_hash`.
+///
+/// Resolves the hash with [`block_number_for_hash`], then reads like
+/// [`get_storage_at_block`]. Only blocks Reth has indexed (canonical or still
+/// tracked) resolve, so a hash from a reorged-out block fails instead of silently
+/// reading a different chain's state.
+pub fn get_storage_at_block_hash<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    storage_key: B256,
+    block_hash: B256,
+) -> Result<U256> {
+    let block_number = block_number_for_hash(tx, block_hash)?;
+    get_storage_at_block(tx, address, storage_key, block_number)
+}
 
-    // Slot was never set
-    Ok(U256::ZERO)
+/// Get the timestamp (unix seconds) of a block from the `Headers` table.
+pub fn block_timestamp<TX: DbTx>(tx: &TX, block_number: BlockNumber) -> Result<u64> {
+    HistoricalCursors::new(tx)?.block_timestamp(block_number)
 }
 
 /// Read V3 pool data at a specific block number.
@@ -81,33 +178,82 @@ pub fn read_v3_pool_at_block<TX: DbTx>(
     pool: &PoolInput,
     block_number: BlockNumber,
 ) -> Result<PoolOutput> {
-    let tick_spacing = pool
-        .tick_spacing
-        .ok_or_else(|| eyre!("V3 pool missing tick_spacing"))?;
+    validate_block_range(tx, block_number, block_number)?;
+    read_v3_pool_at_block_with_cursors(&mut HistoricalCursors::new(tx)?, pool, block_number)
+}
+
+/// [`read_v3_pool_at_block`] using caller-provided cursors.
+pub fn read_v3_pool_at_block_with_cursors<TX: DbTx>(
+    cursors: &mut HistoricalCursors<TX>,
+    pool: &PoolInput,
+    block_number: BlockNumber,
+) -> Result<PoolOutput> {
+    read_v3_pool_at_block_words(cursors, pool, block_number, None)
+}
+
+/// Read V3 pool data at a specific block, only scanning bitmap words near the
+/// historical tick.
+///
+/// Reads slot0 at the block first, then queries `range_words` bitmap words on each
+/// side of its tick (see [`tick_math::word_positions_around_tick`]) instead of the
+/// full word range. Each word costs a `StoragesHistory` lookup, so this is what makes
+/// historical concentrated-liquidity snapshots practical; ticks outside the window
+/// are not returned.
+pub fn read_v3_pool_at_block_focused<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    block_number: BlockNumber,
+    range_words: i16,
+) -> Result<PoolOutput> {
+    validate_block_range(tx, block_number, block_number)?;
+    read_v3_pool_at_block_words(
+        &mut HistoricalCursors::new(tx)?,
+        pool,
+        block_number,
+        Some(range_words),
+    )
+}
+
+fn read_v3_pool_at_block_words<TX: DbTx>(
+    cursors: &mut HistoricalCursors<TX>,
+    pool: &PoolInput,
+    block_number: BlockNumber,
+    range_words: Option<i16>,
+) -> Result<PoolOutput> {
+    let tick_spacing = pool_tick_spacing(pool)?;
 
     // Get factory-specific storage slots (PancakeSwap V3 has different layout)
-    let slots = v3_slots_for_factory(pool.factory);
+    let slots = pool.v3_slots();
 
     // Read slot0 at historical block
     let slot0_slot = storage::simple_slot(slots.slot0);
-    let slot0_value = get_storage_at_block(tx, pool.address, slot0_slot, block_number)?;
-    let slot0 = decoding::decode_slot0(slot0_value)?;
+    let slot0_value = cursors.storage_at(pool.address, slot0_slot, block_number)?;
+    let slot0 = decode_pool_slot0(pool, slot0_value)?;
 
     // Read liquidity at historical block
     let liquidity_slot = storage::simple_slot(slots.liquidity);
-    let liquidity_value = get_storage_at_block(tx, pool.address, liquidity_slot, block_number)?;
-    let liquidity = u128::try_from(liquidity_value).map_err(|e| eyre!("liquidity overflow: {e}"))?;
-
-    // Generate word positions to query based on tick spacing
-    let word_positions = tick_math::generate_word_positions(tick_spacing);
+    let liquidity_value = cursors.storage_at(pool.address, liquidity_slot, block_number)?;
+    let (liquidity, liquidity_upper_bits_set) = decoding::decode_liquidity(liquidity_value);
+
+    // Generate word positions to query: everything, or a window around the historical tick,
+    // limited to the pool's tick bounds
+    let bounded =
+        tick_math::word_positions_in_range(tick_spacing, pool.tick_lower, pool.tick_upper);
+    let word_positions = match range_words {
+        Some(range) => tick_math::word_positions_around_tick(slot0.tick, tick_spacing, range)
+            .into_iter()
+            .filter(|word_pos| bounded.contains(word_pos))
+            .collect(),
+        None => bounded,
+    };
 
     // Read all bitmaps at historical block
     let mut bitmaps = Vec::new();
     for word_pos in &word_positions {
         let bitmap_slot = storage::bitmap_slot(*word_pos, slots.tick_bitmap);
-        let value = get_storage_at_block(tx, pool.address, bitmap_slot, block_number)?;
+        let value = cursors.storage_at(pool.address, bitmap_slot, block_number)?;
 
-        if value != U256::ZERO {
+        if value != U256::ZERO || pool.include_empty {
             bitmaps.push(Bitmap {
                 word_pos: *word_pos,
                 bitmap: value,
@@ -123,12 +269,14 @@ pub fn read_v3_pool_at_block<TX: DbTx>(
             tick_math::extract_ticks_from_bitmap_u256(bitmap.word_pos, &bitmap_bytes, tick_spacing);
         tick_values.extend(ticks);
     }
+    tick_values.retain(|tick| pool.tick_in_range(*tick));
+    let expected_ticks = tick_values.len();
 
     // Read tick data for each initialized tick at historical block
     let mut ticks = Vec::new();
     for tick_value in tick_values {
         let tick_slot = storage::tick_slot(tick_value, slots.ticks);
-        let value = get_storage_at_block(tx, pool.address, tick_slot, block_number)?;
+        let value = cursors.storage_at(pool.address, tick_slot, block_number)?;
 
         if value != U256::ZERO {
             let tick_data = decoding::decode_tick_info(tick_value, value)?;
@@ -136,13 +284,18 @@ pub fn read_v3_pool_at_block<TX: DbTx>(
         }
     }
 
+    check_bitmap_ticks_present(pool, tick_spacing, expected_ticks, ticks.len())?;
+    ticks.retain(|tick| pool.keeps_tick(tick));
+
     Ok(PoolOutput::new_v3(
         pool.address,
         slot0,
         liquidity,
         ticks,
         bitmaps,
-    ))
+    )
+    .with_fee(pool.output_fee())
+    .with_liquidity_upper_bits_set(liquidity_upper_bits_set))
 }
 
 /// Read V2 pool data at a specific block number.
@@ -151,13 +304,115 @@ pub fn read_v2_pool_at_block<TX: DbTx>(
     pool: &PoolInput,
     block_number: BlockNumber,
 ) -> Result<PoolOutput> {
-    let reserve_slot = storage::simple_slot(v2::RESERVE);
-    let value = get_storage_at_block(tx, pool.address, reserve_slot, block_number)?;
-    let reserves = decoding::decode_v2_reserves(value)?;
+    validate_block_range(tx, block_number, block_number)?;
+    read_v2_pool_at_block_with_cursors(&mut HistoricalCursors::new(tx)?, pool, block_number)
+}
+
+/// [`read_v2_pool_at_block`] using caller-provided cursors.
+pub fn read_v2_pool_at_block_with_cursors<TX: DbTx>(
+    cursors: &mut HistoricalCursors<TX>,
+    pool: &PoolInput,
+    block_number: BlockNumber,
+) -> Result<PoolOutput> {
+    let layout = pool.v2_reserve_layout();
+    let values = layout
+        .slots()
+        .into_iter()
+        .map(|slot| cursors.storage_at(pool.address, slot, block_number))
+        .collect::<Result<Vec<_>>>()?;
+    let reserves = decoding::decode_v2_reserves_with_layout(layout, &values)?;
 
     Ok(PoolOutput::new_v2(pool.address, reserves))
 }
 
+/// Every block in `[from_block, to_block]` where `storage_key` of `address` was modified.
+///
+/// Walks the `StoragesHistory` shards for the slot in ascending order, so the result is
+/// sorted. Useful for sampling historical state only at real change points; the value
+/// after each returned block can be read with [`get_storage_at_block`].
+pub fn slot_change_blocks<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    storage_key: B256,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<Vec<BlockNumber>> {
+    validate_block_range(tx, from_block, to_block)?;
+    walk_slot_changes(tx, address, storage_key, from_block, to_block)
+}
+
+fn walk_slot_changes<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    storage_key: B256,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<Vec<BlockNumber>> {
+    use reth_db::models::storage_sharded_key::StorageShardedKey;
+
+    let mut blocks = Vec::new();
+    let mut history_cursor = tx.cursor_read::<tables::StoragesHistory>()?;
+
+    // Shards are keyed by their highest block, so the first shard >= from_block
+    // is the one that may contain it
+    let mut shard = history_cursor.seek(StorageShardedKey::new(address, storage_key, from_block))?;
+
+    while let Some((key, block_list)) = shard {
+        if key.address != address || key.sharded_key.key != storage_key {
+            break;
+        }
+
+        for block in block_list.iter() {
+            if block > to_block {
+                return Ok(blocks);
+            }
+            if block >= from_block {
+                blocks.push(block);
+            }
+        }
+
+        shard = history_cursor.next()?;
+    }
+
+    Ok(blocks)
+}
+
+/// Reconstruct the reserves path of a V2 pool over `[from_block, to_block]`.
+///
+/// Returns the reserves at `from_block`, followed by an entry for every block in the
+/// range where the reserves slot changed, each holding the state AFTER that block.
+/// Consecutive identical values are collapsed.
+pub fn v2_reserves_history<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<Vec<(BlockNumber, Reserves)>> {
+    validate_block_range(tx, from_block, to_block)?;
+
+    let reserve_slot = storage::simple_slot(v2::RESERVE);
+    let mut cursors = HistoricalCursors::new(tx)?;
+
+    let mut last_value = cursors.storage_at(address, reserve_slot, from_block)?;
+    let mut history = vec![(from_block, decoding::decode_v2_reserves(last_value)?)];
+
+    let changes = if from_block < to_block {
+        walk_slot_changes(tx, address, reserve_slot, from_block + 1, to_block)?
+    } else {
+        Vec::new()
+    };
+
+    for block in changes {
+        let value = cursors.storage_at(address, reserve_slot, block)?;
+        if value != last_value {
+            history.push((block, decoding::decode_v2_reserves(value)?));
+            last_value = value;
+        }
+    }
+
+    Ok(history)
+}
+
 /// Read V4 pool data at a specific block number.
 pub fn read_v4_pool_at_block<TX: DbTx>(
     tx: &TX,
@@ -165,30 +420,42 @@ pub fn read_v4_pool_at_block<TX: DbTx>(
     pool_id: B256,
     block_number: BlockNumber,
 ) -> Result<PoolOutput> {
-    let tick_spacing = pool
-        .tick_spacing
-        .ok_or_else(|| eyre!("V4 pool missing tick_spacing"))?;
+    validate_block_range(tx, block_number, block_number)?;
+    read_v4_pool_at_block_with_cursors(&mut HistoricalCursors::new(tx)?, pool, pool_id, block_number)
+}
+
+/// [`read_v4_pool_at_block`] using caller-provided cursors.
+pub fn read_v4_pool_at_block_with_cursors<TX: DbTx>(
+    cursors: &mut HistoricalCursors<TX>,
+    pool: &PoolInput,
+    pool_id: B256,
+    block_number: BlockNumber,
+) -> Result<PoolOutput> {
+    let tick_spacing = pool_tick_spacing(pool)?;
+
+    let slots = pool.v4_slots();
 
     // Read slot0 at historical block
-    let slot0_slot = storage::v4_slot0_slot(pool_id);
-    let slot0_value = get_storage_at_block(tx, pool.address, slot0_slot, block_number)?;
-    let slot0 = decoding::decode_slot0(slot0_value)?;
+    let slot0_slot = slots.slot0(pool_id);
+    let slot0_value = cursors.storage_at(pool.address, slot0_slot, block_number)?;
+    let slot0 = decode_pool_slot0(pool, slot0_value)?;
 
     // Read liquidity at historical block
-    let liquidity_slot = storage::v4_liquidity_slot(pool_id);
-    let liquidity_value = get_storage_at_block(tx, pool.address, liquidity_slot, block_number)?;
-    let liquidity = u128::try_from(liquidity_value).map_err(|e| eyre!("liquidity overflow: {e}"))?;
+    let liquidity_slot = slots.liquidity(pool_id);
+    let liquidity_value = cursors.storage_at(pool.address, liquidity_slot, block_number)?;
+    let (liquidity, liquidity_upper_bits_set) = decoding::decode_liquidity(liquidity_value);
 
     // Generate word positions to query based on tick spacing
-    let word_positions = tick_math::generate_word_positions(tick_spacing);
+    let word_positions =
+        tick_math::word_positions_in_range(tick_spacing, pool.tick_lower, pool.tick_upper);
 
     // Read all bitmaps at historical block
     let mut bitmaps = Vec::new();
     for word_pos in &word_positions {
-        let bitmap_slot = storage::v4_bitmap_slot(pool_id, *word_pos);
-        let value = get_storage_at_block(tx, pool.address, bitmap_slot, block_number)?;
+        let bitmap_slot = slots.bitmap(pool_id, *word_pos);
+        let value = cursors.storage_at(pool.address, bitmap_slot, block_number)?;
 
-        if value != U256::ZERO {
+        if value != U256::ZERO || pool.include_empty {
             bitmaps.push(Bitmap {
                 word_pos: *word_pos,
                 bitmap: value,
@@ -204,12 +471,14 @@ pub fn read_v4_pool_at_block<TX: DbTx>(
             tick_math::extract_ticks_from_bitmap_u256(bitmap.word_pos, &bitmap_bytes, tick_spacing);
         tick_values.extend(ticks);
     }
+    tick_values.retain(|tick| pool.tick_in_range(*tick));
+    let expected_ticks = tick_values.len();
 
     // Read tick data for each initialized tick at historical block
     let mut ticks = Vec::new();
     for tick_value in tick_values {
-        let tick_slot = storage::v4_tick_slot(pool_id, tick_value);
-        let value = get_storage_at_block(tx, pool.address, tick_slot, block_number)?;
+        let tick_slot = slots.tick(pool_id, tick_value);
+        let value = cursors.storage_at(pool.address, tick_slot, block_number)?;
 
         if value != U256::ZERO {
             let tick_data = decoding::decode_tick_info(tick_value, value)?;
@@ -217,6 +486,9 @@ pub fn read_v4_pool_at_block<TX: DbTx>(
         }
     }
 
+    check_bitmap_ticks_present(pool, tick_spacing, expected_ticks, ticks.len())?;
+    ticks.retain(|tick| pool.keeps_tick(tick));
+
     Ok(PoolOutput::new_v4(
         pool.address,
         pool_id,
@@ -224,7 +496,9 @@ pub fn read_v4_pool_at_block<TX: DbTx>(
         liquidity,
         ticks,
         bitmaps,
-    ))
+    )
+    .with_fee(pool.output_fee())
+    .with_liquidity_upper_bits_set(liquidity_upper_bits_set))
 }
 
 /// Query multiple storage slots at a specific block (batch optimization).
@@ -234,12 +508,79 @@ pub fn get_storage_batch_at_block<TX: DbTx>(
     storage_keys: &[B256],
     block_number: BlockNumber,
 ) -> Result<Vec<U256>> {
+    validate_block_range(tx, block_number, block_number)?;
+    let mut cursors = HistoricalCursors::new(tx)?;
     storage_keys
         .iter()
-        .map(|key| get_storage_at_block(tx, address, *key, block_number))
+        .map(|key| cursors.storage_at(address, *key, block_number))
         .collect()
 }
 
+/// Account nonce, balance and code hash as of the end of `block_number`.
+///
+/// Same lookup as [`get_storage_at_block`], through `AccountsHistory` and
+/// `AccountChangeSets`; falls back to [`readers::read_account_info`] if the account
+/// has not changed since. `None` if the account did not exist at that block.
+///
+/// Returns [`ScrapeError::AccountChangesetGap`] if the history index lists a change
+/// whose changeset row is missing.
+pub fn account_info_at_block<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    block_number: BlockNumber,
+) -> Result<Option<AccountInfo>> {
+    use reth_db::models::sharded_key::ShardedKey;
+
+    validate_block_range(tx, block_number, block_number)?;
+
+    let mut history = tx.cursor_read::<tables::AccountsHistory>()?;
+    if let Some((key, block_list)) = history.seek(ShardedKey::new(address, block_number))? {
+        if key.key == address {
+            let rank = block_list.rank(block_number);
+            if let Some(change_block) = block_list.select(rank) {
+                let mut changesets = tx.cursor_dup_read::<tables::AccountChangeSets>()?;
+                if let Some(entry) = changesets.seek_by_key_subkey(change_block, address)? {
+                    if entry.address == address {
+                        return Ok(entry.info.map(|account| AccountInfo {
+                            nonce: account.nonce,
+                            balance: account.balance,
+                            bytecode_hash: account.bytecode_hash,
+                        }));
+                    }
+                }
+                return Err(
+                    ScrapeError::AccountChangesetGap { block: change_block, address }.into()
+                );
+            }
+        }
+    }
+
+    readers::read_account_info(tx, address)
+}
+
+/// Whether any storage slot of `address` changed in blocks `(from_block, to_block]`.
+///
+/// Reth stores no per-account storage root, so this checks `StorageChangeSets`
+/// instead: one seek per block in the range, without reading any slot values. When
+/// it returns `false` a pool collected at `from_block` is still exact at `to_block`
+/// and can be skipped by incremental indexers.
+pub fn storage_changed_between<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<bool> {
+    validate_block_range(tx, from_block, to_block)?;
+
+    let mut changesets = tx.cursor_dup_read::<tables::StorageChangeSets>()?;
+    for block in from_block + 1..=to_block {
+        if changesets.seek_exact((block, address).into())?.is_some() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
 #[cfg(test)]
 mod tests {
     // These tests require a real Reth database with historical data.
@@ -1,31 +1,96 @@
+pub mod cache;
 pub mod contracts;
+pub mod db;
+pub mod error;
 pub mod decoding;
+pub mod diff;
 pub mod events;
+pub mod export;
 pub mod historical;
+pub mod pricing;
 pub mod readers;
+pub mod serde_helpers;
+pub mod simulation;
 pub mod storage;
 pub mod tick_math;
+pub mod topics;
 pub mod types;
+pub mod validation;
 
+#[cfg(feature = "async")]
+pub mod async_api;
+#[cfg(feature = "serde_bincode")]
+pub mod binary;
+#[cfg(feature = "rayon")]
+pub mod parallel;
 #[cfg(feature = "python")]
 pub mod python;
 
-use alloy_primitives::{Address, B256};
+use alloy_primitives::{Address, B256, U256};
 use eyre::{eyre, Result};
-use reth_db::{database::Database, open_db_read_only};
-use std::path::Path;
+use reth_db::{database::Database, transaction::DbTx, DatabaseEnv};
+use std::{
+    collections::{BTreeMap, HashMap},
+    path::Path,
+};
 
 use types::BlockNumber;
 
-pub use events::{EventLog, EventScanResult};
-pub use types::{Bitmap, HistoricalPoolOutput, PoolInput, PoolOutput, Protocol, Reserves, Slot0, Tick};
+#[cfg(feature = "async")]
+pub use async_api::{
+    collect_pool_data_async, collect_pool_data_at_block_async, scan_pool_events_async,
+    scan_pool_events_multi_async,
+};
+pub use cache::CachingReader;
+pub use db::{static_files_dir, Capabilities, OpenOptions};
+pub use diff::{BitmapChange, PoolDiff, TickChange};
+pub use error::ScrapeError;
+pub use events::{
+    decode_v2_swap, decode_v2_sync, decode_v3_swap, decode_v4_modify_liquidity, decode_v4_swap,
+    ensure_block_data, estimate_scan_blocks_with_logs, suggest_block_chunk_size,
+    validate_block_range, EventCountResult, EventLog, EventScanResult, ScanConfig, ScanCounters,
+    ScanEstimate, ScanState, ScanTx, StaticFileTx, SwapBucket, TouchingTx,
+};
+pub use types::{
+    AccountInfo, Bitmap, CompactPool, HistoricalPoolOutput, Observation, PoolInput, PoolKey,
+    PoolOutput, Position, Protocol, Reserves, Slot0, Tick, TickRangeAmounts, V2Oracle,
+};
+
+/// Resolve the pool ID of a V4 pool.
+///
+/// Uses the pool's own `pool_id` when set; otherwise takes the next entry of the
+/// positional `v4_pool_ids` list. Only V4 pools without an ID consume a positional entry.
+fn resolve_v4_pool_id(
+    pool: &PoolInput,
+    v4_pool_ids: Option<&[B256]>,
+    next_idx: &mut usize,
+) -> Result<B256> {
+    if let Some(pool_id) = pool.pool_id {
+        return Ok(pool_id);
+    }
+
+    let pool_ids = v4_pool_ids.ok_or_else(|| {
+        eyre!("V4 pool {} has no pool_id and no v4_pool_ids were provided", pool.address)
+    })?;
+
+    let pool_id = pool_ids.get(*next_idx).copied().ok_or_else(|| {
+        eyre!(
+            "Not enough pool IDs provided for V4 pools (need at least {})",
+            *next_idx + 1
+        )
+    })?;
+    *next_idx += 1;
+
+    Ok(pool_id)
+}
 
 /// Main function to collect pool data from reth database
 ///
 /// # Arguments
 /// * `db_path` - Path to the reth database directory
 /// * `pools` - List of pool configurations to collect data from
-/// * `v4_pool_ids` - Optional list of pool IDs for V4 pools (must match order of V4 pools in `pools`)
+/// * `v4_pool_ids` - Optional list of pool IDs for V4 pools without their own `pool_id`
+///   (must match the order of those V4 pools in `pools`)
 ///
 /// # Returns
 /// Vector of `PoolOutput` containing collected data for each pool
@@ -40,8 +105,10 @@ pub use types::{Bitmap, HistoricalPoolOutput, PoolInput, PoolOutput, Protocol, R
 ///         address: "0x1234...".parse().unwrap(),
 ///         protocol: Protocol::UniswapV3,
 ///         tick_spacing: Some(60),
-///         factory: None,
+///         ..Default::default()
 ///     },
+///     // or with the constructors and builders
+///     PoolInput::new_v3(Address::ZERO, 60).with_tick_range(Some(-600), Some(600)),
 /// ];
 ///
 /// let results = collect_pool_data("/path/to/reth/db", &pools, None).unwrap();
@@ -50,48 +117,231 @@ pub fn collect_pool_data(
     db_path: impl AsRef<Path>,
     pools: &[PoolInput],
     v4_pool_ids: Option<&[B256]>,
+) -> Result<Vec<PoolOutput>> {
+    collect_pool_data_with_options(db_path, pools, v4_pool_ids, &OpenOptions::default())
+}
+
+/// Same as [`collect_pool_data`], identifying V4 pools by [`PoolKey`] instead of pool ID
+///
+/// `v4_pool_keys` plays the role of `v4_pool_ids`: one key per V4 pool without its
+/// own `pool_id`, in order. Each key is hashed into its pool ID, and its fee is used
+/// when the pool has none.
+pub fn collect_pool_data_with_keys(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    v4_pool_keys: &[PoolKey],
+) -> Result<Vec<PoolOutput>> {
+    let mut keys = v4_pool_keys.iter();
+    let pools: Vec<PoolInput> = pools
+        .iter()
+        .map(|pool| {
+            let needs_key = pool.protocol == Protocol::UniswapV4 && pool.pool_id.is_none();
+            match if needs_key { keys.next() } else { None } {
+                Some(key) => PoolInput {
+                    pool_id: Some(key.pool_id()),
+                    fee: pool.fee.or(Some(key.fee)),
+                    ..pool.clone()
+                },
+                None => pool.clone(),
+            }
+        })
+        .collect();
+    // Pools left without an ID fail as if the positional list ran out
+    collect_pool_data(db_path, &pools, Some(&[]))
+}
+
+/// Same as [`collect_pool_data`], opening the database with custom [`OpenOptions`]
+pub fn collect_pool_data_with_options(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+    options: &OpenOptions,
 ) -> Result<Vec<PoolOutput>> {
     // Open database read-only
-    let db = open_db_read_only(db_path.as_ref(), Default::default())?;
+    let db = options.open(db_path)?;
 
     let tx = db.tx()?;
 
-    let mut results = Vec::new();
+    collect_pool_data_tx(&tx, pools, v4_pool_ids)
+}
+
+/// Same as [`collect_pool_data`], reading through an already open transaction
+///
+/// For callers that manage the Reth database themselves; every pool is read from
+/// the same snapshot `tx` sees.
+pub fn collect_pool_data_tx<TX: DbTx>(
+    tx: &TX,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+) -> Result<Vec<PoolOutput>> {
+    let mut results = Vec::with_capacity(pools.len());
+    collect_pool_data_stream_tx(tx, pools, v4_pool_ids, |_, output| results.push(output))?;
+    Ok(results)
+}
+
+/// Same as [`collect_pool_data`], handing each pool to `callback` as soon as it is read
+///
+/// `callback` receives the pool's index in `pools` and its output, in input order,
+/// so results can be persisted or progress reported while a long job runs. On error
+/// the pools already passed to `callback` stay delivered and collection stops.
+pub fn collect_pool_data_stream(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+    callback: impl FnMut(usize, PoolOutput),
+) -> Result<()> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    collect_pool_data_stream_tx(&tx, pools, v4_pool_ids, callback)
+}
+
+/// Same as [`collect_pool_data_stream`], reading through an already open transaction
+pub fn collect_pool_data_stream_tx<TX: DbTx>(
+    tx: &TX,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+    mut callback: impl FnMut(usize, PoolOutput),
+) -> Result<()> {
+    let mut v4_pool_id_idx = 0;
+
+    for (index, pool) in pools.iter().enumerate() {
+        #[cfg(feature = "tracing")]
+        let _pool_span = tracing::debug_span!(
+            "read_pool",
+            index,
+            address = %pool.address,
+            protocol = ?pool.protocol,
+            ticks = tracing::field::Empty,
+            bitmaps = tracing::field::Empty,
+        )
+        .entered();
+
+        let output = read_pool(tx, pool, v4_pool_ids, &mut v4_pool_id_idx)?;
+
+        #[cfg(feature = "tracing")]
+        _pool_span.record("ticks", output.ticks.len()).record("bitmaps", output.bitmaps.len());
+
+        callback(index, output);
+    }
+
+    Ok(())
+}
+
+/// Read one pool's current state, taking its V4 pool ID from `v4_pool_ids` if needed
+fn read_pool<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    v4_pool_ids: Option<&[B256]>,
+    v4_pool_id_idx: &mut usize,
+) -> Result<PoolOutput> {
+    match pool.protocol {
+        Protocol::UniswapV2 => readers::read_v2_pool(tx, pool),
+        Protocol::UniswapV3 => readers::read_v3_pool(tx, pool),
+        Protocol::UniswapV4 => {
+            let pool_id = resolve_v4_pool_id(pool, v4_pool_ids, v4_pool_id_idx)?;
+            readers::read_v4_pool(tx, pool, pool_id)
+        }
+    }
+}
+
+/// Same as [`collect_pool_data`], returning one result per pool instead of stopping
+/// at the first failure
+///
+/// A pool that cannot be read (e.g. missing `tick_spacing`) yields an `Err` in its
+/// position while the rest of the batch is still collected. Typed failures keep
+/// their [`ScrapeError`] variant; anything else becomes [`ScrapeError::PoolFailed`].
+/// Only opening the database fails the whole call.
+pub fn collect_pool_data_lenient(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+) -> Result<Vec<std::result::Result<PoolOutput, ScrapeError>>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    Ok(collect_pool_data_lenient_tx(&tx, pools, v4_pool_ids))
+}
+
+/// Same as [`collect_pool_data_lenient`], reading through an already open transaction
+pub fn collect_pool_data_lenient_tx<TX: DbTx>(
+    tx: &TX,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+) -> Vec<std::result::Result<PoolOutput, ScrapeError>> {
+    let mut v4_pool_id_idx = 0;
+
+    pools
+        .iter()
+        .map(|pool| {
+            read_pool(tx, pool, v4_pool_ids, &mut v4_pool_id_idx)
+                .map_err(|report| ScrapeError::for_pool(pool.address, report))
+        })
+        .collect()
+}
+
+/// Same as [`collect_pool_data`], reading each distinct pool only once
+///
+/// Inputs that are identical once their V4 pool ID is resolved (same address,
+/// protocol, pool ID and options) are read once, and the output is cloned into
+/// every position where the pool appears. Results keep the order of `pools`.
+pub fn collect_pool_data_dedup(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+) -> Result<Vec<PoolOutput>> {
+    let mut unique: Vec<PoolInput> = Vec::new();
+    let mut index_of: HashMap<PoolInput, usize> = HashMap::new();
+    let mut positions = Vec::with_capacity(pools.len());
     let mut v4_pool_id_idx = 0;
 
     for pool in pools {
-        match pool.protocol {
-            Protocol::UniswapV2 => {
-                let output = readers::read_v2_pool(&tx, pool)?;
-                results.push(output);
-            }
-            Protocol::UniswapV3 => {
-                let output = readers::read_v3_pool(&tx, pool)?;
-                results.push(output);
-            }
+        let resolved = match pool.protocol {
             Protocol::UniswapV4 => {
-                // V4 requires a pool ID
-                let pool_ids = v4_pool_ids.ok_or_else(|| {
-                    eyre!("V4 pools require pool_ids parameter")
-                })?;
-
-                if v4_pool_id_idx >= pool_ids.len() {
-                    return Err(eyre!(
-                        "Not enough pool IDs provided for V4 pools (need at least {})",
-                        v4_pool_id_idx + 1
-                    ));
-                }
-
-                let pool_id = pool_ids[v4_pool_id_idx];
-                v4_pool_id_idx += 1;
-
-                let output = readers::read_v4_pool(&tx, pool, pool_id)?;
-                results.push(output);
+                let pool_id = resolve_v4_pool_id(pool, v4_pool_ids, &mut v4_pool_id_idx)?;
+                PoolInput { pool_id: Some(pool_id), ..pool.clone() }
             }
-        }
+            _ => pool.clone(),
+        };
+        let index = *index_of.entry(resolved.clone()).or_insert_with(|| {
+            unique.push(resolved);
+            unique.len() - 1
+        });
+        positions.push(index);
     }
 
-    Ok(results)
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+    let outputs = collect_pool_data_tx(&tx, &unique, None)?;
+
+    Ok(positions.into_iter().map(|index| outputs[index].clone()).collect())
+}
+
+/// Estimate the memory a [`collect_pool_data`] call would use, in bytes
+///
+/// Reads only each pool's bitmap words to count initialized ticks (see
+/// [`readers::estimate_pool_heap_size`]), so it is far cheaper than the collection
+/// itself. Useful for choosing batch sizes before collecting thousands of pools.
+pub fn estimate_collection_size(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+) -> Result<usize> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    let mut total = pools.len() * std::mem::size_of::<PoolOutput>();
+    let mut v4_pool_id_idx = 0;
+    for pool in pools {
+        let pool_id = match pool.protocol {
+            Protocol::UniswapV4 => {
+                Some(resolve_v4_pool_id(pool, v4_pool_ids, &mut v4_pool_id_idx)?)
+            }
+            _ => None,
+        };
+        total += readers::estimate_pool_heap_size(&tx, pool, pool_id)?;
+    }
+    Ok(total)
 }
 
 /// Collect data from a single pool
@@ -121,19 +371,134 @@ pub fn collect_slot0_only(
     pool: &PoolInput,
     v4_pool_id: Option<B256>,
 ) -> Result<PoolOutput> {
-    let db = open_db_read_only(db_path.as_ref(), Default::default())?;
+    let db = OpenOptions::default().open(db_path)?;
     let tx = db.tx()?;
 
     match pool.protocol {
         Protocol::UniswapV3 => readers::read_v3_slot0_only(&tx, pool),
         Protocol::UniswapV4 => {
-            let pool_id = v4_pool_id.ok_or_else(|| eyre!("V4 pool requires pool_id"))?;
+            let pool_id = pool
+                .pool_id
+                .or(v4_pool_id)
+                .ok_or_else(|| eyre!("V4 pool requires pool_id"))?;
             readers::read_v4_slot0_only(&tx, pool, pool_id)
         }
         Protocol::UniswapV2 => Err(eyre!("collect_slot0_only only supports V3/V4 pools")),
     }
 }
 
+/// Read only slot0 of many V3/V4 pools in one transaction, for price feeds
+///
+/// One storage read per pool; see [`readers::read_slot0_only`]. V4 pools must carry
+/// their own `pool_id`.
+pub fn collect_slot0s(db_path: impl AsRef<Path>, pools: &[PoolInput]) -> Result<Vec<Slot0>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    pools.iter().map(|pool| readers::read_slot0_only(&tx, pool)).collect()
+}
+
+/// Read a V3 pool's active oracle observations, oldest first
+///
+/// See [`readers::read_v3_observations`].
+pub fn collect_v3_observations(
+    db_path: impl AsRef<Path>,
+    pool: &PoolInput,
+) -> Result<Vec<Observation>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    readers::read_v3_observations(&tx, pool)
+}
+
+/// Arithmetic-mean tick of a V3 pool over the last `seconds_ago` seconds
+///
+/// See [`readers::compute_twap`].
+pub fn collect_v3_twap(db_path: impl AsRef<Path>, pool: &PoolInput, seconds_ago: u32) -> Result<i32> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    readers::compute_twap(&tx, pool, seconds_ago)
+}
+
+/// Dump raw storage words of any contract; see [`readers::read_raw_slots`]
+pub fn collect_raw_slots(
+    db_path: impl AsRef<Path>,
+    address: Address,
+    slots: &[B256],
+) -> Result<Vec<(B256, U256)>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    readers::read_raw_slots(&tx, address, slots)
+}
+
+/// Read a V3 liquidity position by its key
+///
+/// See [`readers::read_v3_position`] and [`storage::v3_position_key`].
+pub fn collect_v3_position(
+    db_path: impl AsRef<Path>,
+    pool: &PoolInput,
+    position_key: B256,
+) -> Result<Position> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    readers::read_v3_position(&tx, pool, position_key)
+}
+
+/// Read a V4 pool's global fee growth accumulators; see [`readers::read_v4_fee_growth`]
+pub fn collect_v4_fee_growth(
+    db_path: impl AsRef<Path>,
+    pool: &PoolInput,
+    pool_id: B256,
+) -> Result<(U256, U256)> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    readers::read_v4_fee_growth(&tx, pool, pool_id)
+}
+
+/// Read every slot of one V3/V4 tick; see [`readers::read_tick_full`]
+pub fn collect_tick_full(
+    db_path: impl AsRef<Path>,
+    pool: &PoolInput,
+    v4_pool_id: Option<B256>,
+    tick: i32,
+) -> Result<Tick> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    readers::read_tick_full(&tx, pool, v4_pool_id, tick)
+}
+
+/// Report which features are usable on the database at `db_path`
+///
+/// A one-call diagnostic for pruned or static-file-backed nodes, where reads
+/// otherwise return zeros or no logs; see [`db::check_capabilities`].
+pub fn check_db(db_path: impl AsRef<Path>) -> Result<Capabilities> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    db::check_capabilities(&tx)
+}
+
+/// Classify a set of raw addresses as V2/V3 pools by probing their storage
+///
+/// Entries are `None` where the layout is inconclusive; see [`readers::detect_protocol`].
+pub fn detect_pool_protocols(
+    db_path: impl AsRef<Path>,
+    addresses: &[Address],
+) -> Result<Vec<Option<Protocol>>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    addresses
+        .iter()
+        .map(|address| readers::detect_protocol(&tx, *address))
+        .collect()
+}
+
 /// Helper to collect data from multiple V3 pools efficiently
 pub fn collect_v3_pools(
     db_path: impl AsRef<Path>,
@@ -149,6 +514,22 @@ pub fn collect_v3_pools(
     collect_pool_data(db_path, pools, None)
 }
 
+/// Same as [`collect_v3_pools`], finding bitmap words by walking the storage present
+/// for each pool instead of seeking every word; see [`readers::read_v3_bitmaps_present`]
+pub fn collect_v3_pools_sparse(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+) -> Result<Vec<PoolOutput>> {
+    if pools.iter().any(|pool| pool.protocol != Protocol::UniswapV3) {
+        return Err(eyre!("All pools must be UniswapV3"));
+    }
+
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    pools.iter().map(|pool| readers::read_v3_pool_sparse(&tx, pool)).collect()
+}
+
 /// Helper to collect data from multiple V2 pools efficiently
 pub fn collect_v2_pools(
     db_path: impl AsRef<Path>,
@@ -164,71 +545,357 @@ pub fn collect_v2_pools(
     collect_pool_data(db_path, pools, None)
 }
 
+/// Helper to collect data from multiple V4 pools efficiently
+///
+/// `pool_ids[i]` is the pool ID of `pools[i]`. A pool that already carries its own
+/// `pool_id` must match its entry. All pools are read together with
+/// [`readers::read_v4_pools_batch`], walking the storage cursor once per phase
+/// instead of seeking every slot.
+pub fn collect_v4_pools(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    pool_ids: &[B256],
+) -> Result<Vec<PoolOutput>> {
+    if pools.len() != pool_ids.len() {
+        return Err(eyre!(
+            "Expected one pool ID per V4 pool, got {} pools and {} pool IDs",
+            pools.len(),
+            pool_ids.len()
+        ));
+    }
+
+    // Verify all pools are V4 and pair each with its pool ID
+    let pools = pools
+        .iter()
+        .zip(pool_ids)
+        .map(|(pool, pool_id)| {
+            if pool.protocol != Protocol::UniswapV4 {
+                return Err(eyre!("All pools must be UniswapV4"));
+            }
+            if let Some(own) = pool.pool_id.filter(|own| own != pool_id) {
+                return Err(eyre!(
+                    "V4 pool {} has pool_id {} but {} was provided",
+                    pool.address,
+                    own,
+                    pool_id
+                ));
+            }
+            Ok((pool, *pool_id))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    readers::read_v4_pools_batch(&tx, &pools)
+}
+
+/// Read a V2 pair's TWAP oracle state; see [`readers::read_v2_oracle`]
+pub fn collect_v2_oracle(db_path: impl AsRef<Path>, pool: &PoolInput) -> Result<V2Oracle> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    readers::read_v2_oracle(&tx, pool)
+}
+
 /// Collect historical pool data at a specific block number
 ///
 /// # Arguments
 /// * `db_path` - Path to the reth database directory
 /// * `pools` - List of pool configurations to collect data from
-/// * `v4_pool_ids` - Optional list of pool IDs for V4 pools
+/// * `v4_pool_ids` - Optional list of pool IDs for V4 pools without their own `pool_id`
 /// * `block_number` - Block number to query state at
 ///
 /// # Returns
-/// Vector of `HistoricalPoolOutput` containing data at the specified block
+/// Vector of `HistoricalPoolOutput` containing data at the specified block,
+/// with `block_timestamp` set when the block header is present in the DB
 pub fn collect_pool_data_at_block(
     db_path: impl AsRef<Path>,
     pools: &[PoolInput],
     v4_pool_ids: Option<&[B256]>,
     block_number: BlockNumber,
 ) -> Result<Vec<HistoricalPoolOutput>> {
-    let db = open_db_read_only(db_path.as_ref(), Default::default())?;
+    collect_pool_data_at_block_with_options(
+        db_path,
+        pools,
+        v4_pool_ids,
+        block_number,
+        &OpenOptions::default(),
+    )
+}
+
+/// Same as [`collect_pool_data_at_block`], opening the database with custom [`OpenOptions`]
+pub fn collect_pool_data_at_block_with_options(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+    block_number: BlockNumber,
+    options: &OpenOptions,
+) -> Result<Vec<HistoricalPoolOutput>> {
+    let db = options.open(db_path)?;
     let tx = db.tx()?;
 
-    let mut results = Vec::new();
-    let mut v4_pool_id_idx = 0;
+    collect_pool_data_at_block_tx(&tx, pools, v4_pool_ids, block_number)
+}
 
-    for pool in pools {
-        let pool_data = match pool.protocol {
-            Protocol::UniswapV2 => {
-                historical::read_v2_pool_at_block(&tx, pool, block_number)?
-            }
-            Protocol::UniswapV3 => {
-                historical::read_v3_pool_at_block(&tx, pool, block_number)?
-            }
-            Protocol::UniswapV4 => {
-                let pool_ids = v4_pool_ids.ok_or_else(|| {
-                    eyre!("V4 pools require pool_ids parameter")
-                })?;
+/// Same as [`collect_pool_data_at_block`], identifying the block by hash
+///
+/// The hash is resolved through Reth's `HeaderNumbers` index; see
+/// [`historical::block_number_for_hash`].
+pub fn collect_pool_data_at_block_hash(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+    block_hash: B256,
+) -> Result<Vec<HistoricalPoolOutput>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
 
-                if v4_pool_id_idx >= pool_ids.len() {
-                    return Err(eyre!(
-                        "Not enough pool IDs provided for V4 pools (need at least {})",
-                        v4_pool_id_idx + 1
-                    ));
-                }
+    let block_number = historical::block_number_for_hash(&tx, block_hash)?;
+    collect_pool_data_at_block_tx(&tx, pools, v4_pool_ids, block_number)
+}
 
-                let pool_id = pool_ids[v4_pool_id_idx];
-                v4_pool_id_idx += 1;
+/// Same as [`collect_pool_data_at_block`], pinned to the latest finalized block
+///
+/// Plain state follows the canonical tip, so a read taken around a reorg can return
+/// state that is later rolled back. Reading as of the finalized block (see
+/// [`historical::finalized_block`]) only returns state that can no longer be
+/// reorged out, at the cost of lagging the tip by roughly two epochs. Each output's
+/// `block_number` is the finalized block that was read.
+pub fn collect_pool_data_finalized(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+) -> Result<Vec<HistoricalPoolOutput>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
 
-                historical::read_v4_pool_at_block(&tx, pool, pool_id, block_number)?
-            }
-        };
+    collect_pool_data_finalized_tx(&tx, pools, v4_pool_ids)
+}
+
+/// Same as [`collect_pool_data_finalized`], reading through an already open transaction
+pub fn collect_pool_data_finalized_tx<TX: DbTx>(
+    tx: &TX,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+) -> Result<Vec<HistoricalPoolOutput>> {
+    let block_number = historical::finalized_block(tx)?;
+    collect_pool_data_at_block_tx(tx, pools, v4_pool_ids, block_number)
+}
+
+/// Same as [`collect_pool_data_at_block`], reading through an already open transaction
+pub fn collect_pool_data_at_block_tx<TX: DbTx>(
+    tx: &TX,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+    block_number: BlockNumber,
+) -> Result<Vec<HistoricalPoolOutput>> {
+    events::validate_block_range(tx, block_number, block_number)?;
+
+    // One set of history/changeset/plain-state cursors for the whole batch
+    let mut cursors = historical::HistoricalCursors::new(tx)?;
+    let block_timestamp = cursors.block_timestamp(block_number).ok();
+
+    let mut results = Vec::new();
+    let mut v4_pool_id_idx = 0;
+
+    for pool in pools {
+        let pool_data = read_pool_at_block(
+            &mut cursors,
+            pool,
+            v4_pool_ids,
+            &mut v4_pool_id_idx,
+            block_number,
+        )?;
 
         results.push(HistoricalPoolOutput {
             pool_data,
             block_number,
+            block_timestamp,
         });
     }
 
     Ok(results)
 }
 
+/// Read one pool as of `block_number`, taking its V4 pool ID from `v4_pool_ids` if needed
+fn read_pool_at_block<TX: DbTx>(
+    cursors: &mut historical::HistoricalCursors<TX>,
+    pool: &PoolInput,
+    v4_pool_ids: Option<&[B256]>,
+    v4_pool_id_idx: &mut usize,
+    block_number: BlockNumber,
+) -> Result<PoolOutput> {
+    match pool.protocol {
+        Protocol::UniswapV2 => {
+            historical::read_v2_pool_at_block_with_cursors(cursors, pool, block_number)
+        }
+        Protocol::UniswapV3 => {
+            historical::read_v3_pool_at_block_with_cursors(cursors, pool, block_number)
+        }
+        Protocol::UniswapV4 => {
+            let pool_id = resolve_v4_pool_id(pool, v4_pool_ids, v4_pool_id_idx)?;
+            historical::read_v4_pool_at_block_with_cursors(cursors, pool, pool_id, block_number)
+        }
+    }
+}
+
+/// Read `decimals` of tokens that store it at `slot` / `byte_offset`, for use as a
+/// [`pricing::DecimalsCache`]; see [`readers::read_erc20_decimals`]
+///
+/// Tokens with nothing stored there are left out of the map.
+pub fn collect_token_decimals(
+    db_path: impl AsRef<Path>,
+    tokens: &[Address],
+    slot: u8,
+    byte_offset: u8,
+) -> Result<HashMap<Address, u8>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    let mut decimals = HashMap::new();
+    for token in tokens {
+        if let Some(value) = readers::read_erc20_decimals(&tx, *token, slot, byte_offset)? {
+            decimals.insert(*token, value);
+        }
+    }
+    Ok(decimals)
+}
+
+/// Read a V4 pool, scanning only `range_words` bitmap words on each side of its
+/// current tick; see [`readers::read_v4_pool_range`]
+pub fn collect_v4_pool_range(
+    db_path: impl AsRef<Path>,
+    pool: &PoolInput,
+    pool_id: B256,
+    range_words: i16,
+) -> Result<PoolOutput> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    readers::read_v4_pool_range(&tx, pool, pool_id, range_words)
+}
+
+/// Read a V4 pool with its tick set taken from ModifyLiquidity events instead of a
+/// bitmap scan
+///
+/// Folds the pool's ModifyLiquidity events from `from_block` (the pool's creation
+/// block, or earlier) to the latest block with [`events::v4_touched_ticks`], then
+/// reads only those ticks with [`readers::read_v4_pool_at_ticks`]. Starting after
+/// the creation block misses ticks whose positions were all opened before it.
+pub fn collect_v4_pool_from_events(
+    db_path: impl AsRef<Path>,
+    pool: &PoolInput,
+    pool_id: B256,
+    from_block: BlockNumber,
+) -> Result<PoolOutput> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, None)?;
+    let touched = events::v4_touched_ticks(&tx, pool.address, pool_id, from_block, to_block)?;
+    let candidates: Vec<i32> = touched.into_keys().collect();
+    readers::read_v4_pool_at_ticks(tx.mdbx(), pool, pool_id, &candidates)
+}
+
+/// Read a V3 pool at a historical block, scanning only `range_words` bitmap words
+/// on each side of the tick at that block
+///
+/// Much faster than [`collect_pool_data_at_block`] for concentrated-liquidity
+/// snapshots; see [`historical::read_v3_pool_at_block_focused`].
+pub fn collect_v3_pool_at_block_focused(
+    db_path: impl AsRef<Path>,
+    pool: &PoolInput,
+    block_number: BlockNumber,
+    range_words: i16,
+) -> Result<HistoricalPoolOutput> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    let pool_data = historical::read_v3_pool_at_block_focused(&tx, pool, block_number, range_words)?;
+
+    Ok(HistoricalPoolOutput {
+        pool_data,
+        block_number,
+        block_timestamp: historical::block_timestamp(&tx, block_number).ok(),
+    })
+}
+
+/// Reserves of a V2 pool at every block where they changed in `[from_block, to_block]`
+///
+/// The first entry is the state at `from_block`; see [`historical::v2_reserves_history`].
+pub fn get_v2_reserves_history(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<Vec<(BlockNumber, Reserves)>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    historical::v2_reserves_history(&tx, pool_address, from_block, to_block)
+}
+
+/// Blocks in `[from_block, to_block]` where a storage slot changed
+///
+/// See [`historical::slot_change_blocks`].
+pub fn get_slot_change_blocks(
+    db_path: impl AsRef<Path>,
+    address: Address,
+    storage_key: B256,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<Vec<BlockNumber>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    historical::slot_change_blocks(&tx, address, storage_key, from_block, to_block)
+}
+
+/// Whether any storage slot of `address` changed in blocks `(from_block, to_block]`
+///
+/// See [`historical::storage_changed_between`].
+pub fn get_storage_changed_between(
+    db_path: impl AsRef<Path>,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+) -> Result<bool> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    historical::storage_changed_between(&tx, address, from_block, to_block)
+}
+
+/// Nonce, balance and code hash of `address`, or `None` if it does not exist
+///
+/// See [`readers::read_account_info`].
+pub fn get_account_info(
+    db_path: impl AsRef<Path>,
+    address: Address,
+) -> Result<Option<AccountInfo>> {
+    let db = OpenOptions::default().open(db_path)?;
+    let tx = db.tx()?;
+
+    readers::read_account_info(&tx, address)
+}
+
+/// Read transaction for the event scanners over the database opened from `db_path`,
+/// paired with the node's static files ([`static_files_dir`]) when it has them
+fn open_scan_tx(
+    db: &DatabaseEnv,
+    db_path: &Path,
+) -> Result<StaticFileTx<<DatabaseEnv as Database>::TX>> {
+    StaticFileTx::open(db.tx()?, static_files_dir(db_path))
+}
+
 /// Scan for events from a pool address
 ///
 /// # Arguments
 /// * `db_path` - Path to the reth database directory
 /// * `pool_address` - Address of the pool to scan for events
 /// * `from_block` - Starting block number (inclusive)
-/// * `to_block` - Ending block number (inclusive)
+/// * `to_block` - Ending block number (inclusive); `None` scans to the latest block in the DB
 /// * `topics` - Optional topic filters (e.g., event signatures)
 ///
 /// # Returns
@@ -237,38 +904,337 @@ pub fn scan_pool_events(
     db_path: impl AsRef<Path>,
     pool_address: Address,
     from_block: BlockNumber,
-    to_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
     topics: Option<Vec<B256>>,
 ) -> Result<EventScanResult> {
-    let db = open_db_read_only(db_path.as_ref(), Default::default())?;
-    let tx = db.tx()?;
+    scan_pool_events_with_options(
+        db_path,
+        pool_address,
+        from_block,
+        to_block,
+        topics,
+        &OpenOptions::default(),
+    )
+}
+
+/// Same as [`scan_pool_events`], opening the database with custom [`OpenOptions`]
+pub fn scan_pool_events_with_options(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+    options: &OpenOptions,
+) -> Result<EventScanResult> {
+    let db = options.open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
 
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
     events::scan_events(&tx, pool_address, from_block, to_block, topics)
 }
 
+/// Same as [`scan_pool_events`] with an explicit [`ScanConfig`] (e.g. `use_bloom: false`)
+pub fn scan_pool_events_with_config(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+    config: &ScanConfig,
+) -> Result<EventScanResult> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_events_with_config(&tx, pool_address, from_block, to_block, topics, config)
+}
+
+/// Same as [`scan_pool_events`], scanning block chunks in parallel; see
+/// [`parallel::scan_events_parallel`]
+#[cfg(feature = "rayon")]
+pub fn scan_pool_events_parallel(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+    chunk_size: Option<u64>,
+) -> Result<EventScanResult> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    parallel::scan_events_parallel_with_static_files(
+        &db,
+        tx.static_files(),
+        pool_address,
+        from_block,
+        to_block,
+        topics,
+        chunk_size,
+    )
+}
+
+/// Sample every pool at every block in parallel; see
+/// [`parallel::collect_historical_grid`]
+///
+/// `result[i][j]` is `pools[i]` as of `blocks[j]`.
+#[cfg(feature = "rayon")]
+pub fn collect_historical_grid(
+    db_path: impl AsRef<Path>,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+    blocks: &[BlockNumber],
+) -> Result<Vec<Vec<HistoricalPoolOutput>>> {
+    let db = OpenOptions::default().open(db_path)?;
+
+    parallel::collect_historical_grid(&db, pools, v4_pool_ids, blocks)
+}
+
+/// Same as [`scan_pool_events`], calling `progress` with each block number as the
+/// scan reaches it (e.g. to drive a progress bar on archive-scale ranges)
+pub fn scan_pool_events_with_progress(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+    progress: &mut dyn FnMut(BlockNumber),
+) -> Result<EventScanResult> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_events_with_progress(
+        &tx,
+        pool_address,
+        from_block,
+        to_block,
+        topics,
+        Some(progress),
+    )
+}
+
+/// Blocks in `[from_block, to_block]` whose bloom filter may contain logs from
+/// `pool_address` (and all `topics`, if given), reading headers only
+///
+/// See [`events::bloom_candidate_blocks`].
+pub fn get_bloom_candidate_blocks(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+) -> Result<Vec<BlockNumber>> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::bloom_candidate_blocks(&tx, pool_address, from_block, to_block, topics)
+}
+
+/// Scan for events from a pool address, grouped by block number
+///
+/// See [`events::scan_events_by_block`].
+pub fn scan_pool_events_by_block(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+) -> Result<BTreeMap<BlockNumber, Vec<EventLog>>> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_events_by_block(&tx, pool_address, from_block, to_block, topics)
+}
+
+/// Scan for events matching any of several topic filters in a single pass
+///
+/// # Arguments
+/// * `db_path` - Path to the reth database directory
+/// * `pool_address` - Address of the pool to scan for events
+/// * `from_block` - Starting block number (inclusive)
+/// * `to_block` - Ending block number (inclusive); `None` scans to the latest block in the DB
+/// * `topic_sets` - Topic filters, OR-ed together (each set is AND-matched positionally)
+///
+/// # Returns
+/// `EventScanResult` whose logs carry `matched_filter`, the index of the set they matched
+pub fn scan_pool_events_any(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topic_sets: &[Vec<B256>],
+) -> Result<EventScanResult> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_events_any(&tx, pool_address, from_block, to_block, topic_sets)
+}
+
+/// Count events from a pool address without collecting the logs
+///
+/// Cheaper than `scan_pool_events` when only the number of matching events
+/// is needed (e.g. "how many swaps happened in this range").
+pub fn count_pool_events(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+) -> Result<EventCountResult> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::count_events(&tx, pool_address, from_block, to_block, topics)
+}
+
+/// Transactions that emitted matching events from a pool, without the logs
+///
+/// See [`events::scan_touching_transactions`].
+pub fn get_touching_transactions(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+) -> Result<Vec<TouchingTx>> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_touching_transactions(&tx, pool_address, from_block, to_block, topics)
+}
+
+/// Scan a pool's events, keeping only those from transactions sent by `sender`
+///
+/// See [`events::scan_events_by_sender`].
+pub fn scan_pool_events_by_sender(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    sender: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+) -> Result<EventScanResult> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_events_by_sender(&tx, pool_address, sender, from_block, to_block, topics)
+}
+
+/// Count events from multiple pool addresses in a single pass
+pub fn count_pool_events_multi(
+    db_path: impl AsRef<Path>,
+    pool_addresses: &[Address],
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+) -> Result<Vec<EventCountResult>> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::count_events_multi_address(&tx, pool_addresses, from_block, to_block, topics)
+}
+
+/// Get V2 Swap events for a pair
+pub fn get_v2_swap_events(
+    db_path: impl AsRef<Path>,
+    pair_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+) -> Result<EventScanResult> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::get_v2_swap_events(&tx, pair_address, from_block, to_block)
+}
+
+/// Get V2 Sync events for a pair
+pub fn get_v2_sync_events(
+    db_path: impl AsRef<Path>,
+    pair_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+) -> Result<EventScanResult> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::get_v2_sync_events(&tx, pair_address, from_block, to_block)
+}
+
 /// Get V3 Swap events for a pool
 pub fn get_v3_swap_events(
     db_path: impl AsRef<Path>,
     pool_address: Address,
     from_block: BlockNumber,
-    to_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
 ) -> Result<EventScanResult> {
-    let db = open_db_read_only(db_path.as_ref(), Default::default())?;
-    let tx = db.tx()?;
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
 
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
     events::get_v3_swap_events(&tx, pool_address, from_block, to_block)
 }
 
+/// Aggregate a V3 pool's swaps into time buckets of `window_secs`
+///
+/// Returns OHLC-style candles (in ticks) plus swap count and volume per bucket.
+pub fn get_v3_swap_buckets(
+    db_path: impl AsRef<Path>,
+    pool_address: Address,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    window_secs: u32,
+) -> Result<Vec<SwapBucket>> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_swaps_bucketed(&tx, pool_address, from_block, to_block, window_secs)
+}
+
+/// Scan all events for a single V4 pool from the PoolManager
+///
+/// # Arguments
+/// * `db_path` - Path to the reth database directory
+/// * `pool_manager` - Address of the V4 PoolManager singleton
+/// * `pool_id` - Pool ID (first indexed topic of every pool event)
+/// * `from_block` - Starting block number (inclusive)
+/// * `to_block` - Ending block number (inclusive); `None` scans to the latest block in the DB
+pub fn scan_v4_pool_events(
+    db_path: impl AsRef<Path>,
+    pool_manager: Address,
+    pool_id: B256,
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+) -> Result<EventScanResult> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_v4_pool_events(&tx, pool_manager, pool_id, from_block, to_block)
+}
+
 /// Get V3 Mint events for a pool
 pub fn get_v3_mint_events(
     db_path: impl AsRef<Path>,
     pool_address: Address,
     from_block: BlockNumber,
-    to_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
 ) -> Result<EventScanResult> {
-    let db = open_db_read_only(db_path.as_ref(), Default::default())?;
-    let tx = db.tx()?;
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
 
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
     events::get_v3_mint_events(&tx, pool_address, from_block, to_block)
 }
 
@@ -277,11 +1243,12 @@ pub fn get_v3_burn_events(
     db_path: impl AsRef<Path>,
     pool_address: Address,
     from_block: BlockNumber,
-    to_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
 ) -> Result<EventScanResult> {
-    let db = open_db_read_only(db_path.as_ref(), Default::default())?;
-    let tx = db.tx()?;
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
 
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
     events::get_v3_burn_events(&tx, pool_address, from_block, to_block)
 }
 
@@ -300,7 +1267,7 @@ pub fn get_v3_burn_events(
 /// * `db_path` - Path to the reth database directory
 /// * `pool_addresses` - List of pool addresses to scan for events
 /// * `from_block` - Starting block number (inclusive)
-/// * `to_block` - Ending block number (inclusive)
+/// * `to_block` - Ending block number (inclusive); `None` scans to the latest block in the DB
 /// * `topics` - Optional topic filters (e.g., event signatures)
 ///
 /// # Returns
@@ -309,15 +1276,87 @@ pub fn scan_pool_events_multi(
     db_path: impl AsRef<Path>,
     pool_addresses: &[Address],
     from_block: BlockNumber,
-    to_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
     topics: Option<Vec<B256>>,
 ) -> Result<Vec<EventScanResult>> {
-    let db = open_db_read_only(db_path.as_ref(), Default::default())?;
-    let tx = db.tx()?;
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
 
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
     events::scan_events_multi_address(&tx, pool_addresses, from_block, to_block, topics)
 }
 
+/// Same as [`scan_pool_events_multi`], but keyed by pool address; duplicate
+/// addresses are scanned once
+pub fn scan_pool_events_by_address(
+    db_path: impl AsRef<Path>,
+    pool_addresses: &[Address],
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+) -> Result<HashMap<Address, EventScanResult>> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_events_by_address(&tx, pool_addresses, from_block, to_block, topics)
+}
+
+/// Scan many pools for several event types in one pass, bucketed by address and topic0
+///
+/// See [`events::scan_events_multi_address_by_topic`].
+pub fn scan_pool_events_by_topic(
+    db_path: impl AsRef<Path>,
+    pool_addresses: &[Address],
+    topic0s: &[B256],
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+) -> Result<HashMap<Address, HashMap<B256, Vec<EventLog>>>> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_events_multi_address_by_topic(&tx, pool_addresses, topic0s, from_block, to_block)
+}
+
+/// Continue an incremental scan; see [`events::resume_scan`]
+///
+/// `to_block` defaults to the latest block when `None`.
+pub fn resume_pool_scan(
+    db_path: impl AsRef<Path>,
+    state: &mut ScanState,
+    to_block: impl Into<Option<BlockNumber>>,
+) -> Result<Vec<EventScanResult>> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    events::resume_scan(&tx, state, to_block.into())
+}
+
+/// Same as [`scan_pool_events_multi`], calling `progress` with each block number as
+/// the scan reaches it
+pub fn scan_pool_events_multi_with_progress(
+    db_path: impl AsRef<Path>,
+    pool_addresses: &[Address],
+    from_block: BlockNumber,
+    to_block: impl Into<Option<BlockNumber>>,
+    topics: Option<Vec<B256>>,
+    progress: &mut dyn FnMut(BlockNumber),
+) -> Result<Vec<EventScanResult>> {
+    let db = OpenOptions::default().open(&db_path)?;
+    let tx = open_scan_tx(&db, db_path.as_ref())?;
+
+    let to_block = events::resolve_to_block(&tx, to_block.into())?;
+    events::scan_events_multi_address_with_progress(
+        &tx,
+        pool_addresses,
+        from_block,
+        to_block,
+        topics,
+        Some(progress),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -336,4 +1375,23 @@ mod tests {
         assert_eq!(v3_pool.protocol, Protocol::UniswapV3);
         assert_eq!(v3_pool.tick_spacing, Some(60));
     }
+
+    #[test]
+    fn test_resolve_v4_pool_id_prefers_own_id() {
+        let addr = Address::repeat_byte(0x44);
+        let own_id = B256::repeat_byte(0xaa);
+        let positional = [B256::repeat_byte(0x01), B256::repeat_byte(0x02)];
+        let mut idx = 0;
+
+        // Self-describing pool does not consume a positional ID
+        let with_id = PoolInput::new_v4_with_id(addr, 60, own_id);
+        assert_eq!(resolve_v4_pool_id(&with_id, Some(&positional), &mut idx).unwrap(), own_id);
+        assert_eq!(idx, 0);
+
+        let without_id = PoolInput::new_v4(addr, 60);
+        assert_eq!(resolve_v4_pool_id(&without_id, Some(&positional), &mut idx).unwrap(), positional[0]);
+        assert_eq!(resolve_v4_pool_id(&without_id, Some(&positional), &mut idx).unwrap(), positional[1]);
+        assert!(resolve_v4_pool_id(&without_id, Some(&positional), &mut idx).is_err());
+        assert!(resolve_v4_pool_id(&without_id, None, &mut 0).is_err());
+    }
 }
@@ -0,0 +1,258 @@
+/// Parallel event scanning over block chunks and historical sampling (rayon).
+///
+/// The block range is split into chunks that are scanned on rayon workers, each
+/// with its own read transaction, and the per-chunk results are merged back in
+/// block order. Historical grids are split per (pool, block) the same way.
+use alloy_primitives::{Address, B256};
+use eyre::{eyre, Result};
+use rayon::prelude::*;
+use reth_db::database::Database;
+use reth_ethereum_primitives::EthPrimitives;
+use reth_provider::providers::StaticFileProvider;
+
+use crate::{
+    events::{self, EventScanResult, ScanConfig, StaticFileTx},
+    historical::HistoricalCursors,
+    resolve_v4_pool_id,
+    types::{BlockNumber, HistoricalPoolOutput, PoolInput, Protocol},
+};
+
+/// Number of blocks sampled by [`events::suggest_block_chunk_size`] when no chunk
+/// size is given
+const CHUNK_SIZE_SAMPLE: u64 = 1000;
+
+/// Split `[from_block, to_block]` into consecutive inclusive chunks of `chunk_size` blocks
+fn block_chunks(
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    chunk_size: u64,
+) -> Vec<(BlockNumber, BlockNumber)> {
+    let chunk_size = chunk_size.max(1);
+    let mut chunks = Vec::new();
+    let mut start = from_block;
+    while start <= to_block {
+        let end = start.saturating_add(chunk_size - 1).min(to_block);
+        chunks.push((start, end));
+        match end.checked_add(1) {
+            Some(next) => start = next,
+            None => break,
+        }
+    }
+    chunks
+}
+
+/// Concatenate chunk results (given in block order) into one result for the full range
+fn merge_chunks(
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    chunks: Vec<EventScanResult>,
+) -> Result<EventScanResult> {
+    let mut merged = EventScanResult {
+        address,
+        from_block,
+        to_block,
+        logs: Vec::new(),
+        blocks_scanned: 0,
+        blocks_skipped_by_bloom: 0,
+        blocks_false_positive: 0,
+    };
+    for chunk in chunks {
+        merged.merge(chunk)?;
+    }
+    Ok(merged)
+}
+
+/// [`events::scan_events`] split into chunks scanned in parallel.
+///
+/// `chunk_size` defaults to [`events::suggest_block_chunk_size`] sampled at
+/// `from_block`. Logs come back in block order and the block counters are summed
+/// over all chunks, so the result matches a sequential scan.
+pub fn scan_events_parallel<DB: Database>(
+    db: &DB,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+    chunk_size: Option<u64>,
+) -> Result<EventScanResult> {
+    scan_events_parallel_with_static_files(
+        db,
+        None,
+        address,
+        from_block,
+        to_block,
+        topics,
+        chunk_size,
+    )
+}
+
+/// [`scan_events_parallel`] also reading headers and receipts from the node's
+/// `static_files` (see [`events::StaticFileTx`]); every chunk shares the provider
+pub fn scan_events_parallel_with_static_files<DB: Database>(
+    db: &DB,
+    static_files: Option<&StaticFileProvider<EthPrimitives>>,
+    address: Address,
+    from_block: BlockNumber,
+    to_block: BlockNumber,
+    topics: Option<Vec<B256>>,
+    chunk_size: Option<u64>,
+) -> Result<EventScanResult> {
+    let scan_tx = || -> Result<StaticFileTx<DB::TX>> {
+        Ok(StaticFileTx::new(db.tx()?, static_files.cloned()))
+    };
+    {
+        let tx = scan_tx()?;
+        events::ensure_block_data(&tx, from_block, to_block)?;
+        events::validate_block_range(&tx, from_block, to_block)?;
+    }
+
+    let chunk_size = match chunk_size {
+        Some(size) => size,
+        None => {
+            let sample = CHUNK_SIZE_SAMPLE.min(to_block - from_block + 1);
+            events::suggest_block_chunk_size(&db.tx()?, from_block, sample)?
+        }
+    };
+
+    let results = block_chunks(from_block, to_block, chunk_size)
+        .into_par_iter()
+        .map(|(start, end)| {
+            let tx = scan_tx()?;
+            let config = ScanConfig::default();
+            events::scan_events_impl(&tx, address, start, end, topics.clone(), &config, None)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    merge_chunks(address, from_block, to_block, results)
+}
+
+/// Split `flat` into consecutive rows of `row_len` items (`rows` rows when empty)
+fn into_rows<T>(flat: Vec<T>, rows: usize, row_len: usize) -> Vec<Vec<T>> {
+    if row_len == 0 {
+        return (0..rows).map(|_| Vec::new()).collect();
+    }
+    let mut flat = flat.into_iter();
+    (0..rows).map(|_| flat.by_ref().take(row_len).collect()).collect()
+}
+
+/// Read every pool as of every block in `blocks`, in parallel over (pool, block) pairs.
+///
+/// Each rayon worker keeps its own read transaction and historical cursors across
+/// the pairs it processes. The result is ordered by pool, then block:
+/// `result[i][j]` is `pools[i]` at `blocks[j]`, exactly as
+/// [`crate::collect_pool_data_at_block_tx`] would return it. V4 pool IDs are
+/// resolved up front, so positional `v4_pool_ids` behave as in the serial
+/// collectors. Every block must be at or below the database tip.
+pub fn collect_historical_grid<DB: Database>(
+    db: &DB,
+    pools: &[PoolInput],
+    v4_pool_ids: Option<&[B256]>,
+    blocks: &[BlockNumber],
+) -> Result<Vec<Vec<HistoricalPoolOutput>>> {
+    let mut v4_pool_id_idx = 0;
+    let pools = pools
+        .iter()
+        .map(|pool| match pool.protocol {
+            Protocol::UniswapV4 => {
+                let pool_id = resolve_v4_pool_id(pool, v4_pool_ids, &mut v4_pool_id_idx)?;
+                Ok(PoolInput { pool_id: Some(pool_id), ..pool.clone() })
+            }
+            _ => Ok(pool.clone()),
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    if let Some(last) = blocks.iter().max() {
+        events::validate_block_range(&db.tx()?, *last, *last)?;
+    }
+
+    let pairs: Vec<(&PoolInput, BlockNumber)> = pools
+        .iter()
+        .flat_map(|pool| blocks.iter().map(move |block| (pool, *block)))
+        .collect();
+
+    let outputs = pairs
+        .into_par_iter()
+        .map_init(
+            || -> Result<_> {
+                let tx = db.tx()?;
+                let cursors = HistoricalCursors::new(&tx)?;
+                Ok((tx, cursors))
+            },
+            |state, (pool, block_number)| {
+                let (_, cursors) =
+                    state.as_mut().map_err(|e| eyre!("failed to open read transaction: {e}"))?;
+                let pool_data =
+                    crate::read_pool_at_block(cursors, pool, None, &mut 0, block_number)?;
+                Ok(HistoricalPoolOutput {
+                    pool_data,
+                    block_number,
+                    block_timestamp: cursors.block_timestamp(block_number).ok(),
+                })
+            },
+        )
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(into_rows(outputs, pools.len(), blocks.len()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::events::EventLog;
+    use alloy_primitives::Log;
+
+    #[test]
+    fn test_into_rows() {
+        assert_eq!(into_rows(vec![1, 2, 3, 4, 5, 6], 2, 3), vec![vec![1, 2, 3], vec![4, 5, 6]]);
+        assert_eq!(into_rows(Vec::<u8>::new(), 2, 0), vec![Vec::<u8>::new(), Vec::new()]);
+        assert!(into_rows(Vec::<u8>::new(), 0, 3).is_empty());
+    }
+
+    #[test]
+    fn test_block_chunks() {
+        assert_eq!(block_chunks(10, 34, 10), vec![(10, 19), (20, 29), (30, 34)]);
+        assert_eq!(block_chunks(5, 5, 100), vec![(5, 5)]);
+        assert_eq!(block_chunks(0, 2, 0), vec![(0, 0), (1, 1), (2, 2)]);
+        assert_eq!(block_chunks(u64::MAX - 1, u64::MAX, 1).len(), 2);
+    }
+
+    #[test]
+    fn test_merge_chunks_sums_counters() {
+        let chunk = |from: BlockNumber, to: BlockNumber, blocks: &[BlockNumber]| EventScanResult {
+            address: Address::ZERO,
+            from_block: from,
+            to_block: to,
+            logs: blocks
+                .iter()
+                .map(|block_number| EventLog {
+                    log: Log::new_unchecked(Address::ZERO, vec![], Default::default()),
+                    block_number: *block_number,
+                    transaction_index: 0,
+                    log_index: 0,
+                    transaction_hash: None,
+                    matched_filter: None,
+                })
+                .collect(),
+            blocks_scanned: to - from + 1,
+            blocks_skipped_by_bloom: 7,
+            blocks_false_positive: 1,
+        };
+
+        let merged = merge_chunks(
+            Address::ZERO,
+            0,
+            19,
+            vec![chunk(0, 9, &[2, 5]), chunk(10, 19, &[11])],
+        )
+        .unwrap();
+        let blocks: Vec<BlockNumber> = merged.logs.iter().map(|l| l.block_number).collect();
+        assert_eq!(blocks, vec![2, 5, 11]);
+        assert_eq!(
+            (merged.from_block, merged.to_block, merged.blocks_scanned),
+            (0, 19, 20)
+        );
+        assert_eq!(merged.blocks_skipped_by_bloom, 14);
+        assert_eq!(merged.blocks_false_positive, 2);
+    }
+}
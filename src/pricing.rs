@@ -0,0 +1,287 @@
+/// Price helpers derived from collected pool state.
+///
+/// All prices are mid-prices in human units (decimal-adjusted), quoted as
+/// token1 per token0 unless stated otherwise. Nothing here assumes a chain: token
+/// decimals always come from the caller (see [`TokenMeta`]), since the same asset can
+/// have different decimals on different networks.
+use alloy_primitives::{Address, U256};
+use eyre::{eyre, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::types::{PoolOutput, Reserves};
+
+/// Token addresses and decimals of a pool, in pool order (token0, token1).
+///
+/// Neither is stored in pool storage for V3/V4 (token addresses are immutables),
+/// so callers supply them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PoolTokens {
+    pub token0: Address,
+    pub token1: Address,
+    pub decimals0: u8,
+    pub decimals1: u8,
+}
+
+/// A token as configured for one chain: its address there, decimals and display symbol.
+///
+/// Typically loaded per network from a token list (USDC is 6 decimals on mainnet but
+/// 18 on some L2 bridges), then passed to [`price_in_base`] and friends.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct TokenMeta {
+    pub address: Address,
+    pub decimals: u8,
+    pub symbol: String,
+}
+
+impl TokenMeta {
+    pub fn new(address: Address, decimals: u8, symbol: impl Into<String>) -> Self {
+        Self { address, decimals, symbol: symbol.into() }
+    }
+}
+
+/// Known token decimals, e.g. filled once from [`crate::readers::read_erc20_decimals`]
+/// or a static token list, so pricing never needs a network call.
+pub type DecimalsCache = HashMap<Address, u8>;
+
+impl PoolTokens {
+    /// Look up both tokens' decimals in `cache`.
+    pub fn from_cache(token0: Address, token1: Address, cache: &DecimalsCache) -> Result<Self> {
+        let decimals = |token: Address| {
+            cache
+                .get(&token)
+                .copied()
+                .ok_or_else(|| eyre!("decimals for token {token} not in cache"))
+        };
+        Ok(Self {
+            token0,
+            token1,
+            decimals0: decimals(token0)?,
+            decimals1: decimals(token1)?,
+        })
+    }
+
+    /// Build from the metadata of a pool's two tokens, in either order.
+    ///
+    /// Uniswap V2/V3/V4 pools sort their tokens by address, so the lower address is
+    /// token0 (native ETH in V4, `Address::ZERO`, always sorts first).
+    pub fn from_meta(a: &TokenMeta, b: &TokenMeta) -> Self {
+        let (token0, token1) = if a.address <= b.address { (a, b) } else { (b, a) };
+        Self {
+            token0: token0.address,
+            token1: token1.address,
+            decimals0: token0.decimals,
+            decimals1: token1.decimals,
+        }
+    }
+}
+
+fn decimals_factor(decimals0: u8, decimals1: u8) -> f64 {
+    10f64.powi(decimals0 as i32 - decimals1 as i32)
+}
+
+/// Convert `sqrtPriceX96` to a token1-per-token0 price
+pub fn sqrt_price_x96_to_price(sqrt_price_x96: U256, decimals0: u8, decimals1: u8) -> f64 {
+    let sqrt_price = f64::from(sqrt_price_x96) / 2f64.powi(96);
+    sqrt_price * sqrt_price * decimals_factor(decimals0, decimals1)
+}
+
+/// Convert a tick to a token1-per-token0 price (`1.0001^tick`)
+pub fn tick_to_price(tick: i32, decimals0: u8, decimals1: u8) -> f64 {
+    1.0001f64.powi(tick) * decimals_factor(decimals0, decimals1)
+}
+
+/// Token1-per-token0 price implied by V2 reserves
+pub fn reserves_to_price(reserves: &Reserves, decimals0: u8, decimals1: u8) -> Result<f64> {
+    if reserves.reserve0 == 0 {
+        return Err(eyre!("reserve0 is zero, price undefined"));
+    }
+    Ok(reserves.reserve1 as f64 / reserves.reserve0 as f64 * decimals_factor(decimals0, decimals1))
+}
+
+/// Token1-per-token0 mid-price of a collected pool (reserves for V2, slot0 for V3/V4)
+pub fn mid_price(pool: &PoolOutput, decimals0: u8, decimals1: u8) -> Result<f64> {
+    if let Some(reserves) = &pool.reserves {
+        return reserves_to_price(reserves, decimals0, decimals1);
+    }
+    let slot0 = pool
+        .slot0
+        .as_ref()
+        .ok_or_else(|| eyre!("pool {} has neither reserves nor slot0", pool.address))?;
+    if slot0.sqrt_price_x96.is_zero() {
+        return Err(eyre!("pool {} is not initialized", pool.address));
+    }
+    Ok(sqrt_price_x96_to_price(slot0.sqrt_price_x96, decimals0, decimals1))
+}
+
+/// Price of `token` in units of the pool's other token
+fn price_of(pool: &PoolOutput, tokens: &PoolTokens, token: Address) -> Result<f64> {
+    let price = mid_price(pool, tokens.decimals0, tokens.decimals1)?;
+    if token == tokens.token0 {
+        Ok(price)
+    } else if token == tokens.token1 {
+        Ok(1.0 / price)
+    } else {
+        Err(eyre!("token {token} is not in pool {}", pool.address))
+    }
+}
+
+/// The token of `tokens` that isn't `shared_token`
+fn other_token(tokens: &PoolTokens, shared_token: Address) -> Result<Address> {
+    if tokens.token0 == shared_token {
+        Ok(tokens.token1)
+    } else if tokens.token1 == shared_token {
+        Ok(tokens.token0)
+    } else {
+        Err(eyre!("shared token {shared_token} is not in pool"))
+    }
+}
+
+/// Price a token through two pools sharing one token, e.g. TOKEN/WETH and WETH/USDC.
+///
+/// Returns the price of pool A's non-shared token in units of pool B's non-shared
+/// token (TOKEN in USDC for the example above), multiplying the two mid-prices
+/// through `shared_token`. `tokens` gives the token addresses/decimals of
+/// `(pool_a, pool_b)`.
+pub fn derived_price(
+    pool_a: &PoolOutput,
+    pool_b: &PoolOutput,
+    shared_token: Address,
+    tokens: (&PoolTokens, &PoolTokens),
+) -> Result<f64> {
+    let (tokens_a, tokens_b) = tokens;
+    let base = other_token(tokens_a, shared_token)?;
+    other_token(tokens_b, shared_token)?;
+
+    // base -> shared, then shared -> quote
+    let base_in_shared = price_of(pool_a, tokens_a, base)?;
+    let shared_in_quote = price_of(pool_b, tokens_b, shared_token)?;
+
+    Ok(base_in_shared * shared_in_quote)
+}
+
+/// Price of `token` in units of `base` from a pool holding exactly those two tokens,
+/// e.g. WETH in USDC from a WETH/USDC pool on any chain.
+pub fn price_in_base(pool: &PoolOutput, token: &TokenMeta, base: &TokenMeta) -> Result<f64> {
+    price_of(pool, &PoolTokens::from_meta(token, base), token.address)
+}
+
+/// [`derived_price`] with [`TokenMeta`] config: price of `token` in units of `base`,
+/// through `pool_a` (token/shared) and `pool_b` (shared/base).
+pub fn derived_price_in_base(
+    pool_a: &PoolOutput,
+    pool_b: &PoolOutput,
+    token: &TokenMeta,
+    shared: &TokenMeta,
+    base: &TokenMeta,
+) -> Result<f64> {
+    Ok(price_in_base(pool_a, token, shared)? * price_in_base(pool_b, shared, base)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Slot0;
+
+    fn assert_close(a: f64, b: f64) {
+        assert!((a - b).abs() <= b.abs() * 1e-9, "{a} != {b}");
+    }
+
+    fn v3_pool_at_tick(tick: i32) -> PoolOutput {
+        let slot0 = Slot0 {
+            raw_data: None,
+            sqrt_price_x96: crate::tick_math::get_sqrt_ratio_at_tick(tick).unwrap(),
+            tick,
+            observation_index: 0,
+            observation_cardinality: 1,
+            observation_cardinality_next: 1,
+            fee_protocol: 0,
+            unlocked: true,
+        };
+        PoolOutput::new_v3(Address::ZERO, slot0, 0, vec![], vec![])
+    }
+
+    #[test]
+    fn test_sqrt_price_matches_tick_price() {
+        for tick in [-200_000, -60, 0, 60, 200_000] {
+            let pool = v3_pool_at_tick(tick);
+            let sqrt = pool.slot0.as_ref().unwrap().sqrt_price_x96;
+            assert_close(sqrt_price_x96_to_price(sqrt, 18, 6), tick_to_price(tick, 18, 6));
+        }
+    }
+
+    #[test]
+    fn test_pool_tokens_from_cache() {
+        let weth = Address::repeat_byte(0x02);
+        let usdc = Address::repeat_byte(0x03);
+        let cache = DecimalsCache::from([(weth, 18), (usdc, 6)]);
+
+        let tokens = PoolTokens::from_cache(usdc, weth, &cache).unwrap();
+        assert_eq!((tokens.decimals0, tokens.decimals1), (6, 18));
+        assert_close(tick_to_price(0, tokens.decimals0, tokens.decimals1), 1e-12);
+
+        assert!(PoolTokens::from_cache(usdc, Address::repeat_byte(0x04), &cache).is_err());
+    }
+
+    #[test]
+    fn test_derived_price() {
+        let token = Address::repeat_byte(0x01);
+        let weth = Address::repeat_byte(0x02);
+        let usdc = Address::repeat_byte(0x03);
+
+        // TOKEN/WETH V2 pool (token0 = TOKEN): 1 TOKEN = 0.5 WETH
+        let pool_a = PoolOutput::new_v2(
+            Address::repeat_byte(0xa),
+            Reserves {
+                raw_data: None,
+                reserve0: 2_000_000_000_000_000_000,
+                reserve1: 1_000_000_000_000_000_000,
+                block_timestamp_last: 0,
+            },
+        );
+        let tokens_a = PoolTokens { token0: token, token1: weth, decimals0: 18, decimals1: 18 };
+
+        // USDC/WETH V2 pool (token0 = USDC, 6 decimals): 1 WETH = 3000 USDC
+        let pool_b = PoolOutput::new_v2(
+            Address::repeat_byte(0xb),
+            Reserves {
+                raw_data: None,
+                reserve0: 3_000_000_000,
+                reserve1: 1_000_000_000_000_000_000,
+                block_timestamp_last: 0,
+            },
+        );
+        let tokens_b = PoolTokens { token0: usdc, token1: weth, decimals0: 6, decimals1: 18 };
+
+        let price = derived_price(&pool_a, &pool_b, weth, (&tokens_a, &tokens_b)).unwrap();
+        assert_close(price, 1500.0);
+
+        assert!(derived_price(&pool_a, &pool_b, usdc, (&tokens_a, &tokens_b)).is_err());
+    }
+
+    #[test]
+    fn test_price_in_base_uses_configured_decimals() {
+        let weth = TokenMeta::new(Address::repeat_byte(0x02), 18, "WETH");
+        let usdc = TokenMeta::new(Address::repeat_byte(0x03), 6, "USDC");
+        // Same pool state read on a chain where the bridged USDC has 18 decimals
+        let usdc_18 = TokenMeta { decimals: 18, ..usdc.clone() };
+
+        // token0 = WETH (lower address); 1 WETH = 3000 USDC (6 decimals)
+        let pool = PoolOutput::new_v2(
+            Address::repeat_byte(0xa),
+            Reserves {
+                raw_data: None,
+                reserve0: 1_000_000_000_000_000_000,
+                reserve1: 3_000_000_000,
+                block_timestamp_last: 0,
+            },
+        );
+
+        assert_close(price_in_base(&pool, &weth, &usdc).unwrap(), 3000.0);
+        assert_close(price_in_base(&pool, &usdc, &weth).unwrap(), 1.0 / 3000.0);
+        assert_close(price_in_base(&pool, &weth, &usdc_18).unwrap(), 3e-9);
+
+        let tokens = PoolTokens::from_meta(&usdc, &weth);
+        assert_eq!((tokens.token0, tokens.decimals0), (weth.address, 18));
+    }
+}
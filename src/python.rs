@@ -3,14 +3,16 @@
 //! Build with: maturin develop --features python
 
 use pyo3::prelude::*;
-use pyo3::types::PyList;
+use pyo3::types::{PyDict, PyList, PyLong};
 use std::str::FromStr;
 
 use crate::{
-    collect_pool_data, collect_pool_data_at_block, scan_pool_events, scan_pool_events_multi,
-    get_v3_swap_events, get_v3_mint_events, get_v3_burn_events, PoolInput, Protocol,
+    collect_pool_data_at_block_with_options, collect_pool_data_with_options, scan_pool_events,
+    scan_pool_events_multi, get_v3_swap_events, get_v3_mint_events, get_v3_burn_events, Bitmap,
+    storage::{StorageLayout, V2ReserveLayout}, OpenOptions, PoolInput, PoolKey, PoolOutput,
+    Protocol, Reserves, Slot0, Tick,
 };
-use alloy_primitives::{Address, B256};
+use alloy_primitives::{Address, B256, U256};
 
 /// Python wrapper for PoolInput
 #[pyclass]
@@ -37,12 +39,325 @@ impl PyPoolInput {
     }
 }
 
+/// Convert a Python pool dict into a `PoolInput`
+///
+/// Keys: `address`, `protocol` (required), `tick_spacing`, `factory` (optional).
+fn parse_pool(item: &Bound<'_, PyAny>) -> PyResult<PoolInput> {
+    let dict = item.downcast::<pyo3::types::PyDict>()?;
+
+    let address_str: String = dict
+        .get_item("address")?
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing 'address'"))?
+        .extract()?;
+
+    let protocol_str: String = dict
+        .get_item("protocol")?
+        .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing 'protocol'"))?
+        .extract()?;
+
+    let tick_spacing: Option<i32> = match dict.get_item("tick_spacing")? {
+        Some(v) => {
+            if v.is_none() {
+                None
+            } else {
+                Some(v.extract()?)
+            }
+        }
+        None => None,
+    };
+
+    let factory: Option<Address> = match dict.get_item("factory")? {
+        Some(v) if !v.is_none() => {
+            let factory_str: String = v.extract()?;
+            Some(Address::from_str(&factory_str).map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid factory: {}", e))
+            })?)
+        }
+        _ => None,
+    };
+
+    let pool_id: Option<B256> = match dict.get_item("pool_id")? {
+        Some(v) if !v.is_none() => {
+            let pool_id_str: String = v.extract()?;
+            Some(B256::from_str(&pool_id_str).map_err(|e| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid pool_id: {}", e))
+            })?)
+        }
+        _ => None,
+    };
+
+    // A V4 "pool_key" dict is hashed into the pool ID when no "pool_id" is given,
+    // and supplies the fee when no "fee" is given
+    let pool_key = match dict.get_item("pool_key")? {
+        Some(v) if !v.is_none() => Some(parse_pool_key(&v)?),
+        _ => None,
+    };
+    let pool_id = pool_id.or_else(|| pool_key.map(|key| key.pool_id()));
+
+    let fee: Option<u32> = match dict.get_item("fee")? {
+        Some(v) if !v.is_none() => Some(v.extract()?),
+        _ => pool_key.map(|key| key.fee),
+    };
+
+    let include_empty: bool = match dict.get_item("include_empty")? {
+        Some(v) if !v.is_none() => v.extract()?,
+        _ => false,
+    };
+
+    let v4_pools_slot: Option<u8> = match dict.get_item("v4_pools_slot")? {
+        Some(v) if !v.is_none() => Some(v.extract()?),
+        _ => None,
+    };
+
+    let tick_lower: Option<i32> = match dict.get_item("tick_lower")? {
+        Some(v) if !v.is_none() => Some(v.extract()?),
+        _ => None,
+    };
+    let tick_upper: Option<i32> = match dict.get_item("tick_upper")? {
+        Some(v) if !v.is_none() => Some(v.extract()?),
+        _ => None,
+    };
+
+    let min_liquidity_gross: Option<u128> = match dict.get_item("min_liquidity_gross")? {
+        Some(v) if !v.is_none() => Some(v.extract()?),
+        _ => None,
+    };
+
+    let validate_slot0: bool = match dict.get_item("validate_slot0")? {
+        Some(v) if !v.is_none() => v.extract()?,
+        _ => false,
+    };
+
+    let layout = match dict.get_item("layout")? {
+        Some(v) if !v.is_none() => {
+            let name: String = v.extract()?;
+            Some(StorageLayout::by_name(&name).ok_or_else(|| {
+                PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Unknown layout: {}", name))
+            })?)
+        }
+        _ => None,
+    };
+
+    // V2 forks: "reserve_slot" moves the packed reserves, "reserve0_slot" /
+    // "reserve1_slot" (and optionally "timestamp_slot") select unpacked reserves
+    let optional_slot = |name: &str| -> PyResult<Option<u8>> {
+        match dict.get_item(name)? {
+            Some(v) if !v.is_none() => Ok(Some(v.extract()?)),
+            _ => Ok(None),
+        }
+    };
+    let v2_reserves = match (
+        optional_slot("reserve_slot")?,
+        optional_slot("reserve0_slot")?,
+        optional_slot("reserve1_slot")?,
+    ) {
+        (None, None, None) => None,
+        (Some(slot), None, None) => Some(V2ReserveLayout::Packed { slot }),
+        (None, Some(reserve0_slot), Some(reserve1_slot)) => Some(V2ReserveLayout::Unpacked {
+            reserve0_slot,
+            reserve1_slot,
+            timestamp_slot: optional_slot("timestamp_slot")?,
+        }),
+        _ => {
+            return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+                "Give either 'reserve_slot' or both 'reserve0_slot' and 'reserve1_slot'",
+            ))
+        }
+    };
+
+    let address = Address::from_str(&address_str)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid address: {}", e)))?;
+
+    let protocol = match protocol_str.to_lowercase().as_str() {
+        "v2" | "uniswapv2" => Protocol::UniswapV2,
+        "v3" | "uniswapv3" => Protocol::UniswapV3,
+        "v4" | "uniswapv4" => Protocol::UniswapV4,
+        _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
+            format!("Invalid protocol: {}", protocol_str)
+        )),
+    };
+
+    let pool = PoolInput {
+        address,
+        protocol,
+        tick_spacing,
+        factory,
+        pool_id,
+        include_empty,
+        tick_lower,
+        tick_upper,
+        validate_slot0,
+        layout,
+        v2_reserves,
+        min_liquidity_gross,
+        fee,
+    };
+    Ok(match v4_pools_slot {
+        Some(pools_slot) => pool.with_v4_pools_slot(pools_slot),
+        None => pool,
+    })
+}
+
+/// Convert a Python V4 pool key dict into a `PoolKey`
+///
+/// Keys: `currency0`, `currency1`, `fee`, `tick_spacing`, `hooks` (all required).
+fn parse_pool_key(item: &Bound<'_, PyAny>) -> PyResult<PoolKey> {
+    let dict = item.downcast::<pyo3::types::PyDict>()?;
+
+    let field = |name: &str| {
+        dict.get_item(name)?.ok_or_else(|| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Missing pool_key '{}'", name))
+        })
+    };
+    let address = |name: &str| -> PyResult<Address> {
+        let value: String = field(name)?.extract()?;
+        Address::from_str(&value).map_err(|e| {
+            PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid {}: {}", name, e))
+        })
+    };
+
+    Ok(PoolKey {
+        currency0: address("currency0")?,
+        currency1: address("currency1")?,
+        fee: field("fee")?.extract()?,
+        tick_spacing: field("tick_spacing")?.extract()?,
+        hooks: address("hooks")?,
+    })
+}
+
+/// Convert a list of Python pool dicts into `PoolInput`s
+fn parse_pools(pools: &Bound<'_, PyList>) -> PyResult<Vec<PoolInput>> {
+    pools.iter().map(|item| parse_pool(&item)).collect()
+}
+
+/// Convert optional hex pool-ID strings into `B256`s
+fn parse_pool_ids(v4_pool_ids: Option<Vec<String>>) -> PyResult<Option<Vec<B256>>> {
+    v4_pool_ids
+        .map(|ids| {
+            ids.iter()
+                .map(|id_str| {
+                    B256::from_str(id_str).map_err(|e| {
+                        PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
+                            "Invalid pool ID: {}",
+                            e
+                        ))
+                    })
+                })
+                .collect()
+        })
+        .transpose()
+}
+
+/// Convert a `U256` into a Python int (via its decimal representation)
+fn u256_to_py(py: Python<'_>, value: U256) -> PyResult<PyObject> {
+    Ok(py.get_type_bound::<PyLong>().call1((value.to_string(),))?.unbind())
+}
+
+fn reserves_to_py<'py>(py: Python<'py>, reserves: &Reserves) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("raw_data", &reserves.raw_data)?;
+    dict.set_item("reserve0", reserves.reserve0)?;
+    dict.set_item("reserve1", reserves.reserve1)?;
+    dict.set_item("block_timestamp_last", reserves.block_timestamp_last)?;
+    Ok(dict)
+}
+
+fn slot0_to_py<'py>(py: Python<'py>, slot0: &Slot0) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("raw_data", &slot0.raw_data)?;
+    dict.set_item("sqrt_price_x96", u256_to_py(py, slot0.sqrt_price_x96)?)?;
+    dict.set_item("tick", slot0.tick)?;
+    dict.set_item("observation_index", slot0.observation_index)?;
+    dict.set_item("observation_cardinality", slot0.observation_cardinality)?;
+    dict.set_item("observation_cardinality_next", slot0.observation_cardinality_next)?;
+    dict.set_item("fee_protocol", slot0.fee_protocol)?;
+    dict.set_item("unlocked", slot0.unlocked)?;
+    Ok(dict)
+}
+
+fn tick_to_py<'py>(py: Python<'py>, tick: &Tick) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("tick", tick.tick)?;
+    dict.set_item("raw_data", &tick.raw_data)?;
+    dict.set_item("liquidity_gross", tick.liquidity_gross)?;
+    dict.set_item("liquidity_net", tick.liquidity_net)?;
+    dict.set_item("fee_growth_outside_0_x128", u256_to_py(py, tick.fee_growth_outside_0_x128)?)?;
+    dict.set_item("fee_growth_outside_1_x128", u256_to_py(py, tick.fee_growth_outside_1_x128)?)?;
+    dict.set_item("tick_cumulative_outside", tick.tick_cumulative_outside)?;
+    dict.set_item(
+        "seconds_per_liquidity_outside_x128",
+        u256_to_py(py, tick.seconds_per_liquidity_outside_x128)?,
+    )?;
+    dict.set_item("seconds_outside", tick.seconds_outside)?;
+    dict.set_item("initialized", tick.initialized)?;
+    Ok(dict)
+}
+
+fn bitmap_to_py<'py>(py: Python<'py>, bitmap: &Bitmap) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("word_pos", bitmap.word_pos)?;
+    dict.set_item("bitmap", u256_to_py(py, bitmap.bitmap)?)?;
+    Ok(dict)
+}
+
+/// Build a Python dict for a `PoolOutput` with the same keys as its JSON form
+fn pool_output_to_py<'py>(py: Python<'py>, pool: &PoolOutput) -> PyResult<Bound<'py, PyDict>> {
+    let protocol = match pool.protocol {
+        Protocol::UniswapV2 => "uniswapv2",
+        Protocol::UniswapV3 => "uniswapv3",
+        Protocol::UniswapV4 => "uniswapv4",
+    };
+
+    let dict = PyDict::new_bound(py);
+    dict.set_item("address", format!("{:#x}", pool.address))?;
+    dict.set_item("protocol", protocol)?;
+    if let Some(pool_id) = pool.pool_id {
+        dict.set_item("pool_id", format!("{:#x}", pool_id))?;
+    }
+    match &pool.reserves {
+        Some(reserves) => dict.set_item("reserves", reserves_to_py(py, reserves)?)?,
+        None => dict.set_item("reserves", py.None())?,
+    }
+    match &pool.slot0 {
+        Some(slot0) => dict.set_item("slot0", slot0_to_py(py, slot0)?)?,
+        None => dict.set_item("slot0", py.None())?,
+    }
+    if let Some(liquidity) = pool.liquidity {
+        dict.set_item("liquidity", liquidity)?;
+    }
+    if let Some(fee) = pool.fee {
+        dict.set_item("fee", fee)?;
+    }
+    if pool.liquidity_upper_bits_set {
+        dict.set_item("liquidity_upper_bits_set", true)?;
+    }
+
+    let ticks = PyList::empty_bound(py);
+    for tick in &pool.ticks {
+        ticks.append(tick_to_py(py, tick)?)?;
+    }
+    dict.set_item("ticks", ticks)?;
+
+    let bitmaps = PyList::empty_bound(py);
+    for bitmap in &pool.bitmaps {
+        bitmaps.append(bitmap_to_py(py, bitmap)?)?;
+    }
+    dict.set_item("bitmaps", bitmaps)?;
+
+    Ok(dict)
+}
+
 /// Collect pool data from reth database
 ///
 /// Parameters:
 /// - db_path (str): Path to reth database directory
 /// - pools (List[PyPoolInput]): List of pool configurations
-/// - v4_pool_ids (Optional[List[str]]): List of pool IDs for V4 pools (hex strings)
+/// - v4_pool_ids (Optional[List[str]]): List of pool IDs for V4 pools (hex strings);
+///   V4 pool dicts may instead carry their own "pool_id" key, which takes precedence
+/// - max_readers (Optional[int]): MDBX max concurrent readers (Reth default if None)
+/// - exclusive (Optional[bool]): Open the DB with an exclusive lock (Reth default if None)
+/// - flat (bool): Return the columnar layout (`{"pools": {col: [...]}, "ticks": {col: [...]}}`,
+///   see `export::to_flat_json`) instead of a list of nested pool objects
 ///
 /// Returns:
 /// - str: JSON string containing collected pool data
@@ -61,192 +376,124 @@ impl PyPoolInput {
 /// data = json.loads(result_json)
 /// ```
 #[pyfunction]
-#[pyo3(signature = (db_path, pools, v4_pool_ids=None))]
+#[pyo3(signature = (db_path, pools, v4_pool_ids=None, max_readers=None, exclusive=None, flat=false))]
 fn collect_pools(
     db_path: String,
     pools: &Bound<'_, PyList>,
     v4_pool_ids: Option<Vec<String>>,
+    max_readers: Option<u64>,
+    exclusive: Option<bool>,
+    flat: bool,
 ) -> PyResult<String> {
-    // Convert Python pools to Rust PoolInput
-    let rust_pools: Result<Vec<PoolInput>, PyErr> = pools
-        .iter()
-        .map(|item| {
-            let dict = item.downcast::<pyo3::types::PyDict>()?;
-
-            let address_str: String = dict
-                .get_item("address")?
-                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing 'address'"))?
-                .extract()?;
-
-            let protocol_str: String = dict
-                .get_item("protocol")?
-                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing 'protocol'"))?
-                .extract()?;
-
-            let tick_spacing: Option<i32> = match dict.get_item("tick_spacing")? {
-                Some(v) => {
-                    if v.is_none() {
-                        None
-                    } else {
-                        Some(v.extract()?)
-                    }
-                }
-                None => None,
-            };
-
-            let slot0_only: bool = match dict.get_item("slot0_only")? {
-                Some(v) => v.extract().unwrap_or(false),
-                None => false,
-            };
-
-            let address = Address::from_str(&address_str)
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid address: {}", e)))?;
-
-            let protocol = match protocol_str.to_lowercase().as_str() {
-                "v2" | "uniswapv2" => Protocol::UniswapV2,
-                "v3" | "uniswapv3" => Protocol::UniswapV3,
-                "v4" | "uniswapv4" => Protocol::UniswapV4,
-                _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                    format!("Invalid protocol: {}", protocol_str)
-                )),
-            };
-
-            Ok(PoolInput {
-                address,
-                protocol,
-                tick_spacing,
-                slot0_only,
-            })
-        })
-        .collect();
-
-    let rust_pools = rust_pools?;
-
-    // Convert V4 pool IDs if provided
-    let rust_v4_pool_ids: Option<Result<Vec<B256>, PyErr>> = v4_pool_ids.map(|ids| {
-        ids.iter()
-            .map(|id_str| {
-                B256::from_str(id_str).map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                        "Invalid pool ID: {}",
-                        e
-                    ))
-                })
-            })
-            .collect()
-    });
-
-    let rust_v4_pool_ids = rust_v4_pool_ids.transpose()?;
+    let rust_pools = parse_pools(pools)?;
+    let rust_v4_pool_ids = parse_pool_ids(v4_pool_ids)?;
+    let options = OpenOptions { max_readers, exclusive };
 
     // Call Rust function
-    let results = collect_pool_data(&db_path, &rust_pools, rust_v4_pool_ids.as_deref())
+    let results = collect_pool_data_with_options(&db_path, &rust_pools, rust_v4_pool_ids.as_deref(), &options)
         .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Collection failed: {}", e)))?;
 
     // Serialize to JSON
-    let json = serde_json::to_string(&results)
-        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Serialization failed: {}", e)))?;
+    let json = if flat {
+        serde_json::to_string(&crate::export::to_flat_json(&results))
+    } else {
+        serde_json::to_string(&results)
+    }
+    .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Serialization failed: {}", e)))?;
 
     Ok(json)
 }
 
+/// Collect pool data from reth database as native Python objects
+///
+/// Same inputs as `collect_pools`, but returns a list of dicts directly
+/// instead of a JSON string, avoiding the serialize/parse round-trip.
+/// `U256`/`u128`/`i128` fields come through as Python ints.
+///
+/// Returns:
+/// - List[dict]: One dict per pool, with the same keys as the JSON output
+///
+/// Example:
+/// ```python
+/// from scrape_rethdb_data import collect_pools_native
+///
+/// pools = [{"address": "0x...", "protocol": "v3", "tick_spacing": 60}]
+/// results = collect_pools_native("/path/to/reth/db", pools)
+/// print(results[0]["slot0"]["sqrt_price_x96"])  # int
+/// ```
+#[pyfunction]
+#[pyo3(signature = (db_path, pools, v4_pool_ids=None, max_readers=None, exclusive=None))]
+fn collect_pools_native<'py>(
+    py: Python<'py>,
+    db_path: String,
+    pools: &Bound<'py, PyList>,
+    v4_pool_ids: Option<Vec<String>>,
+    max_readers: Option<u64>,
+    exclusive: Option<bool>,
+) -> PyResult<Bound<'py, PyList>> {
+    let rust_pools = parse_pools(pools)?;
+    let rust_v4_pool_ids = parse_pool_ids(v4_pool_ids)?;
+    let options = OpenOptions { max_readers, exclusive };
+
+    let results = collect_pool_data_with_options(&db_path, &rust_pools, rust_v4_pool_ids.as_deref(), &options)
+        .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Collection failed: {}", e)))?;
+
+    let list = PyList::empty_bound(py);
+    for pool in &results {
+        list.append(pool_output_to_py(py, pool)?)?;
+    }
+
+    Ok(list)
+}
+
 /// Collect pool data at a specific block number
 ///
 /// Parameters:
 /// - db_path (str): Path to reth database directory
 /// - pools (List[dict]): List of pool configurations
-/// - v4_pool_ids (Optional[List[str]]): List of pool IDs for V4 pools (hex strings)
 /// - block_number (int): Block number to query state at
+/// - v4_pool_ids (Optional[List[str]]): List of pool IDs for V4 pools (hex strings),
+///   in the same order as the V4 entries in `pools`
+/// - max_readers (Optional[int]): MDBX max concurrent readers (Reth default if None)
+/// - exclusive (Optional[bool]): Open the DB with an exclusive lock (Reth default if None)
 ///
 /// Returns:
-/// - str: JSON string containing historical pool data
+/// - str: JSON string containing a list of `HistoricalPoolOutput`s
+///   (pool fields flattened, plus `block_number`)
 ///
 /// Example:
 /// ```python
 /// from scrape_rethdb_data import collect_pools_at_block
 ///
-/// pools = [{"address": "0x...", "protocol": "v3", "tick_spacing": 60}]
-/// result_json = collect_pools_at_block("/path/to/reth/db", pools, 12345678, None)
+/// pools = [
+///     {"address": "0x...", "protocol": "v3", "tick_spacing": 60},
+///     {"address": "0x<PoolManager>", "protocol": "v4", "tick_spacing": 60},
+/// ]
+/// result_json = collect_pools_at_block(
+///     "/path/to/reth/db", pools, 12345678, v4_pool_ids=["0x<poolId>"]
+/// )
 /// ```
 #[pyfunction]
-#[pyo3(signature = (db_path, pools, block_number, v4_pool_ids=None))]
+#[pyo3(signature = (db_path, pools, block_number, v4_pool_ids=None, max_readers=None, exclusive=None))]
 fn collect_pools_at_block(
     db_path: String,
     pools: &Bound<'_, PyList>,
     block_number: u64,
     v4_pool_ids: Option<Vec<String>>,
+    max_readers: Option<u64>,
+    exclusive: Option<bool>,
 ) -> PyResult<String> {
-    // Convert Python pools to Rust PoolInput (same logic as collect_pools)
-    let rust_pools: Result<Vec<PoolInput>, PyErr> = pools
-        .iter()
-        .map(|item| {
-            let dict = item.downcast::<pyo3::types::PyDict>()?;
-
-            let address_str: String = dict
-                .get_item("address")?
-                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing 'address'"))?
-                .extract()?;
-
-            let protocol_str: String = dict
-                .get_item("protocol")?
-                .ok_or_else(|| PyErr::new::<pyo3::exceptions::PyValueError, _>("Missing 'protocol'"))?
-                .extract()?;
-
-            let tick_spacing: Option<i32> = match dict.get_item("tick_spacing")? {
-                Some(v) => {
-                    if v.is_none() {
-                        None
-                    } else {
-                        Some(v.extract()?)
-                    }
-                }
-                None => None,
-            };
-
-            let address = Address::from_str(&address_str)
-                .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Invalid address: {}", e)))?;
-
-            let protocol = match protocol_str.to_lowercase().as_str() {
-                "v2" | "uniswapv2" => Protocol::UniswapV2,
-                "v3" | "uniswapv3" => Protocol::UniswapV3,
-                "v4" | "uniswapv4" => Protocol::UniswapV4,
-                _ => return Err(PyErr::new::<pyo3::exceptions::PyValueError, _>(
-                    format!("Invalid protocol: {}", protocol_str)
-                )),
-            };
-
-            Ok(PoolInput {
-                address,
-                protocol,
-                tick_spacing,
-                slot0_only: false,
-            })
-        })
-        .collect();
-
-    let rust_pools = rust_pools?;
-
-    // Convert V4 pool IDs if provided
-    let rust_v4_pool_ids: Option<Result<Vec<B256>, PyErr>> = v4_pool_ids.map(|ids| {
-        ids.iter()
-            .map(|id_str| {
-                B256::from_str(id_str).map_err(|e| {
-                    PyErr::new::<pyo3::exceptions::PyValueError, _>(format!(
-                        "Invalid pool ID: {}",
-                        e
-                    ))
-                })
-            })
-            .collect()
-    });
-
-    let rust_v4_pool_ids = rust_v4_pool_ids.transpose()?;
+    let rust_pools = parse_pools(pools)?;
+    let rust_v4_pool_ids = parse_pool_ids(v4_pool_ids)?;
+    let options = OpenOptions { max_readers, exclusive };
 
     // Call Rust function
-    let results = collect_pool_data_at_block(
+    let results = collect_pool_data_at_block_with_options(
         &db_path,
         &rust_pools,
         rust_v4_pool_ids.as_deref(),
         block_number,
+        &options,
     )
     .map_err(|e| PyErr::new::<pyo3::exceptions::PyRuntimeError, _>(format!("Collection failed: {}", e)))?;
 
@@ -263,7 +510,7 @@ fn collect_pools_at_block(
 /// - db_path (str): Path to reth database directory
 /// - pool_address (str): Address of the pool to scan (hex string)
 /// - from_block (int): Starting block number (inclusive)
-/// - to_block (int): Ending block number (inclusive)
+/// - to_block (Optional[int]): Ending block number (inclusive); None scans to the latest block
 /// - topics (Optional[List[str]]): Optional topic filters (hex strings)
 ///
 /// Returns:
@@ -282,12 +529,12 @@ fn collect_pools_at_block(
 /// )
 /// ```
 #[pyfunction]
-#[pyo3(signature = (db_path, pool_address, from_block, to_block, topics=None))]
+#[pyo3(signature = (db_path, pool_address, from_block, to_block=None, topics=None))]
 fn scan_events(
     db_path: String,
     pool_address: String,
     from_block: u64,
-    to_block: u64,
+    to_block: Option<u64>,
     topics: Option<Vec<String>>,
 ) -> PyResult<String> {
     let address = Address::from_str(&pool_address)
@@ -323,7 +570,7 @@ fn scan_events(
 /// - db_path (str): Path to reth database directory
 /// - pool_addresses (List[str]): List of pool addresses to scan (hex strings)
 /// - from_block (int): Starting block number (inclusive)
-/// - to_block (int): Ending block number (inclusive)
+/// - to_block (Optional[int]): Ending block number (inclusive); None scans to the latest block
 /// - topics (Optional[List[str]]): Optional topic filters (hex strings)
 ///
 /// Returns:
@@ -342,12 +589,12 @@ fn scan_events(
 /// )
 /// ```
 #[pyfunction]
-#[pyo3(signature = (db_path, pool_addresses, from_block, to_block, topics=None))]
+#[pyo3(signature = (db_path, pool_addresses, from_block, to_block=None, topics=None))]
 fn scan_events_multi(
     db_path: String,
     pool_addresses: Vec<String>,
     from_block: u64,
-    to_block: u64,
+    to_block: Option<u64>,
     topics: Option<Vec<String>>,
 ) -> PyResult<String> {
     let addresses: Result<Vec<Address>, PyErr> = pool_addresses
@@ -473,6 +720,7 @@ fn get_burn_events(
 #[pymodule]
 fn scrape_rethdb_data(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_function(wrap_pyfunction!(collect_pools, m)?)?;
+    m.add_function(wrap_pyfunction!(collect_pools_native, m)?)?;
     m.add_function(wrap_pyfunction!(collect_pools_at_block, m)?)?;
     m.add_function(wrap_pyfunction!(scan_events, m)?)?;
     m.add_function(wrap_pyfunction!(scan_events_multi, m)?)?;
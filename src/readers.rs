@@ -1,41 +1,458 @@
 /// Pool data readers using Alloy-based decoding.
 /// Clean implementation with proper storage unpacking.
-use alloy_primitives::{B256, U256};
+use alloy_primitives::{Address, B256, U256};
 use eyre::{eyre, Result};
 use reth_db::{cursor::DbDupCursorRO, tables, transaction::DbTx};
+use std::collections::HashMap;
 
 use crate::{
     decoding,
+    error::ScrapeError,
     storage::{self, v2, v3_slots_for_factory},
     tick_math,
-    types::{Bitmap, PoolInput, PoolOutput},
+    types::{
+        AccountInfo, Bitmap, Observation, PoolInput, PoolOutput, Position, Protocol, Slot0, Tick,
+        TickRangeAmounts, V2Oracle,
+    },
 };
 
+/// Resolve the value returned by `seek_by_key_subkey` for `slot`.
+///
+/// The dup cursor returns the first entry `>=` the requested subkey, so an unset
+/// slot yields the next-higher slot of the same account. Only an exact key match
+/// counts; anything else means the slot is zero.
+fn exact_slot_value(found: Option<(B256, U256)>, slot: B256) -> U256 {
+    match found {
+        Some((key, value)) if key == slot => value,
+        _ => U256::ZERO,
+    }
+}
+
+/// Forward-only view of one account's dup-sorted storage, as `(slot, value)` pairs.
+trait SlotCursor {
+    /// Position at the first slot `>= key`
+    fn seek(&mut self, key: B256) -> Result<Option<(B256, U256)>>;
+    /// Advance to the next slot of the same account
+    fn next(&mut self) -> Result<Option<(B256, U256)>>;
+}
+
+struct PlainStorageCursor<C> {
+    cursor: C,
+    address: Address,
+}
+
+impl<C: DbDupCursorRO<tables::PlainStorageState>> SlotCursor for PlainStorageCursor<C> {
+    fn seek(&mut self, key: B256) -> Result<Option<(B256, U256)>> {
+        let found = self.cursor.seek_by_key_subkey(self.address, key)?;
+        Ok(found.map(|entry| (entry.key, entry.value)))
+    }
+
+    fn next(&mut self) -> Result<Option<(B256, U256)>> {
+        let found = self.cursor.next_dup_val()?;
+        Ok(found.map(|entry| (entry.key, entry.value)))
+    }
+}
+
+/// How far to step forward before falling back to a fresh seek. Keccak-derived slots
+/// of other mappings (positions, other ticks) interleave with the requested ones, so
+/// long gaps are cheaper to jump than to walk.
+const MAX_FORWARD_STEPS: usize = 16;
+
+fn read_sorted_with<C: SlotCursor>(cursor: &mut C, keys: &[B256]) -> Result<Vec<U256>> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    order.sort_by_key(|&i| keys[i]);
+
+    let mut values = vec![U256::ZERO; keys.len()];
+    let mut current: Option<(B256, U256)> = None;
+    let mut positioned = false;
+
+    for i in order {
+        let key = keys[i];
+
+        if positioned {
+            let mut steps = 0;
+            while let Some((slot, _)) = current {
+                if slot >= key || steps == MAX_FORWARD_STEPS {
+                    break;
+                }
+                current = cursor.next()?;
+                steps += 1;
+            }
+            if current.is_none() {
+                // Walked past the account's last slot: remaining keys are unset
+                break;
+            }
+        }
+
+        if !positioned || current.is_some_and(|(slot, _)| slot < key) {
+            current = cursor.seek(key)?;
+            positioned = true;
+            if current.is_none() {
+                break;
+            }
+        }
+
+        values[i] = exact_slot_value(current, key);
+    }
+
+    Ok(values)
+}
+
+/// Read many storage slots of one account with a single forward pass of the cursor.
+///
+/// Keys are visited in sorted order, stepping the dup-sorted `PlainStorageState`
+/// cursor forward instead of issuing one random seek per key (a fresh seek is only
+/// used to jump large gaps). Returns values aligned with `keys`; unset slots are zero.
+pub fn read_slots_sorted<TX: DbTx>(tx: &TX, address: Address, keys: &[B256]) -> Result<Vec<U256>> {
+    let mut cursor = PlainStorageCursor {
+        cursor: tx.cursor_dup_read::<tables::PlainStorageState>()?,
+        address,
+    };
+    read_sorted_with(&mut cursor, keys)
+}
+
+/// The pool's `tick_spacing`, failing if it is missing or not positive
+/// ([`ScrapeError::InvalidTickSpacing`]) instead of panicking later on a division by zero.
+pub(crate) fn pool_tick_spacing(pool: &PoolInput) -> Result<i32> {
+    let tick_spacing = pool.tick_spacing.ok_or_else(|| match pool.protocol {
+        Protocol::UniswapV4 => eyre!("V4 pool missing tick_spacing"),
+        _ => eyre!("V3 pool missing tick_spacing"),
+    })?;
+    if tick_spacing <= 0 {
+        return Err(ScrapeError::InvalidTickSpacing {
+            pool: pool.address,
+            tick_spacing,
+        }
+        .into());
+    }
+    Ok(tick_spacing)
+}
+
+/// Read arbitrary storage slots of `address` as raw words, without any decoding
+///
+/// A low-level inspector for unknown layouts: each requested slot is paired with its
+/// value, which is zero unless `PlainStorageState` holds that exact key.
+pub fn read_raw_slots<TX: DbTx>(
+    tx: &TX,
+    address: Address,
+    slots: &[B256],
+) -> Result<Vec<(B256, U256)>> {
+    let values = read_slots_sorted(tx, address, slots)?;
+    Ok(slots.iter().copied().zip(values).collect())
+}
+
+/// Fail with [`ScrapeError::TickSpacingMismatch`] if some ticks flagged in the bitmaps
+/// had no stored data (`found` of `expected` were read back).
+pub(crate) fn check_bitmap_ticks_present(
+    pool: &PoolInput,
+    tick_spacing: i32,
+    expected: usize,
+    found: usize,
+) -> Result<()> {
+    if found < expected {
+        return Err(ScrapeError::TickSpacingMismatch {
+            pool: pool.address,
+            tick_spacing,
+            missing: expected - found,
+            expected,
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// Decode a pool's slot0, enforcing [`decoding::slot0_in_bounds`] when the pool
+/// asks for it via `validate_slot0`.
+pub(crate) fn decode_pool_slot0(pool: &PoolInput, value: U256) -> Result<Slot0> {
+    let slot0 = decoding::decode_slot0(value)?;
+    if pool.validate_slot0 && !decoding::slot0_in_bounds(&slot0) {
+        return Err(ScrapeError::ImplausibleSlot0 {
+            pool: pool.address,
+            sqrt_price_x96: slot0.sqrt_price_x96,
+            tick: slot0.tick,
+        }
+        .into());
+    }
+    Ok(slot0)
+}
+
+/// Whether a decoded slot0 describes an initialized V3 pool: the price is within
+/// `[MIN_SQRT_RATIO, MAX_SQRT_RATIO)` and the tick matches the price.
+fn is_plausible_slot0(value: U256) -> bool {
+    let Ok(slot0) = decoding::decode_slot0(value) else {
+        return false;
+    };
+    if !decoding::slot0_in_bounds(&slot0) {
+        return false;
+    }
+    let sqrt_price = slot0.sqrt_price_x96;
+
+    // tick must be the greatest tick with getSqrtRatioAtTick(tick) <= sqrtPrice
+    match (
+        tick_math::get_sqrt_ratio_at_tick(slot0.tick),
+        tick_math::get_sqrt_ratio_at_tick(slot0.tick + 1),
+    ) {
+        (Ok(at_tick), Ok(at_next)) => at_tick <= sqrt_price && sqrt_price < at_next,
+        _ => false,
+    }
+}
+
+/// Whether a storage word holds a non-zero address (upper 96 bits clear).
+fn as_address_word(value: U256) -> Option<U256> {
+    (!value.is_zero() && value >> 160 == U256::ZERO).then_some(value)
+}
+
+/// Guess whether `address` is a Uniswap V2 pair or V3 pool by probing its storage.
+///
+/// - V3: slot 0 decodes to a slot0 whose `sqrtPriceX96` is in range and agrees with its tick
+/// - V2: slots 6/7 hold two ordered token addresses and slot 8 (reserves) is non-zero
+///
+/// Returns `None` when neither layout matches (not a pool, uninitialized, or a
+/// non-standard fork). V4 pools live inside the PoolManager singleton and are never detected.
+pub fn detect_protocol<TX: DbTx>(tx: &TX, address: Address) -> Result<Option<Protocol>> {
+    let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    let mut read = |slot: B256| -> Result<U256> {
+        let found = cursor.seek_by_key_subkey(address, slot)?;
+        Ok(exact_slot_value(found.map(|entry| (entry.key, entry.value)), slot))
+    };
+
+    let v3_slots = v3_slots_for_factory(None);
+    if is_plausible_slot0(read(storage::simple_slot(v3_slots.slot0))?) {
+        return Ok(Some(Protocol::UniswapV3));
+    }
+
+    let token0 = as_address_word(read(storage::simple_slot(v2::TOKEN0))?);
+    let token1 = as_address_word(read(storage::simple_slot(v2::TOKEN1))?);
+    let reserves = read(storage::simple_slot(v2::RESERVE))?;
+    if let (Some(token0), Some(token1)) = (token0, token1) {
+        if token0 < token1 && !reserves.is_zero() {
+            return Ok(Some(Protocol::UniswapV2));
+        }
+    }
+
+    Ok(None)
+}
+
 /// Read V2 reserve data from reth database.
 pub fn read_v2_pool<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<PoolOutput> {
+    // Slot 8 unless the pool configures a fork's reserve layout
+    let layout = pool.v2_reserve_layout();
+    let values = read_slots_sorted(tx, pool.address, &layout.slots())?;
+
+    let reserves = decoding::decode_v2_reserves_with_layout(layout, &values)?;
+
+    Ok(PoolOutput::new_v2(pool.address, reserves))
+}
+
+/// Read an ERC20's `decimals` from storage, for tokens that store it in a slot.
+///
+/// There is no common layout: many tokens (OpenZeppelin, Solmate) return a constant
+/// or an immutable, so nothing is stored at all. Callers pass the `slot` and the
+/// `byte_offset` of the packed `uint8` within it (0 = lowest byte). Returns `None`
+/// when the byte is zero (unset).
+pub fn read_erc20_decimals<TX: DbTx>(
+    tx: &TX,
+    token: Address,
+    slot: u8,
+    byte_offset: u8,
+) -> Result<Option<u8>> {
+    if byte_offset >= 32 {
+        return Err(eyre!("byte_offset {byte_offset} is outside a 32-byte slot"));
+    }
+
     let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    let key = storage::simple_slot(slot);
+    let found = cursor.seek_by_key_subkey(token, key)?;
+    let value = exact_slot_value(found.map(|entry| (entry.key, entry.value)), key);
 
-    // Read reserves from slot 8
-    let reserve_slot = storage::simple_slot(v2::RESERVE);
+    let decimals = value.byte(byte_offset as usize);
+    Ok((decimals != 0).then_some(decimals))
+}
 
-    let value = cursor
-        .seek_by_key_subkey(pool.address, reserve_slot)?
-        .filter(|entry| entry.key == reserve_slot)
-        .map_or(U256::ZERO, |entry| entry.value);
+/// Read an account's nonce, balance and code hash from `PlainAccountState`.
+///
+/// Returns `None` if the account does not exist. Reth keeps no per-account storage
+/// root in its tables (only trie branch nodes), so to tell whether a pool's storage
+/// changed between two blocks use [`crate::historical::storage_changed_between`].
+pub fn read_account_info<TX: DbTx>(tx: &TX, address: Address) -> Result<Option<AccountInfo>> {
+    Ok(tx.get::<tables::PlainAccountState>(address)?.map(|account| AccountInfo {
+        nonce: account.nonce,
+        balance: account.balance,
+        bytecode_hash: account.bytecode_hash,
+    }))
+}
 
-    let reserves = decoding::decode_v2_reserves(value)?;
+/// Read a V2 pair's price accumulators and `kLast` (slots 9-11) along with the
+/// reserves' `blockTimestampLast`.
+pub fn read_v2_oracle<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<V2Oracle> {
+    let keys: Vec<B256> = [
+        v2::RESERVE,
+        v2::PRICE0_CUMULATIVE_LAST,
+        v2::PRICE1_CUMULATIVE_LAST,
+        v2::K_LAST,
+    ]
+    .into_iter()
+    .map(storage::simple_slot)
+    .collect();
+    let values = read_slots_sorted(tx, pool.address, &keys)?;
 
-    Ok(PoolOutput::new_v2(pool.address, reserves))
+    let reserves = decoding::decode_v2_reserves(values[0])?;
+    Ok(V2Oracle {
+        price0_cumulative_last: values[1],
+        price1_cumulative_last: values[2],
+        k_last: values[3],
+        block_timestamp_last: reserves.block_timestamp_last,
+    })
+}
+
+/// Length of the `raw_data` hex strings the decoders attach ("0x" + 64 digits)
+const RAW_HEX_LEN: usize = 66;
+
+/// Predict [`PoolOutput::heap_size`] of a full read without reading any ticks
+///
+/// Reads only the bitmap words: every set bit becomes one tick with its raw hex
+/// string. V4 pools need their `pool_id`.
+pub fn estimate_pool_heap_size<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    v4_pool_id: Option<B256>,
+) -> Result<usize> {
+    if pool.protocol == Protocol::UniswapV2 {
+        return Ok(RAW_HEX_LEN);
+    }
+    let word_positions =
+        tick_math::word_positions_in_range(pool_tick_spacing(pool)?, pool.tick_lower, pool.tick_upper);
+
+    let bitmap_slots: Vec<B256> = if pool.protocol == Protocol::UniswapV4 {
+        let pool_id = v4_pool_id.ok_or_else(|| eyre!("V4 pool {} needs a pool_id", pool.address))?;
+        let slots = pool.v4_slots();
+        word_positions
+            .into_iter()
+            .map(|word_pos| slots.bitmap(pool_id, word_pos))
+            .collect()
+    } else {
+        let slots = pool.v3_slots();
+        word_positions
+            .into_iter()
+            .map(|word_pos| storage::bitmap_slot(word_pos, slots.tick_bitmap))
+            .collect()
+    };
+
+    let words = read_slots_sorted(tx, pool.address, &bitmap_slots)?;
+    let (mut kept_words, mut ticks) = (0usize, 0usize);
+    for word in words {
+        if !word.is_zero() || pool.include_empty {
+            kept_words += 1;
+        }
+        ticks += word.count_ones();
+    }
+
+    Ok(RAW_HEX_LEN
+        + kept_words * std::mem::size_of::<Bitmap>()
+        + ticks * (std::mem::size_of::<crate::types::Tick>() + RAW_HEX_LEN))
+}
+
+/// Storage slots [`read_v3_pool`] queries up front, labeled for manual checks
+/// (e.g. `cast storage <pool> <slot>`), without touching the database.
+///
+/// Lists `slot0`, `liquidity` and every `bitmap[word]` in the pool's tick range.
+/// Tick slots depend on which bitmap bits are set; compute those with
+/// [`storage::tick_slot`] once the bitmaps are known.
+pub fn plan_v3_slots(pool: &PoolInput) -> Result<Vec<(String, B256)>> {
+    let tick_spacing = pool_tick_spacing(pool)?;
+    let slots = pool.v3_slots();
+
+    let mut plan = vec![
+        ("slot0".to_string(), storage::simple_slot(slots.slot0)),
+        ("liquidity".to_string(), storage::simple_slot(slots.liquidity)),
+    ];
+    plan.extend(
+        tick_math::word_positions_in_range(tick_spacing, pool.tick_lower, pool.tick_upper)
+            .into_iter()
+            .map(|word_pos| {
+                (
+                    format!("bitmap[{word_pos}]"),
+                    storage::bitmap_slot(word_pos, slots.tick_bitmap),
+                )
+            }),
+    );
+    Ok(plan)
+}
+
+/// Walk every stored slot of the account in order and keep those listed in `wanted`,
+/// as `(label, value)` pairs. Stops as soon as all wanted slots were seen.
+fn walk_present_slots<C: SlotCursor, K: Copy>(
+    cursor: &mut C,
+    wanted: &HashMap<B256, K>,
+) -> Result<Vec<(K, U256)>> {
+    let mut found = Vec::new();
+    let mut entry = cursor.seek(B256::ZERO)?;
+    while let Some((slot, value)) = entry {
+        if let Some(label) = wanted.get(&slot) {
+            found.push((*label, value));
+            if found.len() == wanted.len() {
+                break;
+            }
+        }
+        entry = cursor.next()?;
+    }
+    Ok(found)
+}
+
+/// Read a V3 pool's bitmap words by walking the slots actually present in storage
+///
+/// Instead of seeking every word position in the tick range, walks the pool's
+/// `PlainStorageState` entries once and recognizes the bitmap slots among them.
+/// Cheaper for sparse pools with a small tick spacing, where most computed words
+/// are unset; costlier for pools with many ticks and positions. Only suited to V3
+/// pools: a V4 PoolManager holds the storage of every pool.
+pub fn read_v3_bitmaps_present<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<Vec<Bitmap>> {
+    let tick_spacing = pool_tick_spacing(pool)?;
+    let slots = pool.v3_slots();
+
+    let word_positions =
+        tick_math::word_positions_in_range(tick_spacing, pool.tick_lower, pool.tick_upper);
+    let wanted: HashMap<B256, i16> = word_positions
+        .iter()
+        .map(|word_pos| (storage::bitmap_slot(*word_pos, slots.tick_bitmap), *word_pos))
+        .collect();
+
+    let mut cursor = PlainStorageCursor {
+        cursor: tx.cursor_dup_read::<tables::PlainStorageState>()?,
+        address: pool.address,
+    };
+    let present: HashMap<i16, U256> =
+        walk_present_slots(&mut cursor, &wanted)?.into_iter().collect();
+
+    Ok(word_positions
+        .into_iter()
+        .filter_map(|word_pos| match present.get(&word_pos) {
+            Some(value) if !value.is_zero() => Some(Bitmap { word_pos, bitmap: *value }),
+            _ if pool.include_empty => Some(Bitmap { word_pos, bitmap: U256::ZERO }),
+            _ => None,
+        })
+        .collect())
 }
 
 /// Read V3 pool data from reth database.
 pub fn read_v3_pool<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<PoolOutput> {
-    let tick_spacing = pool
-        .tick_spacing
-        .ok_or_else(|| eyre!("V3 pool missing tick_spacing"))?;
+    read_v3_pool_impl(tx, pool, false)
+}
+
+/// Same as [`read_v3_pool`], finding bitmap words with [`read_v3_bitmaps_present`]
+pub fn read_v3_pool_sparse<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<PoolOutput> {
+    read_v3_pool_impl(tx, pool, true)
+}
+
+fn read_v3_pool_impl<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    walk_bitmaps: bool,
+) -> Result<PoolOutput> {
+    let tick_spacing = pool_tick_spacing(pool)?;
 
     // Get factory-specific storage slots (PancakeSwap V3 has different layout)
-    let slots = v3_slots_for_factory(pool.factory);
+    let slots = pool.v3_slots();
 
     let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
 
@@ -46,7 +463,7 @@ pub fn read_v3_pool<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<PoolOutput> {
         .filter(|entry| entry.key == slot0_slot)
         .map_or(U256::ZERO, |entry| entry.value);
 
-    let slot0 = decoding::decode_slot0(slot0_value)?;
+    let slot0 = decode_pool_slot0(pool, slot0_value)?;
 
     // Read liquidity
     let liquidity_slot = storage::simple_slot(slots.liquidity);
@@ -55,30 +472,33 @@ pub fn read_v3_pool<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<PoolOutput> {
         .filter(|entry| entry.key == liquidity_slot)
         .map_or(U256::ZERO, |entry| entry.value);
 
-    let liquidity = u128::try_from(liquidity_value).map_err(|e| eyre!("liquidity overflow: {e}"))?;
+    let (liquidity, liquidity_upper_bits_set) = decoding::decode_liquidity(liquidity_value);
 
-    // Generate word positions to query based on tick spacing
-    let word_positions = tick_math::generate_word_positions(tick_spacing);
+    let bitmaps = if walk_bitmaps {
+        read_v3_bitmaps_present(tx, pool)?
+    } else {
+        // Generate word positions to query based on tick spacing
+        let word_positions =
+            tick_math::word_positions_in_range(tick_spacing, pool.tick_lower, pool.tick_upper);
 
-    // Read all bitmaps
-    let mut bitmaps = Vec::new();
-    for word_pos in &word_positions {
-        let bitmap_slot = storage::bitmap_slot(*word_pos, slots.tick_bitmap);
+        // Read all bitmaps in one sorted cursor pass
+        let bitmap_slots: Vec<B256> = word_positions
+            .iter()
+            .map(|word_pos| storage::bitmap_slot(*word_pos, slots.tick_bitmap))
+            .collect();
+        let bitmap_values = read_slots_sorted(tx, pool.address, &bitmap_slots)?;
 
-        if let Some(entry) = cursor.seek_by_key_subkey(pool.address, bitmap_slot)? {
-            // seek_by_key_subkey returns first entry >= requested slot
-            // We must verify it's an EXACT match
-            if entry.key == bitmap_slot {
-                let value = entry.value;
-                if value != U256::ZERO {
-                    bitmaps.push(Bitmap {
-                        word_pos: *word_pos,
-                        bitmap: value,
-                    });
-                }
+        let mut bitmaps = Vec::new();
+        for (word_pos, value) in word_positions.iter().zip(bitmap_values) {
+            if value != U256::ZERO || pool.include_empty {
+                bitmaps.push(Bitmap {
+                    word_pos: *word_pos,
+                    bitmap: value,
+                });
             }
         }
-    }
+        bitmaps
+    };
 
     // Extract initialized ticks from bitmaps
     let mut tick_values = Vec::new();
@@ -88,79 +508,106 @@ pub fn read_v3_pool<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<PoolOutput> {
             tick_math::extract_ticks_from_bitmap_u256(bitmap.word_pos, &bitmap_bytes, tick_spacing);
         tick_values.extend(ticks);
     }
+    tick_values.retain(|tick| pool.tick_in_range(*tick));
+    let expected_ticks = tick_values.len();
 
-    // Read tick data for each initialized tick
-    let mut ticks = Vec::new();
-    for tick_value in tick_values {
-        let tick_slot = storage::tick_slot(tick_value, slots.ticks);
+    // Read tick data for each initialized tick in one sorted cursor pass
+    let tick_slots: Vec<B256> = tick_values
+        .iter()
+        .map(|tick_value| storage::tick_slot(*tick_value, slots.ticks))
+        .collect();
+    let tick_storage = read_slots_sorted(tx, pool.address, &tick_slots)?;
 
-        if let Some(entry) = cursor.seek_by_key_subkey(pool.address, tick_slot)? {
-            // Verify exact match
-            if entry.key == tick_slot {
-                let value = entry.value;
-                if value != U256::ZERO {
-                    let tick_data = decoding::decode_tick_info(tick_value, value)?;
-                    ticks.push(tick_data);
-                }
-            }
+    let mut ticks = Vec::new();
+    for (tick_value, value) in tick_values.into_iter().zip(tick_storage) {
+        if value != U256::ZERO {
+            let tick_data = decoding::decode_tick_info(tick_value, value)?;
+            ticks.push(tick_data);
         }
     }
 
+    check_bitmap_ticks_present(pool, tick_spacing, expected_ticks, ticks.len())?;
+    ticks.retain(|tick| pool.keeps_tick(tick));
+
     Ok(PoolOutput::new_v3(
         pool.address,
         slot0,
         liquidity,
         ticks,
         bitmaps,
-    ))
+    )
+    .with_fee(pool.output_fee())
+    .with_liquidity_upper_bits_set(liquidity_upper_bits_set))
 }
 
 /// Read V4 pool data from reth database.
 pub fn read_v4_pool<TX: DbTx>(tx: &TX, pool: &PoolInput, pool_id: B256) -> Result<PoolOutput> {
-    let tick_spacing = pool
-        .tick_spacing
-        .ok_or_else(|| eyre!("V4 pool missing tick_spacing"))?;
+    read_v4_pool_words(tx, pool, pool_id, None)
+}
+
+/// Read a V4 pool, only scanning `range_words` bitmap words on each side of its
+/// current tick.
+///
+/// The V4 counterpart of the focused V3 reads (see
+/// [`tick_math::word_positions_around_tick`]): slot0 is read first, then only the
+/// bitmap words near its tick. Ticks outside the window are not returned.
+pub fn read_v4_pool_range<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    pool_id: B256,
+    range_words: i16,
+) -> Result<PoolOutput> {
+    read_v4_pool_words(tx, pool, pool_id, Some(range_words))
+}
+
+fn read_v4_pool_words<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    pool_id: B256,
+    range_words: Option<i16>,
+) -> Result<PoolOutput> {
+    let tick_spacing = pool_tick_spacing(pool)?;
 
     let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
 
+    let slots = pool.v4_slots();
+
     // Read slot0 for this poolId
-    let slot0_slot = storage::v4_slot0_slot(pool_id);
+    let slot0_slot = slots.slot0(pool_id);
     let slot0_value = cursor
         .seek_by_key_subkey(pool.address, slot0_slot)?
         .filter(|entry| entry.key == slot0_slot)
         .map_or(U256::ZERO, |entry| entry.value);
 
-    let slot0 = decoding::decode_slot0(slot0_value)?;
+    let slot0 = decode_pool_slot0(pool, slot0_value)?;
 
     // Read liquidity for this poolId
-    let liquidity_slot = storage::v4_liquidity_slot(pool_id);
+    let liquidity_slot = slots.liquidity(pool_id);
     let liquidity_value = cursor
         .seek_by_key_subkey(pool.address, liquidity_slot)?
         .filter(|entry| entry.key == liquidity_slot)
         .map_or(U256::ZERO, |entry| entry.value);
 
-    let liquidity = u128::try_from(liquidity_value).map_err(|e| eyre!("liquidity overflow: {e}"))?;
-
-    // Generate word positions
-    let word_positions = tick_math::generate_word_positions(tick_spacing);
+    let (liquidity, liquidity_upper_bits_set) = decoding::decode_liquidity(liquidity_value);
 
-    // Read all bitmaps for this pool
-    let mut bitmaps = Vec::new();
-    for word_pos in &word_positions {
-        let bitmap_slot = storage::v4_bitmap_slot(pool_id, *word_pos);
+    // Generate word positions: everything, or a window around the current tick,
+    // limited to the pool's tick bounds
+    let bounded =
+        tick_math::word_positions_in_range(tick_spacing, pool.tick_lower, pool.tick_upper);
+    let word_positions: Vec<i16> = match range_words {
+        Some(range) => tick_math::word_positions_around_tick(slot0.tick, tick_spacing, range)
+            .into_iter()
+            .filter(|word_pos| bounded.contains(word_pos))
+            .collect(),
+        None => bounded,
+    };
 
-        if let Some(entry) = cursor.seek_by_key_subkey(pool.address, bitmap_slot)? {
-            if entry.key == bitmap_slot {
-                let value = entry.value;
-                if value != U256::ZERO {
-                    bitmaps.push(Bitmap {
-                        word_pos: *word_pos,
-                        bitmap: value,
-                    });
-                }
-            }
-        }
-    }
+    // Read all bitmaps for this pool in one sorted cursor pass
+    let mut storage_cursor = PlainStorageCursor {
+        cursor: tx.cursor_dup_read::<tables::PlainStorageState>()?,
+        address: pool.address,
+    };
+    let bitmaps = read_v4_bitmaps_with(&mut storage_cursor, pool, pool_id, &word_positions)?;
 
     // Extract initialized ticks
     let mut tick_values = Vec::new();
@@ -170,11 +617,13 @@ pub fn read_v4_pool<TX: DbTx>(tx: &TX, pool: &PoolInput, pool_id: B256) -> Resul
             tick_math::extract_ticks_from_bitmap_u256(bitmap.word_pos, &bitmap_bytes, tick_spacing);
         tick_values.extend(ticks);
     }
+    tick_values.retain(|tick| pool.tick_in_range(*tick));
+    let expected_ticks = tick_values.len();
 
     // Read tick data
     let mut ticks = Vec::new();
     for tick_value in tick_values {
-        let tick_slot = storage::v4_tick_slot(pool_id, tick_value);
+        let tick_slot = slots.tick(pool_id, tick_value);
 
         if let Some(entry) = cursor.seek_by_key_subkey(pool.address, tick_slot)? {
             if entry.key == tick_slot {
@@ -187,6 +636,9 @@ pub fn read_v4_pool<TX: DbTx>(tx: &TX, pool: &PoolInput, pool_id: B256) -> Resul
         }
     }
 
+    check_bitmap_ticks_present(pool, tick_spacing, expected_ticks, ticks.len())?;
+    ticks.retain(|tick| pool.keeps_tick(tick));
+
     Ok(PoolOutput::new_v4(
         pool.address,
         pool_id,
@@ -194,7 +646,236 @@ pub fn read_v4_pool<TX: DbTx>(tx: &TX, pool: &PoolInput, pool_id: B256) -> Resul
         liquidity,
         ticks,
         bitmaps,
-    ))
+    )
+    .with_fee(pool.output_fee())
+    .with_liquidity_upper_bits_set(liquidity_upper_bits_set))
+}
+
+/// Read the V4 bitmap words at `word_positions` of `pool_id` in one sorted pass.
+///
+/// Reth stores no zero slots, so with `include_empty` a word without a storage entry
+/// comes back as an empty bitmap; otherwise only non-zero words are returned.
+fn read_v4_bitmaps_with<C: SlotCursor>(
+    cursor: &mut C,
+    pool: &PoolInput,
+    pool_id: B256,
+    word_positions: &[i16],
+) -> Result<Vec<Bitmap>> {
+    let slots = pool.v4_slots();
+    let keys: Vec<B256> =
+        word_positions.iter().map(|word_pos| slots.bitmap(pool_id, *word_pos)).collect();
+    let values = read_sorted_with(cursor, &keys)?;
+
+    Ok(word_positions
+        .iter()
+        .zip(values)
+        .filter(|(_, value)| *value != U256::ZERO || pool.include_empty)
+        .map(|(word_pos, bitmap)| Bitmap { word_pos: *word_pos, bitmap })
+        .collect())
+}
+
+/// Read a V4 pool's slot0, liquidity and only the given candidate ticks, without
+/// scanning the tick bitmap.
+///
+/// For event-driven reads (see [`crate::events::v4_touched_ticks`]): each candidate
+/// within the pool's tick bounds is read directly and kept if it is initialized in
+/// storage, and the bitmap words are rebuilt from the kept ticks with
+/// [`tick_math::bitmap_from_ticks`]. Initialized ticks missing from `candidate_ticks`
+/// are not found.
+pub fn read_v4_pool_at_ticks<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    pool_id: B256,
+    candidate_ticks: &[i32],
+) -> Result<PoolOutput> {
+    let tick_spacing = pool_tick_spacing(pool)?;
+    let slots = pool.v4_slots();
+
+    let mut tick_values: Vec<i32> = candidate_ticks
+        .iter()
+        .copied()
+        .filter(|tick| tick.rem_euclid(tick_spacing) == 0 && pool.tick_in_range(*tick))
+        .collect();
+    tick_values.sort_unstable();
+    tick_values.dedup();
+
+    let mut keys = vec![slots.slot0(pool_id), slots.liquidity(pool_id)];
+    keys.extend(tick_values.iter().map(|tick| slots.tick(pool_id, *tick)));
+    let values = read_slots_sorted(tx, pool.address, &keys)?;
+
+    let slot0 = decode_pool_slot0(pool, values[0])?;
+    let (liquidity, liquidity_upper_bits_set) = decoding::decode_liquidity(values[1]);
+
+    let mut ticks = Vec::new();
+    for (tick_value, value) in tick_values.into_iter().zip(&values[2..]) {
+        if value.is_zero() {
+            continue;
+        }
+        let tick = decoding::decode_tick_info(tick_value, *value)?;
+        if tick.liquidity_gross > 0 {
+            ticks.push(tick);
+        }
+    }
+
+    let initialized: Vec<i32> = ticks.iter().map(|tick| tick.tick).collect();
+    let mut word_positions: Vec<i16> = initialized
+        .iter()
+        .map(|tick| tick_math::tick_to_word_pos(*tick, tick_spacing))
+        .collect();
+    word_positions.dedup();
+    let bitmaps = word_positions
+        .into_iter()
+        .map(|word_pos| Bitmap {
+            word_pos,
+            bitmap: tick_math::bitmap_from_ticks(word_pos, &initialized, tick_spacing),
+        })
+        .collect();
+    ticks.retain(|tick| pool.keeps_tick(tick));
+
+    Ok(PoolOutput::new_v4(pool.address, pool_id, slot0, liquidity, ticks, bitmaps)
+        .with_fee(pool.output_fee())
+        .with_liquidity_upper_bits_set(liquidity_upper_bits_set))
+}
+
+/// Read `(address, slot)` pairs across accounts, one sorted cursor pass per address.
+/// Returns values aligned with `requests`.
+fn read_slots_multi<TX: DbTx>(tx: &TX, requests: &[(Address, B256)]) -> Result<Vec<U256>> {
+    let mut by_address: HashMap<Address, (Vec<usize>, Vec<B256>)> = HashMap::new();
+    for (i, (address, slot)) in requests.iter().enumerate() {
+        let (indices, keys) = by_address.entry(*address).or_default();
+        indices.push(i);
+        keys.push(*slot);
+    }
+
+    let mut values = vec![U256::ZERO; requests.len()];
+    for (address, (indices, keys)) in by_address {
+        for (i, value) in indices.into_iter().zip(read_slots_sorted(tx, address, &keys)?) {
+            values[i] = value;
+        }
+    }
+    Ok(values)
+}
+
+/// Read many V4 pools with one sorted cursor pass per phase
+///
+/// V4 slots are keccak-scattered across the PoolManager's storage, so reading pools
+/// one by one means a random seek per slot. This computes the slot0, liquidity and
+/// bitmap slots of every pool up front and reads them in a single forward walk, then
+/// does the same for all of their tick slots. Output matches [`read_v4_pool`] per
+/// pool.
+pub fn read_v4_pools_batch<TX: DbTx>(
+    tx: &TX,
+    pools: &[(&PoolInput, B256)],
+) -> Result<Vec<PoolOutput>> {
+    // Phase 1: slot0, liquidity and bitmap words of every pool
+    let mut word_positions = Vec::with_capacity(pools.len());
+    let mut requests = Vec::new();
+    for (pool, pool_id) in pools {
+        let tick_spacing = pool_tick_spacing(pool)?;
+        let slots = pool.v4_slots();
+        let words =
+            tick_math::word_positions_in_range(tick_spacing, pool.tick_lower, pool.tick_upper);
+
+        requests.push((pool.address, slots.slot0(*pool_id)));
+        requests.push((pool.address, slots.liquidity(*pool_id)));
+        requests.extend(
+            words
+                .iter()
+                .map(|word_pos| (pool.address, slots.bitmap(*pool_id, *word_pos))),
+        );
+        word_positions.push(words);
+    }
+    let mut state_values = read_slots_multi(tx, &requests)?.into_iter();
+
+    // Phase 2: decode, then collect the tick slots of every pool
+    let mut partial = Vec::with_capacity(pools.len());
+    let mut requests = Vec::new();
+    for ((pool, pool_id), words) in pools.iter().zip(word_positions) {
+        let tick_spacing = pool_tick_spacing(pool)?;
+        let mut next = || state_values.next().unwrap_or_default();
+        let slot0 = decode_pool_slot0(pool, next())?;
+        let (liquidity, liquidity_upper_bits_set) = decoding::decode_liquidity(next());
+
+        let mut bitmaps = Vec::new();
+        for word_pos in words {
+            let value = next();
+            if value != U256::ZERO || pool.include_empty {
+                bitmaps.push(Bitmap { word_pos, bitmap: value });
+            }
+        }
+
+        let mut tick_values = Vec::new();
+        for bitmap in &bitmaps {
+            let bitmap_bytes = bitmap.bitmap.to_be_bytes::<32>();
+            tick_values.extend(tick_math::extract_ticks_from_bitmap_u256(
+                bitmap.word_pos,
+                &bitmap_bytes,
+                tick_spacing,
+            ));
+        }
+        tick_values.retain(|tick| pool.tick_in_range(*tick));
+
+        let slots = pool.v4_slots();
+        requests.extend(tick_values.iter().map(|tick| (pool.address, slots.tick(*pool_id, *tick))));
+        partial.push((slot0, liquidity, liquidity_upper_bits_set, bitmaps, tick_values));
+    }
+    let mut tick_storage = read_slots_multi(tx, &requests)?.into_iter();
+
+    // Phase 3: assemble outputs
+    let mut outputs = Vec::with_capacity(pools.len());
+    for ((pool, pool_id), (slot0, liquidity, upper_bits_set, bitmaps, tick_values)) in
+        pools.iter().zip(partial)
+    {
+        let expected_ticks = tick_values.len();
+        let mut ticks = Vec::new();
+        for tick_value in tick_values {
+            let value = tick_storage.next().unwrap_or_default();
+            if value != U256::ZERO {
+                ticks.push(decoding::decode_tick_info(tick_value, value)?);
+            }
+        }
+
+        check_bitmap_ticks_present(pool, pool_tick_spacing(pool)?, expected_ticks, ticks.len())?;
+        ticks.retain(|tick| pool.keeps_tick(tick));
+
+        outputs.push(
+            PoolOutput::new_v4(pool.address, *pool_id, slot0, liquidity, ticks, bitmaps)
+                .with_fee(pool.output_fee())
+                .with_liquidity_upper_bits_set(upper_bits_set),
+        );
+    }
+    Ok(outputs)
+}
+
+/// Read and decode only a V3/V4 pool's slot0 (price and tick): one seek per pool.
+///
+/// Lighter than [`read_v3_slot0_only`]/[`read_v4_slot0_only`], which also read
+/// liquidity. V4 pools must carry their own `pool_id`.
+pub fn read_slot0_only<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<Slot0> {
+    let slot0_value = read_slot0_raw(tx, pool)?;
+    decode_pool_slot0(pool, slot0_value)
+}
+
+/// Storage key of a V3/V4 pool's slot0 in its contract (the PoolManager for V4).
+pub(crate) fn slot0_storage_key(pool: &PoolInput) -> Result<B256> {
+    match pool.protocol {
+        Protocol::UniswapV3 => Ok(storage::simple_slot(pool.v3_slots().slot0)),
+        Protocol::UniswapV4 => {
+            let pool_id = pool
+                .pool_id
+                .ok_or_else(|| eyre!("V4 pool {} requires pool_id", pool.address))?;
+            Ok(pool.v4_slots().slot0(pool_id))
+        }
+        Protocol::UniswapV2 => Err(eyre!("read_slot0_only only supports V3/V4 pools")),
+    }
+}
+
+/// Undecoded slot0 word of a V3/V4 pool; zero if the slot is unset.
+pub(crate) fn read_slot0_raw<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<U256> {
+    let slot0_slot = slot0_storage_key(pool)?;
+    let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+    let found = cursor.seek_by_key_subkey(pool.address, slot0_slot)?;
+    Ok(exact_slot_value(found.map(|entry| (entry.key, entry.value)), slot0_slot))
 }
 
 /// Read V3 pool slot0 and liquidity ONLY (no ticks/bitmaps).
@@ -205,7 +886,7 @@ pub fn read_v4_pool<TX: DbTx>(tx: &TX, pool: &PoolInput, pool_id: B256) -> Resul
 /// Storage reads: 2 (slot0 + liquidity) vs 50-1500+ for full scrape.
 pub fn read_v3_slot0_only<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<PoolOutput> {
     // Get factory-specific storage slots (PancakeSwap V3 has different layout)
-    let slots = v3_slots_for_factory(pool.factory);
+    let slots = pool.v3_slots();
 
     let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
 
@@ -216,7 +897,7 @@ pub fn read_v3_slot0_only<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<PoolOut
         .filter(|entry| entry.key == slot0_slot)
         .map_or(U256::ZERO, |entry| entry.value);
 
-    let slot0 = decoding::decode_slot0(slot0_value)?;
+    let slot0 = decode_pool_slot0(pool, slot0_value)?;
 
     // Read liquidity
     let liquidity_slot = storage::simple_slot(slots.liquidity);
@@ -225,7 +906,7 @@ pub fn read_v3_slot0_only<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<PoolOut
         .filter(|entry| entry.key == liquidity_slot)
         .map_or(U256::ZERO, |entry| entry.value);
 
-    let liquidity = u128::try_from(liquidity_value).map_err(|e| eyre!("liquidity overflow: {e}"))?;
+    let (liquidity, liquidity_upper_bits_set) = decoding::decode_liquidity(liquidity_value);
 
     Ok(PoolOutput::new_v3(
         pool.address,
@@ -233,7 +914,9 @@ pub fn read_v3_slot0_only<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<PoolOut
         liquidity,
         Vec::new(),
         Vec::new(),
-    ))
+    )
+    .with_fee(pool.output_fee())
+    .with_liquidity_upper_bits_set(liquidity_upper_bits_set))
 }
 
 /// Read V4 pool slot0 and liquidity ONLY (no ticks/bitmaps).
@@ -249,23 +932,25 @@ pub fn read_v4_slot0_only<TX: DbTx>(
 ) -> Result<PoolOutput> {
     let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
 
+    let slots = pool.v4_slots();
+
     // Read slot0 for this poolId
-    let slot0_slot = storage::v4_slot0_slot(pool_id);
+    let slot0_slot = slots.slot0(pool_id);
     let slot0_value = cursor
         .seek_by_key_subkey(pool.address, slot0_slot)?
         .filter(|entry| entry.key == slot0_slot)
         .map_or(U256::ZERO, |entry| entry.value);
 
-    let slot0 = decoding::decode_slot0(slot0_value)?;
+    let slot0 = decode_pool_slot0(pool, slot0_value)?;
 
     // Read liquidity for this poolId
-    let liquidity_slot = storage::v4_liquidity_slot(pool_id);
+    let liquidity_slot = slots.liquidity(pool_id);
     let liquidity_value = cursor
         .seek_by_key_subkey(pool.address, liquidity_slot)?
         .filter(|entry| entry.key == liquidity_slot)
         .map_or(U256::ZERO, |entry| entry.value);
 
-    let liquidity = u128::try_from(liquidity_value).map_err(|e| eyre!("liquidity overflow: {e}"))?;
+    let (liquidity, liquidity_upper_bits_set) = decoding::decode_liquidity(liquidity_value);
 
     Ok(PoolOutput::new_v4(
         pool.address,
@@ -274,5 +959,543 @@ pub fn read_v4_slot0_only<TX: DbTx>(
         liquidity,
         Vec::new(),
         Vec::new(),
+    )
+    .with_fee(pool.output_fee())
+    .with_liquidity_upper_bits_set(liquidity_upper_bits_set))
+}
+
+/// Read a single V3 oracle observation, `observations[index]`.
+///
+/// Uses the factory-specific observations slot (PancakeSwap V3 is shifted by one).
+pub fn read_v3_observation<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    index: u16,
+) -> Result<Observation> {
+    let slots = pool.v3_slots();
+    let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+
+    let slot = storage::observation_slot(index, slots.observations);
+    let found = cursor.seek_by_key_subkey(pool.address, slot)?;
+    let value = exact_slot_value(found.map(|entry| (entry.key, entry.value)), slot);
+
+    decoding::decode_observation(index, value)
+}
+
+/// Read the pool's active observations in chronological order.
+///
+/// Reads slot0 for `observationIndex`/`observationCardinality`, then walks the ring
+/// buffer from the oldest entry (`index + 1`) around to the most recent (`index`).
+/// Uninitialized entries (cardinality grown but not yet written) are skipped.
+pub fn read_v3_observations<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<Vec<Observation>> {
+    read_v3_oracle_state(tx, pool).map(|(_, observations)| observations)
+}
+
+/// Slot0 plus the active observations, oldest first (see [`read_v3_observations`])
+fn read_v3_oracle_state<TX: DbTx>(tx: &TX, pool: &PoolInput) -> Result<(Slot0, Vec<Observation>)> {
+    let slots = pool.v3_slots();
+    let mut cursor = tx.cursor_dup_read::<tables::PlainStorageState>()?;
+
+    let slot0_slot = storage::simple_slot(slots.slot0);
+    let found = cursor.seek_by_key_subkey(pool.address, slot0_slot)?;
+    let slot0_value = exact_slot_value(found.map(|entry| (entry.key, entry.value)), slot0_slot);
+    let slot0 = decode_pool_slot0(pool, slot0_value)?;
+
+    let cardinality = slot0.observation_cardinality;
+    let mut observations = Vec::with_capacity(cardinality as usize);
+
+    for offset in 1..=cardinality {
+        let index = ((slot0.observation_index as u32 + offset as u32) % cardinality as u32) as u16;
+        let slot = storage::observation_slot(index, slots.observations);
+        let found = cursor.seek_by_key_subkey(pool.address, slot)?;
+        let value = exact_slot_value(found.map(|entry| (entry.key, entry.value)), slot);
+
+        let observation = decoding::decode_observation(index, value)?;
+        if observation.initialized {
+            observations.push(observation);
+        }
+    }
+
+    Ok((slot0, observations))
+}
+
+/// Arithmetic-mean tick over the last `seconds_ago` seconds (`OracleLibrary.consult`)
+///
+/// "Now" is the timestamp of the latest block, whose state the plain storage tables
+/// hold. Fails when `seconds_ago` is zero or reaches further back than the oldest
+/// observation (the pool's cardinality is too small for the window).
+pub fn compute_twap<TX: DbTx>(tx: &TX, pool: &PoolInput, seconds_ago: u32) -> Result<i32> {
+    let (slot0, observations) = read_v3_oracle_state(tx, pool)?;
+    let now = crate::historical::block_timestamp(tx, crate::events::latest_block(tx)?)?;
+    twap_tick(&observations, slot0.tick, now as u32, seconds_ago)
+}
+
+/// `OracleLibrary.consult` over observations sorted oldest first
+fn twap_tick(observations: &[Observation], tick: i32, now: u32, seconds_ago: u32) -> Result<i32> {
+    if seconds_ago == 0 {
+        return Err(eyre!("seconds_ago must be greater than zero"));
+    }
+    let cumulative_now = tick_cumulative_at(observations, tick, now, 0)?;
+    let cumulative_ago = tick_cumulative_at(observations, tick, now, seconds_ago)?;
+
+    let delta = cumulative_now - cumulative_ago;
+    let period = seconds_ago as i64;
+    let mut mean = delta / period;
+    // Round toward negative infinity
+    if delta < 0 && delta % period != 0 {
+        mean -= 1;
+    }
+    Ok(mean as i32)
+}
+
+/// `tickCumulative` at `now - seconds_ago` (`Oracle.observeSingle`)
+///
+/// Timestamps are uint32 and may have wrapped; they are compared relative to `now`.
+/// Past the newest observation the cumulative is extrapolated with the current tick;
+/// between two observations it is linearly interpolated.
+fn tick_cumulative_at(
+    observations: &[Observation],
+    tick: i32,
+    now: u32,
+    seconds_ago: u32,
+) -> Result<i64> {
+    let (Some(oldest), Some(newest)) = (observations.first(), observations.last()) else {
+        return Err(eyre!("pool has no initialized observations"));
+    };
+    // Map a timestamp onto a monotonic u64 axis ending at `now` (`Oracle.lte`):
+    // values above `now` are from before the last uint32 wrap
+    let adjusted = |time: u32| -> u64 {
+        if time > now {
+            time as u64
+        } else {
+            time as u64 + (1u64 << 32)
+        }
+    };
+    let target = adjusted(now.wrapping_sub(seconds_ago));
+
+    let newest_time = adjusted(newest.block_timestamp);
+    if newest_time <= target {
+        return Ok(newest.tick_cumulative + tick as i64 * (target - newest_time) as i64);
+    }
+    if target < adjusted(oldest.block_timestamp) {
+        return Err(eyre!(
+            "observation too old: {seconds_ago}s ago is before the oldest observation"
+        ));
+    }
+
+    // First observation at or after the target; the one before it is at or before
+    let after_idx = observations.partition_point(|o| adjusted(o.block_timestamp) < target);
+    let after = &observations[after_idx];
+    let after_time = adjusted(after.block_timestamp);
+    if after_time == target {
+        return Ok(after.tick_cumulative);
+    }
+    let before = &observations[after_idx - 1];
+    let before_time = adjusted(before.block_timestamp);
+
+    let observation_delta = (after_time - before_time) as i64;
+    let target_delta = (target - before_time) as i64;
+    Ok(before.tick_cumulative
+        + (after.tick_cumulative - before.tick_cumulative) / observation_delta * target_delta)
+}
+
+/// Read a V3 liquidity position by its key
+///
+/// `position_key` is `keccak256(abi.encodePacked(owner, tickLower, tickUpper))`, see
+/// [`storage::v3_position_key`]. The `positions` slot comes from the pool's storage
+/// layout ([`PoolInput::v3_slots`]), so forks such as PancakeSwap V3 are read
+/// correctly; a position that does not exist decodes to all zeros.
+pub fn read_v3_position<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    position_key: B256,
+) -> Result<Position> {
+    let slots = pool.v3_slots();
+    let base = U256::from_be_bytes(storage::position_slot(position_key, slots.positions).0);
+    let keys: Vec<B256> = (0..4u8).map(|i| B256::from(base + U256::from(i))).collect();
+    let values = read_slots_sorted(tx, pool.address, &keys)?;
+
+    Ok(decoding::decode_position(
+        position_key,
+        [values[0], values[1], values[2], values[3]],
     ))
 }
+
+/// Read a V4 pool's `(feeGrowthGlobal0X128, feeGrowthGlobal1X128)` accumulators
+///
+/// Fee growth per unit of liquidity since the pool was initialized; combine with
+/// tick data via [`tick_math::fee_growth_inside`]. `pool.address` is the PoolManager.
+pub fn read_v4_fee_growth<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    pool_id: B256,
+) -> Result<(U256, U256)> {
+    let (slot_0, slot_1) = pool.v4_slots().fee_growth_globals(pool_id);
+    let values = read_slots_sorted(tx, pool.address, &[slot_0, slot_1])?;
+
+    Ok((values[0], values[1]))
+}
+
+/// Read every slot of a single V3/V4 tick and decode all of its fields
+///
+/// Unlike the collectors, which only read the liquidity slot, this fills the fee
+/// growth and oracle fields and records each `(slot, value)` read in
+/// [`Tick::raw_slots`](crate::types::Tick::raw_slots), for comparing against
+/// `cast storage`. V4 pools use their own `pool_id`, falling back to `v4_pool_id`.
+pub fn read_tick_full<TX: DbTx>(
+    tx: &TX,
+    pool: &PoolInput,
+    v4_pool_id: Option<B256>,
+    tick: i32,
+) -> Result<Tick> {
+    let (base, slot_count) = match pool.protocol {
+        Protocol::UniswapV3 => {
+            let slots = pool.v3_slots();
+            (storage::tick_slot(tick, slots.ticks), 4u8)
+        }
+        Protocol::UniswapV4 => {
+            let pool_id = pool
+                .pool_id
+                .or(v4_pool_id)
+                .ok_or_else(|| eyre!("V4 pool requires pool_id"))?;
+            (pool.v4_slots().tick(pool_id, tick), 3u8)
+        }
+        Protocol::UniswapV2 => return Err(eyre!("read_tick_full only supports V3/V4 pools")),
+    };
+
+    let base = U256::from_be_bytes(base.0);
+    let keys: Vec<B256> = (0..slot_count).map(|i| B256::from(base + U256::from(i))).collect();
+    let values = read_slots_sorted(tx, pool.address, &keys)?;
+    let slots: Vec<(B256, U256)> = keys.into_iter().zip(values).collect();
+
+    decoding::decode_tick_full(tick, &slots)
+}
+
+/// Compute the token0/token1 amounts locked between each pair of adjacent
+/// initialized ticks of a collected V3/V4 pool.
+///
+/// The pool's `liquidity` is the active liquidity at the current tick; ranges above
+/// it add each crossed tick's `liquidity_net`, ranges below subtract it. Each range
+/// is priced at the pool's current `sqrtPriceX96`. Ranges with zero active
+/// liquidity are omitted.
+///
+/// A range is only right when every initialized tick between it and the current
+/// tick was collected: read the full tick set, or a `tick_lower`/`tick_upper`
+/// window that contains the current tick. `min_liquidity_gross` drops ticks
+/// anywhere, so pools read with it give wrong ranges (or fail with negative
+/// active liquidity).
+pub fn liquidity_distribution(pool: &PoolOutput) -> Result<Vec<TickRangeAmounts>> {
+    let slot0 = pool
+        .slot0
+        .as_ref()
+        .ok_or_else(|| eyre!("liquidity_distribution requires a V3/V4 pool with slot0"))?;
+    let current_liquidity = pool
+        .liquidity
+        .ok_or_else(|| eyre!("liquidity_distribution requires the pool's liquidity"))?;
+
+    let mut ticks: Vec<_> = pool.ticks.iter().collect();
+    ticks.sort_by_key(|t| t.tick);
+
+    // gaps[i] is the active liquidity between ticks[i - 1] and ticks[i]
+    let current = ticks.partition_point(|t| t.tick <= slot0.tick);
+    let mut gaps = vec![0i128; ticks.len() + 1];
+    gaps[current] = i128::try_from(current_liquidity)
+        .map_err(|_| eyre!("pool liquidity {current_liquidity} overflows i128"))?;
+    for i in current + 1..gaps.len() {
+        let tick = ticks[i - 1];
+        gaps[i] = gaps[i - 1]
+            .checked_add(tick.liquidity_net)
+            .ok_or_else(|| eyre!("liquidity overflow at tick {}", tick.tick))?;
+    }
+    for i in (0..current).rev() {
+        let tick = ticks[i];
+        gaps[i] = gaps[i + 1]
+            .checked_sub(tick.liquidity_net)
+            .ok_or_else(|| eyre!("liquidity overflow at tick {}", tick.tick))?;
+    }
+
+    let mut ranges = Vec::new();
+    for (pair, &active) in ticks.windows(2).zip(&gaps[1..]) {
+        let (lower, upper) = (pair[0], pair[1]);
+        let liquidity = u128::try_from(active).map_err(|_| {
+            eyre!("negative active liquidity at tick {} (incomplete tick set?)", lower.tick)
+        })?;
+        if liquidity == 0 {
+            continue;
+        }
+
+        let (amount0, amount1) = tick_math::get_amounts_for_liquidity(
+            slot0.sqrt_price_x96,
+            tick_math::get_sqrt_ratio_at_tick(lower.tick)?,
+            tick_math::get_sqrt_ratio_at_tick(upper.tick)?,
+            liquidity,
+        );
+
+        ranges.push(TickRangeAmounts {
+            tick_lower: lower.tick,
+            tick_upper: upper.tick,
+            liquidity,
+            amount0,
+            amount1,
+        });
+    }
+
+    Ok(ranges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_exact_slot_value_sparse_storage() {
+        let reserve_slot = storage::simple_slot(v2::RESERVE);
+        // Sparse fixture: slot 8 unset, slot 9 (price0CumulativeLast) populated.
+        let next_slot = storage::simple_slot(9);
+        let next_value = U256::from(0xdead_beef_u64) << 120;
+
+        // The cursor lands on slot 9; this must not be read as reserves.
+        let value = exact_slot_value(Some((next_slot, next_value)), reserve_slot);
+        assert_eq!(value, U256::ZERO);
+        let reserves = decoding::decode_v2_reserves(value).unwrap();
+        assert_eq!(reserves.reserve0, 0);
+        assert_eq!(reserves.reserve1, 0);
+
+        let value = exact_slot_value(Some((reserve_slot, next_value)), reserve_slot);
+        assert_eq!(value, next_value);
+        assert_eq!(exact_slot_value(None, reserve_slot), U256::ZERO);
+    }
+
+    /// In-memory sorted storage for exercising the sorted read path
+    struct VecCursor {
+        entries: Vec<(B256, U256)>,
+        pos: usize,
+        seeks: usize,
+    }
+
+    impl SlotCursor for VecCursor {
+        fn seek(&mut self, key: B256) -> Result<Option<(B256, U256)>> {
+            self.seeks += 1;
+            self.pos = self.entries.partition_point(|(slot, _)| *slot < key);
+            Ok(self.entries.get(self.pos).copied())
+        }
+
+        fn next(&mut self) -> Result<Option<(B256, U256)>> {
+            self.pos += 1;
+            Ok(self.entries.get(self.pos).copied())
+        }
+    }
+
+    #[test]
+    fn test_read_sorted_matches_random_seeks() {
+        let slot = |n: u64| B256::from(U256::from(n));
+        // Sparse storage: even slots 0..200 set, value = slot number + 1
+        let entries: Vec<(B256, U256)> =
+            (0..100).map(|n| (slot(n * 2), U256::from(n * 2 + 1))).collect();
+        let mut cursor = VecCursor { entries, pos: 0, seeks: 0 };
+
+        // Unsorted, with unset (odd) slots, duplicates, a big gap and a key past the end
+        let keys: Vec<B256> = [40, 3, 2, 2, 190, 4, 500, 0].into_iter().map(slot).collect();
+        let values = read_sorted_with(&mut cursor, &keys).unwrap();
+
+        let expected: Vec<U256> = [41, 0, 3, 3, 191, 5, 0, 1].into_iter().map(U256::from).collect();
+        assert_eq!(values, expected);
+        // Close keys are reached by stepping; only the first read and the gaps seek
+        assert!(cursor.seeks < keys.len());
+    }
+
+    #[test]
+    fn test_v4_include_empty_fills_missing_words() {
+        let pool_id = B256::repeat_byte(0x11);
+        let pool = PoolInput::new_v4_with_id(Address::ZERO, 60, pool_id);
+        let slots = pool.v4_slots();
+        // Only word 1 is stored; Reth keeps no entries for zero words -1 and 0
+        let mut entries = vec![(slots.bitmap(pool_id, 1), U256::from(5u8))];
+        entries.push((slots.slot0(pool_id), U256::from(1u8)));
+        entries.sort();
+        let mut cursor = VecCursor { entries, pos: 0, seeks: 0 };
+
+        let mut words = |pool: &PoolInput| -> Vec<(i16, U256)> {
+            read_v4_bitmaps_with(&mut cursor, pool, pool_id, &[-1, 0, 1])
+                .unwrap()
+                .into_iter()
+                .map(|bitmap| (bitmap.word_pos, bitmap.bitmap))
+                .collect()
+        };
+        assert_eq!(words(&pool), vec![(1, U256::from(5u8))]);
+        assert_eq!(
+            words(&pool.with_include_empty(true)),
+            vec![(-1, U256::ZERO), (0, U256::ZERO), (1, U256::from(5u8))]
+        );
+    }
+
+    #[test]
+    fn test_walk_present_slots() {
+        let slot = |n: u64| B256::from(U256::from(n));
+        let entries: Vec<(B256, U256)> = (0..50).map(|n| (slot(n), U256::from(n + 100))).collect();
+        let mut cursor = VecCursor { entries, pos: 0, seeks: 0 };
+
+        let wanted: HashMap<B256, i16> = [(slot(7), -1i16), (slot(3), 2), (slot(99), 5)].into();
+        let found = walk_present_slots(&mut cursor, &wanted).unwrap();
+        assert_eq!(found, vec![(2, U256::from(103u64)), (-1, U256::from(107u64))]);
+        assert_eq!(cursor.seeks, 1);
+
+        // Early stop once every wanted slot was seen
+        let wanted: HashMap<B256, i16> = [(slot(3), 0)].into();
+        walk_present_slots(&mut cursor, &wanted).unwrap();
+        assert_eq!(cursor.pos, 3);
+    }
+
+    #[test]
+    fn test_check_bitmap_ticks_present() {
+        let pool = PoolInput::new_v3(Address::ZERO, 60);
+        assert!(check_bitmap_ticks_present(&pool, 60, 3, 3).is_ok());
+
+        let err = check_bitmap_ticks_present(&pool, 60, 5, 1).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ScrapeError>(),
+            Some(&ScrapeError::TickSpacingMismatch {
+                pool: Address::ZERO,
+                tick_spacing: 60,
+                missing: 4,
+                expected: 5,
+            })
+        );
+    }
+
+    #[test]
+    fn test_decode_pool_slot0_validation() {
+        // A non-pool word: sqrtPriceX96 of 1 is below MIN_SQRT_RATIO
+        let value = U256::from(1u8);
+        let pool = PoolInput::new_v3(Address::ZERO, 60);
+        assert!(decode_pool_slot0(&pool, value).is_ok());
+
+        let err = decode_pool_slot0(&pool.with_validate_slot0(true), value).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ScrapeError>(),
+            Some(&ScrapeError::ImplausibleSlot0 {
+                pool: Address::ZERO,
+                sqrt_price_x96: U256::from(1u8),
+                tick: 0,
+            })
+        );
+    }
+
+    #[test]
+    fn test_plan_v3_slots() {
+        let pool = PoolInput::new_v3(Address::ZERO, 60).with_tick_range(Some(-20000), Some(20000));
+        let plan = plan_v3_slots(&pool).unwrap();
+
+        let labels: Vec<&str> = plan.iter().map(|(label, _)| label.as_str()).collect();
+        assert_eq!(
+            labels,
+            vec!["slot0", "liquidity", "bitmap[-2]", "bitmap[-1]", "bitmap[0]", "bitmap[1]"]
+        );
+        assert_eq!(plan[1].1, storage::simple_slot(storage::v3::LIQUIDITY));
+        assert_eq!(plan[4].1, storage::bitmap_slot(0, storage::v3::TICK_BITMAP));
+
+        assert!(plan_v3_slots(&PoolInput::new_v2(Address::ZERO)).is_err());
+    }
+
+    #[test]
+    fn test_zero_tick_spacing_is_an_error() {
+        let pool = PoolInput::new_v3(Address::ZERO, 0);
+        let err = pool_tick_spacing(&pool).unwrap_err();
+        assert_eq!(
+            err.downcast_ref::<ScrapeError>(),
+            Some(&ScrapeError::InvalidTickSpacing {
+                pool: Address::ZERO,
+                tick_spacing: 0,
+            })
+        );
+        assert!(plan_v3_slots(&pool).is_err());
+        assert_eq!(pool_tick_spacing(&PoolInput::new_v4(Address::ZERO, 60)).unwrap(), 60);
+    }
+
+    #[test]
+    fn test_twap_tick() {
+        let observation = |block_timestamp: u32, tick_cumulative: i64| Observation {
+            block_timestamp,
+            tick_cumulative,
+            initialized: true,
+            ..Default::default()
+        };
+        // Tick -10 from t=1000 to t=1100, then tick 30 until t=1200; current tick 50
+        let observations = vec![
+            observation(1000, 0),
+            observation(1100, -1000),
+            observation(1200, 2000),
+        ];
+
+        // Extrapolated past the newest observation with the current tick
+        assert_eq!(twap_tick(&observations, 50, 1300, 100).unwrap(), 50);
+        // Exactly on observations
+        assert_eq!(twap_tick(&observations, 50, 1200, 100).unwrap(), 30);
+        // Interpolated: [1050, 1250] = 50s at -10, 100s at 30, 50s at 50
+        assert_eq!(twap_tick(&observations, 50, 1250, 200).unwrap(), 25);
+        assert_eq!(twap_tick(&observations[..2], -7, 1103, 3).unwrap(), -7);
+        // Negative mean rounds down: [1097, 1103] = 3s at -10, 3s at -7 => -8.5 -> -9
+        assert_eq!(twap_tick(&observations[..2], -7, 1103, 6).unwrap(), -9);
+
+        assert!(twap_tick(&observations, 50, 1200, 201).is_err());
+        assert!(twap_tick(&observations, 50, 1200, 0).is_err());
+        assert!(twap_tick(&[], 50, 1200, 10).is_err());
+
+        // uint32 timestamp wraparound between observations
+        let wrapped = vec![observation(u32::MAX - 49, 0), observation(50, 1000)];
+        assert_eq!(twap_tick(&wrapped, 10, 50, 100).unwrap(), 10);
+    }
+
+    #[test]
+    fn test_is_plausible_slot0() {
+        // Pack sqrtPriceX96 (bits 0-159) and int24 tick (bits 160-183)
+        let slot0 = |sqrt_price: U256, tick: i32| -> U256 {
+            sqrt_price | (U256::from(tick as u32 & 0xFFFFFF) << 160) | (U256::from(1u8) << 240)
+        };
+
+        let price = tick_math::get_sqrt_ratio_at_tick(-887).unwrap() + U256::from(1u8);
+        assert!(is_plausible_slot0(slot0(price, -887)));
+        // Tick disagreeing with the price
+        assert!(!is_plausible_slot0(slot0(price, 1000)));
+        // Empty slot and a V2-style totalSupply word
+        assert!(!is_plausible_slot0(U256::ZERO));
+        assert!(!is_plausible_slot0(U256::from(10u64).pow(U256::from(21u8))));
+    }
+
+    #[test]
+    fn test_liquidity_distribution_anchors_to_current_liquidity() {
+        let (outer, inner) = (1_000i128, 500i128);
+        let tick = |tick, liquidity_net: i128| Tick {
+            tick,
+            liquidity_gross: liquidity_net.unsigned_abs(),
+            liquidity_net,
+            initialized: true,
+            ..Default::default()
+        };
+        let slot0 = Slot0 {
+            sqrt_price_x96: tick_math::get_sqrt_ratio_at_tick(0).unwrap(),
+            tick: 0,
+            ..Default::default()
+        };
+        let ticks = [tick(-120, outer), tick(-60, inner), tick(60, -inner), tick(120, -outer)];
+        let pool_with = |keep: &dyn Fn(i32) -> bool| {
+            let ticks = ticks.iter().filter(|t| keep(t.tick)).cloned().collect();
+            PoolOutput::new_v3(Address::ZERO, slot0.clone(), 1_500, ticks, Vec::new())
+        };
+        let ranges = |pool: &PoolOutput| -> Vec<(i32, i32, u128)> {
+            liquidity_distribution(pool)
+                .unwrap()
+                .iter()
+                .map(|r| (r.tick_lower, r.tick_upper, r.liquidity))
+                .collect()
+        };
+
+        assert_eq!(
+            ranges(&pool_with(&|_| true)),
+            [(-120, -60, 1_000), (-60, 60, 1_500), (60, 120, 1_000)]
+        );
+        // `tick_lower`/`tick_upper` reads around the price and above it
+        assert_eq!(ranges(&pool_with(&|t| (-60..=60).contains(&t))), [(-60, 60, 1_500)]);
+        assert_eq!(ranges(&pool_with(&|t| t >= 60)), [(60, 120, 1_000)]);
+    }
+}
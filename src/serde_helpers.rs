@@ -0,0 +1,50 @@
+/// Serde helpers for downstream JSON consumers.
+///
+/// alloy serializes `U256` as a 0x-prefixed hex string, which JS/Python consumers
+/// often cannot parse as a number. With the `u256_decimal` feature the `U256` fields
+/// of the output types use [`serialize_u256_as_decimal`] instead; it can also be
+/// applied to your own fields with `#[serde(with = "...")]`.
+use alloy_primitives::U256;
+
+/// `#[serde(with = "serialize_u256_as_decimal")]`: a `U256` as a base-10 string
+///
+/// Deserializing accepts base-10 or 0x-prefixed hex strings, so data written
+/// before switching formats still loads.
+pub mod serialize_u256_as_decimal {
+    use super::U256;
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::str::FromStr;
+
+    pub fn serialize<S: Serializer>(value: &U256, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&value.to_string())
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<U256, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        U256::from_str(&s).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use alloy_primitives::U256;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Wrapper {
+        #[serde(with = "super::serialize_u256_as_decimal")]
+        value: U256,
+    }
+
+    #[test]
+    fn test_u256_decimal_round_trip() {
+        let wrapper = Wrapper { value: U256::MAX };
+        let json = serde_json::to_string(&wrapper).unwrap();
+        assert_eq!(json, format!("{{\"value\":\"{}\"}}", U256::MAX));
+        assert_eq!(serde_json::from_str::<Wrapper>(&json).unwrap(), wrapper);
+
+        let hex: Wrapper = serde_json::from_str(r#"{"value":"0x1f"}"#).unwrap();
+        assert_eq!(hex.value, U256::from(31u8));
+        assert!(serde_json::from_str::<Wrapper>(r#"{"value":"nope"}"#).is_err());
+    }
+}
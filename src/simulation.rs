@@ -0,0 +1,459 @@
+/// Swap simulation over collected V3/V4 pool state.
+///
+/// Ports the exact-input path of Uniswap's `SwapMath.computeSwapStep` and the
+/// `SqrtPriceMath` helpers it uses, stepping through the tick bitmap one word at a
+/// time like `UniswapV3Pool.swap`. Results match the contract as long as the
+/// collected ticks cover the price range the swap moves through.
+use alloy_primitives::U256;
+use eyre::{eyre, Result};
+
+use crate::{
+    tick_math::{self, mul_div, mul_div_rounding_up, Q96},
+    types::{PoolOutput, Tick},
+};
+
+/// Fee denominator: fees are in hundredths of a bip (`3000` = 0.3%)
+const FEE_DENOMINATOR: u32 = 1_000_000;
+
+/// Outcome of [`swap_exact_in`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapResult {
+    /// Input actually consumed, fees included. Less than requested when the swap
+    /// runs out of liquidity before spending everything.
+    pub amount_in: U256,
+    /// Output received
+    pub amount_out: U256,
+    /// Fees paid to liquidity providers, in the input token
+    pub fee_amount: U256,
+    /// Pool price after the swap
+    pub sqrt_price_x96: U256,
+    /// Pool tick after the swap
+    pub tick: i32,
+    /// Active liquidity after the swap
+    pub liquidity: u128,
+    /// Number of initialized ticks crossed
+    pub ticks_crossed: u32,
+}
+
+/// `SqrtPriceMath.getAmount0Delta`: token0 between two prices for `liquidity`
+fn amount0_delta(sqrt_a: U256, sqrt_b: U256, liquidity: u128, round_up: bool) -> U256 {
+    let (sqrt_a, sqrt_b) = (sqrt_a.min(sqrt_b), sqrt_a.max(sqrt_b));
+    let numerator1 = U256::from(liquidity) << 96;
+    let numerator2 = sqrt_b - sqrt_a;
+
+    if round_up {
+        let scaled = mul_div_rounding_up(numerator1, numerator2, sqrt_b);
+        scaled.div_ceil(sqrt_a)
+    } else {
+        mul_div(numerator1, numerator2, sqrt_b) / sqrt_a
+    }
+}
+
+/// `SqrtPriceMath.getAmount1Delta`: token1 between two prices for `liquidity`
+fn amount1_delta(sqrt_a: U256, sqrt_b: U256, liquidity: u128, round_up: bool) -> U256 {
+    let (sqrt_a, sqrt_b) = (sqrt_a.min(sqrt_b), sqrt_a.max(sqrt_b));
+    if round_up {
+        mul_div_rounding_up(U256::from(liquidity), sqrt_b - sqrt_a, Q96)
+    } else {
+        mul_div(U256::from(liquidity), sqrt_b - sqrt_a, Q96)
+    }
+}
+
+/// `SqrtPriceMath.getNextSqrtPriceFromInput`: price after adding `amount_in`
+fn next_sqrt_price_from_input(
+    sqrt_price: U256,
+    liquidity: u128,
+    amount_in: U256,
+    zero_for_one: bool,
+) -> U256 {
+    if amount_in.is_zero() {
+        return sqrt_price;
+    }
+    let liquidity = U256::from(liquidity);
+
+    if zero_for_one {
+        // getNextSqrtPriceFromAmount0RoundingUp, adding token0
+        let numerator1: U256 = liquidity << 96;
+        if let Some(product) = amount_in.checked_mul(sqrt_price) {
+            if let Some(denominator) = numerator1.checked_add(product) {
+                return mul_div_rounding_up(numerator1, sqrt_price, denominator);
+            }
+        }
+        numerator1.div_ceil(numerator1 / sqrt_price + amount_in)
+    } else {
+        // getNextSqrtPriceFromAmount1RoundingDown, adding token1
+        let quotient = if amount_in < U256::from(1u8) << 160 {
+            (amount_in << 96) / liquidity
+        } else {
+            mul_div(amount_in, Q96, liquidity)
+        };
+        sqrt_price + quotient
+    }
+}
+
+/// One exact-input `SwapMath.computeSwapStep` towards `sqrt_target`
+///
+/// Returns `(sqrt_next, amount_in, amount_out, fee_amount)`.
+fn compute_swap_step(
+    sqrt_current: U256,
+    sqrt_target: U256,
+    liquidity: u128,
+    amount_remaining: U256,
+    fee: u32,
+) -> (U256, U256, U256, U256) {
+    let zero_for_one = sqrt_current >= sqrt_target;
+    let fee_complement = U256::from(FEE_DENOMINATOR - fee);
+
+    let amount_remaining_less_fee = mul_div(
+        amount_remaining,
+        fee_complement,
+        U256::from(FEE_DENOMINATOR),
+    );
+    let amount_in_to_target = if zero_for_one {
+        amount0_delta(sqrt_target, sqrt_current, liquidity, true)
+    } else {
+        amount1_delta(sqrt_current, sqrt_target, liquidity, true)
+    };
+
+    let sqrt_next = if amount_remaining_less_fee >= amount_in_to_target {
+        sqrt_target
+    } else {
+        next_sqrt_price_from_input(
+            sqrt_current,
+            liquidity,
+            amount_remaining_less_fee,
+            zero_for_one,
+        )
+    };
+    let reached_target = sqrt_next == sqrt_target;
+
+    let (amount_in, amount_out) = if zero_for_one {
+        (
+            if reached_target {
+                amount_in_to_target
+            } else {
+                amount0_delta(sqrt_next, sqrt_current, liquidity, true)
+            },
+            amount1_delta(sqrt_next, sqrt_current, liquidity, false),
+        )
+    } else {
+        (
+            if reached_target {
+                amount_in_to_target
+            } else {
+                amount1_delta(sqrt_current, sqrt_next, liquidity, true)
+            },
+            amount0_delta(sqrt_current, sqrt_next, liquidity, false),
+        )
+    };
+
+    // The remainder goes to fees when the target was not reached
+    let fee_amount = if reached_target {
+        mul_div_rounding_up(amount_in, U256::from(fee), fee_complement)
+    } else {
+        amount_remaining - amount_in
+    };
+
+    (sqrt_next, amount_in, amount_out, fee_amount)
+}
+
+/// `TickBitmap.nextInitializedTickWithinOneWord` over the collected ticks
+///
+/// Returns the next initialized tick at or below (`lte`) or above `tick`, limited to
+/// the bitmap word the search starts in, and whether that tick is initialized. The
+/// swap loop stops at each word boundary the same way the contract does.
+fn next_initialized_tick_within_one_word(
+    ticks: &[Tick],
+    tick: i32,
+    tick_spacing: i32,
+    lte: bool,
+) -> (i32, bool) {
+    let compressed = tick.div_euclid(tick_spacing);
+    let initialized = ticks
+        .iter()
+        .filter(|t| t.liquidity_gross > 0)
+        .map(|t| t.tick);
+
+    if lte {
+        let word_start = ((compressed >> 8) << 8) * tick_spacing;
+        initialized
+            .filter(|&t| t >= word_start && t <= tick)
+            .max()
+            .map_or((word_start, false), |t| (t, true))
+    } else {
+        let word_end = ((((compressed + 1) >> 8) << 8) + 255) * tick_spacing;
+        initialized
+            .filter(|&t| t > tick && t <= word_end)
+            .min()
+            .map_or((word_end, false), |t| (t, true))
+    }
+}
+
+/// Simulate an exact-input swap against a collected V3/V4 pool
+///
+/// Starts from the pool's `slot0` and `liquidity` and crosses the initialized ticks
+/// in `pool.ticks` (those with non-zero `liquidity_gross`). `zero_for_one` sells
+/// token0 for token1; `fee` is in hundredths of a bip and `tick_spacing` is the
+/// pool's, which sets the bitmap word boundaries each step stops at. There is no
+/// price limit: the swap stops when `amount_in` is spent or the price reaches the
+/// end of the tick range, in which case [`SwapResult::amount_in`] is less than
+/// requested.
+///
+/// Fails if `slot0.sqrt_price_x96` is outside `[MIN_SQRT_RATIO, MAX_SQRT_RATIO)`,
+/// e.g. for an uninitialized pool read back as zeroes, or already at the end of the
+/// range in the swap direction.
+///
+/// Ticks outside the collected range (e.g. a `tick_lower`/`tick_upper` read) are
+/// treated as uninitialized, so large swaps on partial reads are inaccurate.
+pub fn swap_exact_in(
+    pool: &PoolOutput,
+    zero_for_one: bool,
+    amount_in: U256,
+    fee: u32,
+    tick_spacing: i32,
+) -> Result<SwapResult> {
+    let slot0 = pool
+        .slot0
+        .as_ref()
+        .ok_or_else(|| eyre!("swap simulation requires a V3/V4 pool with slot0"))?;
+    if fee >= FEE_DENOMINATOR {
+        return Err(eyre!("fee {fee} must be below {FEE_DENOMINATOR}"));
+    }
+    if tick_spacing <= 0 {
+        return Err(eyre!("tick spacing {tick_spacing} must be positive"));
+    }
+    if !(tick_math::MIN_SQRT_RATIO..tick_math::MAX_SQRT_RATIO).contains(&slot0.sqrt_price_x96) {
+        return Err(eyre!(
+            "sqrt price {} out of range, pool not initialized?",
+            slot0.sqrt_price_x96
+        ));
+    }
+
+    let sqrt_limit = if zero_for_one {
+        tick_math::MIN_SQRT_RATIO + U256::from(1u8)
+    } else {
+        tick_math::MAX_SQRT_RATIO - U256::from(1u8)
+    };
+    // The contract requires the limit to be beyond the current price
+    let limit_ahead = if zero_for_one {
+        sqrt_limit < slot0.sqrt_price_x96
+    } else {
+        sqrt_limit > slot0.sqrt_price_x96
+    };
+    if !limit_ahead {
+        return Err(eyre!(
+            "sqrt price {} is already at the price limit",
+            slot0.sqrt_price_x96
+        ));
+    }
+
+    let mut amount_remaining = amount_in;
+    let mut amount_out = U256::ZERO;
+    let mut fee_amount = U256::ZERO;
+    let mut sqrt_price = slot0.sqrt_price_x96;
+    let mut tick = slot0.tick;
+    let mut liquidity = pool.liquidity.unwrap_or_default();
+    let mut ticks_crossed = 0u32;
+
+    while !amount_remaining.is_zero() && sqrt_price != sqrt_limit {
+        let (tick_next, initialized) =
+            next_initialized_tick_within_one_word(&pool.ticks, tick, tick_spacing, zero_for_one);
+        let tick_next = tick_next.clamp(tick_math::MIN_TICK, tick_math::MAX_TICK);
+        let sqrt_next = tick_math::get_sqrt_ratio_at_tick(tick_next)?;
+
+        let sqrt_target = if zero_for_one {
+            sqrt_next.max(sqrt_limit)
+        } else {
+            sqrt_next.min(sqrt_limit)
+        };
+        let sqrt_start = sqrt_price;
+        let (sqrt_after, step_in, step_out, step_fee) =
+            compute_swap_step(sqrt_price, sqrt_target, liquidity, amount_remaining, fee);
+
+        sqrt_price = sqrt_after;
+        amount_remaining -= step_in + step_fee;
+        amount_out += step_out;
+        fee_amount += step_fee;
+
+        if sqrt_price == sqrt_next {
+            if initialized {
+                let net = pool
+                    .ticks
+                    .iter()
+                    .filter(|t| t.tick == tick_next)
+                    .map(|t| t.liquidity_net)
+                    .sum::<i128>();
+                let net = if zero_for_one { -net } else { net };
+                liquidity = liquidity
+                    .checked_add_signed(net)
+                    .ok_or_else(|| eyre!("liquidity out of range crossing tick {tick_next}"))?;
+                ticks_crossed += 1;
+            }
+            tick = if zero_for_one {
+                tick_next - 1
+            } else {
+                tick_next
+            };
+        } else if sqrt_price != sqrt_start {
+            tick = tick_math::get_tick_at_sqrt_ratio(sqrt_price)?;
+        }
+    }
+
+    Ok(SwapResult {
+        amount_in: amount_in - amount_remaining,
+        amount_out,
+        fee_amount,
+        sqrt_price_x96: sqrt_price,
+        tick,
+        liquidity,
+        ticks_crossed,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Slot0;
+    use alloy_primitives::Address;
+
+    const LIQUIDITY: u128 = 1_000_000_000_000_000_000;
+
+    /// Pool at tick 0 with one position over `[-60, 60)`
+    fn pool() -> PoolOutput {
+        let tick = |tick, liquidity_net| Tick {
+            tick,
+            liquidity_gross: LIQUIDITY,
+            liquidity_net,
+            initialized: true,
+            ..Default::default()
+        };
+        let slot0 = Slot0 {
+            sqrt_price_x96: Q96,
+            tick: 0,
+            ..Default::default()
+        };
+        PoolOutput::new_v3(
+            Address::ZERO,
+            slot0,
+            LIQUIDITY,
+            vec![tick(-60, LIQUIDITY as i128), tick(60, -(LIQUIDITY as i128))],
+            Vec::new(),
+        )
+    }
+
+    #[test]
+    fn test_compute_swap_step_capped_at_target() {
+        let target = tick_math::get_sqrt_ratio_at_tick(-10).unwrap();
+        let (next, amount_in, amount_out, fee_amount) =
+            compute_swap_step(Q96, target, LIQUIDITY, U256::from(10u128.pow(18)), 3000);
+
+        assert_eq!(next, target);
+        assert_eq!(amount_in, amount0_delta(target, Q96, LIQUIDITY, true));
+        assert_eq!(amount_out, amount1_delta(target, Q96, LIQUIDITY, false));
+        let expected_fee =
+            mul_div_rounding_up(amount_in, U256::from(3000u32), U256::from(997_000u32));
+        assert_eq!(fee_amount, expected_fee);
+    }
+
+    #[test]
+    fn test_compute_swap_step_spends_everything() {
+        let target = tick_math::get_sqrt_ratio_at_tick(10).unwrap();
+        let amount = U256::from(1_000_000u64);
+        let (next, amount_in, amount_out, fee_amount) =
+            compute_swap_step(Q96, target, LIQUIDITY, amount, 500);
+
+        assert!(next > Q96 && next < target);
+        assert_eq!(amount_in + fee_amount, amount);
+        assert!(amount_out < amount_in);
+    }
+
+    #[test]
+    fn test_small_swap_stays_in_range() {
+        let pool = pool();
+        let amount = U256::from(10u64.pow(15));
+
+        let result = swap_exact_in(&pool, true, amount, 3000, 60).unwrap();
+        assert_eq!(result.amount_in, amount);
+        assert!(result.amount_out < amount);
+        assert!(result.amount_out > amount * U256::from(99u8) / U256::from(100u8));
+        assert!(result.sqrt_price_x96 < Q96);
+        assert!((-60..0).contains(&result.tick));
+        assert_eq!(
+            result.tick,
+            tick_math::get_tick_at_sqrt_ratio(result.sqrt_price_x96).unwrap()
+        );
+        assert_eq!(result.liquidity, LIQUIDITY);
+        assert_eq!(result.ticks_crossed, 0);
+
+        let result = swap_exact_in(&pool, false, amount, 3000, 60).unwrap();
+        assert!(result.sqrt_price_x96 > Q96);
+        assert!((0..60).contains(&result.tick));
+    }
+
+    #[test]
+    fn test_large_swap_drains_the_range() {
+        let pool = pool();
+        let amount = U256::from(10u128.pow(24));
+        let lower = tick_math::get_sqrt_ratio_at_tick(-60).unwrap();
+
+        let result = swap_exact_in(&pool, true, amount, 3000, 60).unwrap();
+        // All token1 in [-60, 0) comes out, then the price runs to the limit
+        assert_eq!(
+            result.amount_out,
+            tick_math::get_amount1_for_liquidity(lower, Q96, LIQUIDITY)
+        );
+        assert!(result.amount_in < amount);
+        assert_eq!(result.liquidity, 0);
+        assert_eq!(result.ticks_crossed, 1);
+        assert_eq!(
+            result.sqrt_price_x96,
+            tick_math::MIN_SQRT_RATIO + U256::from(1u8)
+        );
+        assert_eq!(result.tick, tick_math::MIN_TICK);
+    }
+
+    #[test]
+    fn test_next_initialized_tick_within_one_word() {
+        let ticks = pool().ticks;
+        let next =
+            |tick, spacing, lte| next_initialized_tick_within_one_word(&ticks, tick, spacing, lte);
+
+        // Spacing 1: words are 256 ticks, searches stop at their edges
+        assert_eq!(next(0, 1, true), (0, false));
+        assert_eq!(next(-1, 1, true), (-60, true));
+        assert_eq!(next(-61, 1, true), (-256, false));
+        assert_eq!(next(0, 1, false), (60, true));
+        assert_eq!(next(60, 1, false), (255, false));
+        assert_eq!(next(255, 1, false), (511, false));
+
+        // Spacing 60: tick -1 compresses to -1, in the word below 0
+        assert_eq!(next(-1, 60, true), (-60, true));
+        assert_eq!(next(-61, 60, true), (-256 * 60, false));
+        assert_eq!(next(60, 60, false), (255 * 60, false));
+    }
+
+    #[test]
+    fn test_rejects_uninitialized_price() {
+        let mut pool = pool();
+        pool.slot0 = Some(Slot0::default());
+        for zero_for_one in [true, false] {
+            assert!(swap_exact_in(&pool, zero_for_one, U256::from(1u8), 3000, 60).is_err());
+        }
+
+        // At the edge of the range there is nothing to swap towards
+        pool.slot0 = Some(Slot0 {
+            sqrt_price_x96: tick_math::MIN_SQRT_RATIO,
+            ..Default::default()
+        });
+        assert!(swap_exact_in(&pool, true, U256::from(1u8), 3000, 60).is_err());
+        assert!(swap_exact_in(&pool, false, U256::from(1u8), 3000, 60).is_ok());
+    }
+
+    #[test]
+    fn test_requires_slot0() {
+        let v2 = PoolOutput::new_v2(Address::ZERO, Default::default());
+        assert!(swap_exact_in(&v2, true, U256::from(1u8), 3000, 60).is_err());
+        assert!(swap_exact_in(&pool(), true, U256::from(1u8), FEE_DENOMINATOR, 60).is_err());
+        assert!(swap_exact_in(&pool(), true, U256::from(1u8), 3000, 0).is_err());
+    }
+}
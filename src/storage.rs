@@ -1,5 +1,6 @@
 use alloy_primitives::{keccak256, Address, B256, U256};
 use alloy_sol_types::SolValue;
+use serde::{Deserialize, Serialize};
 
 /// Known V3 factory addresses for storage layout detection.
 pub mod factories {
@@ -42,31 +43,63 @@ pub mod pancakeswap_v3 {
 /// Get V3 storage slots based on factory address.
 /// Returns (slot0, liquidity, ticks, tick_bitmap) slot numbers.
 pub fn v3_slots_for_factory(factory: Option<Address>) -> V3Slots {
-    if let Some(f) = factory {
-        if f == factories::PANCAKESWAP_V3 {
-            return V3Slots {
-                slot0: pancakeswap_v3::SLOT0,
-                liquidity: pancakeswap_v3::LIQUIDITY,
-                ticks: pancakeswap_v3::TICKS,
-                tick_bitmap: pancakeswap_v3::TICK_BITMAP,
-            };
-        }
-    }
-    V3Slots {
-        slot0: v3::SLOT0,
-        liquidity: v3::LIQUIDITY,
-        ticks: v3::TICKS,
-        tick_bitmap: v3::TICK_BITMAP,
+    match factory {
+        Some(f) if f == factories::PANCAKESWAP_V3 => V3Slots::pancake_v3(),
+        _ => V3Slots::canonical_v3(),
     }
 }
 
 /// V3 storage slot configuration.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct V3Slots {
     pub slot0: u8,
     pub liquidity: u8,
     pub ticks: u8,
     pub tick_bitmap: u8,
+    pub positions: u8,
+    pub observations: u8,
+}
+
+impl V3Slots {
+    /// Uniswap V3 layout, shared by unmodified forks.
+    pub const fn canonical_v3() -> Self {
+        Self {
+            slot0: v3::SLOT0,
+            liquidity: v3::LIQUIDITY,
+            ticks: v3::TICKS,
+            tick_bitmap: v3::TICK_BITMAP,
+            positions: v3::POSITIONS,
+            observations: v3::OBSERVATIONS,
+        }
+    }
+
+    /// SushiSwap V3 (an unmodified Uniswap V3 fork).
+    pub const fn sushi_v3() -> Self {
+        Self::canonical_v3()
+    }
+
+    /// PancakeSwap V3 (`lmPool` shifts everything after slot0 by one slot).
+    pub const fn pancake_v3() -> Self {
+        Self {
+            slot0: pancakeswap_v3::SLOT0,
+            liquidity: pancakeswap_v3::LIQUIDITY,
+            ticks: pancakeswap_v3::TICKS,
+            tick_bitmap: pancakeswap_v3::TICK_BITMAP,
+            positions: pancakeswap_v3::POSITIONS,
+            observations: pancakeswap_v3::OBSERVATIONS,
+        }
+    }
+
+    /// Look up a layout by name: `"uniswap_v3"`/`"canonical_v3"`, `"sushi_v3"` or
+    /// `"pancake_v3"` (case-insensitive).
+    pub fn by_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "uniswap_v3" | "canonical_v3" => Some(Self::canonical_v3()),
+            "sushi_v3" | "sushiswap_v3" => Some(Self::sushi_v3()),
+            "pancake_v3" | "pancakeswap_v3" => Some(Self::pancake_v3()),
+            _ => None,
+        }
+    }
 }
 
 /// `UniswapV4` storage slot constants.
@@ -75,6 +108,8 @@ pub mod v4 {
     // Main pools mapping slot.
     // PoolManager inherits from multiple contracts, so _pools is at slot 6.
     pub const POOLS_SLOT: u8 = 6;
+    // ProtocolFees.protocolFeesAccrued (Currency => uint256), after Owned.owner.
+    pub const PROTOCOL_FEES_ACCRUED_SLOT: u8 = 1;
 
     // Offsets within Pool.State struct (relative to pool's base slot).
     pub const SLOT0_OFFSET: u8 = 0;
@@ -83,11 +118,57 @@ pub mod v4 {
     pub const LIQUIDITY_OFFSET: u8 = 3;
     pub const TICKS_OFFSET: u8 = 4;
     pub const TICK_BITMAP_OFFSET: u8 = 5;
+    pub const POSITIONS_OFFSET: u8 = 6;
 }
 
 /// `UniswapV2` storage slot constants.
 pub mod v2 {
+    pub const TOKEN0: u8 = 6;
+    pub const TOKEN1: u8 = 7;
     pub const RESERVE: u8 = 8;
+    pub const PRICE0_CUMULATIVE_LAST: u8 = 9;
+    pub const PRICE1_CUMULATIVE_LAST: u8 = 10;
+    pub const K_LAST: u8 = 11;
+}
+
+/// Where a V2-style pair stores its reserves.
+///
+/// Uniswap V2 packs both reserves and `blockTimestampLast` into slot 8; some forks
+/// (e.g. Solidly/Velodrome variants) keep them in separate full slots instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case", tag = "kind")]
+pub enum V2ReserveLayout {
+    /// `uint112 reserve0 | uint112 reserve1 | uint32 blockTimestampLast` in one slot.
+    Packed { slot: u8 },
+    /// Each reserve (and optionally the timestamp) in its own full slot.
+    Unpacked {
+        reserve0_slot: u8,
+        reserve1_slot: u8,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        timestamp_slot: Option<u8>,
+    },
+}
+
+impl Default for V2ReserveLayout {
+    fn default() -> Self {
+        Self::Packed { slot: v2::RESERVE }
+    }
+}
+
+impl V2ReserveLayout {
+    /// Storage keys to read, in the order `decode_v2_reserves_with_layout` expects.
+    pub fn slots(&self) -> Vec<B256> {
+        match *self {
+            Self::Packed { slot } => vec![simple_slot(slot)],
+            Self::Unpacked { reserve0_slot, reserve1_slot, timestamp_slot } => {
+                [Some(reserve0_slot), Some(reserve1_slot), timestamp_slot]
+                    .into_iter()
+                    .flatten()
+                    .map(simple_slot)
+                    .collect()
+            }
+        }
+    }
 }
 
 /// Calculate storage slot for a simple value at a fixed slot.
@@ -98,6 +179,13 @@ pub fn simple_slot(slot: u8) -> B256 {
     B256::from(data)
 }
 
+/// Calculate storage slot for `Oracle.Observation[65535] observations`.
+/// A fixed-size array is laid out inline: element `i` is at `arraySlot + i`
+/// (each Observation packs into a single slot).
+pub fn observation_slot(index: u16, array_slot: u8) -> B256 {
+    B256::from(U256::from(array_slot) + U256::from(index))
+}
+
 /// Calculate storage slot for mapping(int16 => uint256) tickBitmap.
 /// Formula: keccak256(abi.encode(wordPos, mappingSlot))
 pub fn bitmap_slot(word_pos: i16, mapping_slot: u8) -> B256 {
@@ -112,45 +200,181 @@ pub fn tick_slot(tick: i32, mapping_slot: u8) -> B256 {
     keccak256(&encoded)
 }
 
+/// V3 position key: `keccak256(abi.encodePacked(owner, tickLower, tickUpper))`.
+/// The int24 ticks are packed as 3 big-endian two's complement bytes.
+pub fn v3_position_key(owner: Address, tick_lower: i32, tick_upper: i32) -> B256 {
+    let mut packed = [0u8; 26];
+    packed[..20].copy_from_slice(owner.as_slice());
+    packed[20..23].copy_from_slice(&tick_lower.to_be_bytes()[1..]);
+    packed[23..].copy_from_slice(&tick_upper.to_be_bytes()[1..]);
+    keccak256(packed)
+}
+
+/// Calculate the base storage slot for mapping(bytes32 => Position.Info) positions.
+/// Formula: keccak256(abi.encode(positionKey, mappingSlot))
+/// The struct spans 4 slots: liquidity, feeGrowthInside0LastX128,
+/// feeGrowthInside1LastX128, then tokensOwed0/tokensOwed1 packed.
+pub fn position_slot(position_key: B256, mapping_slot: u8) -> B256 {
+    let encoded = (position_key, U256::from(mapping_slot)).abi_encode();
+    keccak256(&encoded)
+}
+
+/// V4 storage slot configuration: the slot of PoolManager's `_pools` mapping.
+///
+/// Every V4 slot hashes off `keccak256(abi.encode(poolId, poolsSlot))`, so a
+/// deployment with a different inheritance layout needs its own `pools` slot.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct V4Slots {
+    pub pools: u8,
+}
+
+impl Default for V4Slots {
+    fn default() -> Self {
+        Self { pools: v4::POOLS_SLOT }
+    }
+}
+
+impl V4Slots {
+    /// Base slot where the Pool.State struct begins for a given poolId.
+    pub fn base(&self, pool_id: B256) -> B256 {
+        let encoded = (pool_id, U256::from(self.pools)).abi_encode();
+        keccak256(&encoded)
+    }
+
+    /// Slot0 slot of a pool.
+    pub fn slot0(&self, pool_id: B256) -> B256 {
+        add_offset(self.base(pool_id), v4::SLOT0_OFFSET)
+    }
+
+    /// `feeGrowthGlobal0X128` and `feeGrowthGlobal1X128` slots of a pool.
+    pub fn fee_growth_globals(&self, pool_id: B256) -> (B256, B256) {
+        let base = self.base(pool_id);
+        (
+            add_offset(base, v4::FEE_GROWTH_GLOBAL0_X128_OFFSET),
+            add_offset(base, v4::FEE_GROWTH_GLOBAL1_X128_OFFSET),
+        )
+    }
+
+    /// Liquidity slot of a pool.
+    pub fn liquidity(&self, pool_id: B256) -> B256 {
+        add_offset(self.base(pool_id), v4::LIQUIDITY_OFFSET)
+    }
+
+    /// Slot of `ticks[tick]` in a pool's nested ticks mapping.
+    pub fn tick(&self, pool_id: B256, tick: i32) -> B256 {
+        let ticks_mapping_slot = add_offset(self.base(pool_id), v4::TICKS_OFFSET);
+        tick_slot_from_base(tick, ticks_mapping_slot)
+    }
+
+    /// Slot of `tickBitmap[wordPos]` in a pool's nested bitmap mapping.
+    pub fn bitmap(&self, pool_id: B256, word_pos: i16) -> B256 {
+        let bitmap_mapping_slot = add_offset(self.base(pool_id), v4::TICK_BITMAP_OFFSET);
+        bitmap_slot_from_base(word_pos, bitmap_mapping_slot)
+    }
+}
+
+/// A pool storage layout, selectable per pool with `PoolInput::layout`: the slots of a
+/// V3 fork and the slot of the V4 PoolManager's `_pools` mapping.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct StorageLayout {
+    /// Slots of a V3-style pool
+    pub v3: V3Slots,
+    /// Slot of PoolManager's `_pools` mapping; upgraded or L2 deployments may differ.
+    #[serde(default = "default_v4_pools_slot")]
+    pub v4_pools: u8,
+}
+
+fn default_v4_pools_slot() -> u8 {
+    v4::POOLS_SLOT
+}
+
+impl Default for StorageLayout {
+    fn default() -> Self {
+        Self::canonical_v3()
+    }
+}
+
+impl StorageLayout {
+    /// `v3` slots with the canonical V4 `_pools` slot.
+    pub const fn from_v3(v3: V3Slots) -> Self {
+        Self { v3, v4_pools: v4::POOLS_SLOT }
+    }
+
+    /// Uniswap V3 layout, shared by unmodified forks.
+    pub const fn canonical_v3() -> Self {
+        Self::from_v3(V3Slots::canonical_v3())
+    }
+
+    /// SushiSwap V3 (an unmodified Uniswap V3 fork).
+    pub const fn sushi_v3() -> Self {
+        Self::from_v3(V3Slots::sushi_v3())
+    }
+
+    /// PancakeSwap V3 (`lmPool` shifts everything after slot0 by one slot).
+    pub const fn pancake_v3() -> Self {
+        Self::from_v3(V3Slots::pancake_v3())
+    }
+
+    /// Look up a V3 layout by name, see [`V3Slots::by_name`].
+    pub fn by_name(name: &str) -> Option<Self> {
+        V3Slots::by_name(name).map(Self::from_v3)
+    }
+
+    /// The same layout with PoolManager's `_pools` mapping at `pools_slot`.
+    pub const fn with_v4_pools(mut self, pools_slot: u8) -> Self {
+        self.v4_pools = pools_slot;
+        self
+    }
+
+    /// V4 storage slots of this layout.
+    pub fn v4_slots(&self) -> V4Slots {
+        V4Slots { pools: self.v4_pools }
+    }
+}
+
 /// Calculate storage slot for V4 nested mapping (`PoolId` => mapping(int24 => Tick)).
 /// First hash: `base_slot` = keccak256(abi.encode(poolId, poolsSlot))
 /// Then add offset for ticks mapping.
 /// Final hash: keccak256(abi.encode(tick, `base_slot` + offset))
 pub fn v4_tick_slot(pool_id: B256, tick: i32) -> B256 {
-    let base_slot = pool_base_slot(pool_id);
-    let ticks_mapping_slot = add_offset(base_slot, v4::TICKS_OFFSET);
-    tick_slot_from_base(tick, ticks_mapping_slot)
+    V4Slots::default().tick(pool_id, tick)
 }
 
 /// Calculate storage slot for V4 nested bitmap mapping.
 pub fn v4_bitmap_slot(pool_id: B256, word_pos: i16) -> B256 {
-    let base_slot = pool_base_slot(pool_id);
-    let bitmap_mapping_slot = add_offset(base_slot, v4::TICK_BITMAP_OFFSET);
-    bitmap_slot_from_base(word_pos, bitmap_mapping_slot)
+    V4Slots::default().bitmap(pool_id, word_pos)
 }
 
 /// Calculate V4 slot0 storage slot.
 pub fn v4_slot0_slot(pool_id: B256) -> B256 {
-    let base_slot = pool_base_slot(pool_id);
-    add_offset(base_slot, v4::SLOT0_OFFSET)
+    V4Slots::default().slot0(pool_id)
 }
 
 /// Calculate V4 liquidity storage slot.
 pub fn v4_liquidity_slot(pool_id: B256) -> B256 {
-    let base_slot = pool_base_slot(pool_id);
-    add_offset(base_slot, v4::LIQUIDITY_OFFSET)
+    V4Slots::default().liquidity(pool_id)
 }
 
 /// Get base storage slot for a V4 pool.
 /// This is the base slot where Pool.State struct begins for a given poolId.
 pub fn v4_base_slot(pool_id: B256) -> B256 {
-    pool_base_slot(pool_id)
+    V4Slots::default().base(pool_id)
 }
 
-/// Helper: Get base storage slot for a V4 pool.
-fn pool_base_slot(pool_id: B256) -> B256 {
-    let encoded = (pool_id, U256::from(v4::POOLS_SLOT)).abi_encode();
-    keccak256(&encoded)
+/// V4 pool ID: `keccak256(abi.encode(PoolKey))`, i.e. of
+/// `(currency0, currency1, uint24 fee, int24 tickSpacing, hooks)`.
+///
+/// `currency0` must be the lower address (native ETH is `Address::ZERO`), as the
+/// PoolManager requires when the pool is initialized.
+pub fn v4_pool_id(
+    currency0: Address,
+    currency1: Address,
+    fee: u32,
+    tick_spacing: i32,
+    hooks: Address,
+) -> B256 {
+    // uint24/int24 are padded/sign-extended to a full word exactly like uint32/int32
+    keccak256((currency0, currency1, fee, tick_spacing, hooks).abi_encode())
 }
 
 /// Helper: Add offset to a storage slot.
@@ -178,6 +402,41 @@ fn bitmap_slot_from_base(word_pos: i16, mapping_slot: B256) -> B256 {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_observation_slot() {
+        assert_eq!(observation_slot(0, v3::OBSERVATIONS), simple_slot(v3::OBSERVATIONS));
+        assert_eq!(
+            observation_slot(65534, v3::OBSERVATIONS),
+            B256::from(U256::from(8u32 + 65534))
+        );
+    }
+
+    #[test]
+    fn test_v4_pool_id() {
+        use alloy_primitives::address;
+
+        // Mainnet ETH/USDC 0.05% pool, no hooks
+        let usdc = address!("a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48");
+        assert_eq!(
+            v4_pool_id(Address::ZERO, usdc, 500, 10, Address::ZERO),
+            alloy_primitives::b256!(
+                "21c67e77068de97969ba93d4aab21826d33ca12bb9f565d8496e8fda8a82ca27"
+            )
+        );
+    }
+
+    #[test]
+    fn test_v3_position_key() {
+        use alloy_primitives::aliases::I24;
+
+        let owner = Address::from([0xab; 20]);
+        let expected = keccak256(
+            (owner, I24::try_from(-887220).unwrap(), I24::try_from(60).unwrap())
+                .abi_encode_packed(),
+        );
+        assert_eq!(v3_position_key(owner, -887220, 60), expected);
+    }
+
     #[test]
     fn test_simple_slot() {
         let slot = simple_slot(v3::SLOT0);
@@ -244,6 +503,31 @@ mod tests {
         assert_ne!(slot, slot_neg);
     }
 
+    #[test]
+    fn test_named_layouts() {
+        assert_eq!(v3_slots_for_factory(None), V3Slots::canonical_v3());
+        assert_eq!(
+            v3_slots_for_factory(Some(factories::PANCAKESWAP_V3)),
+            V3Slots::pancake_v3()
+        );
+        assert_eq!(StorageLayout::by_name("PancakeSwap_V3"), Some(StorageLayout::pancake_v3()));
+        assert_eq!(StorageLayout::by_name("sushi_v3"), Some(StorageLayout::canonical_v3()));
+        assert_eq!(StorageLayout::by_name("ramses_v3"), None);
+        assert_eq!(StorageLayout::pancake_v3().v4_slots(), V4Slots::default());
+    }
+
+    #[test]
+    fn test_storage_layout_v4_pools() {
+        let layout = StorageLayout::canonical_v3().with_v4_pools(9);
+        assert_eq!(layout.v3, V3Slots::canonical_v3());
+        assert_eq!(layout.v4_slots(), V4Slots { pools: 9 });
+
+        // Layouts serialized without the V4 slot get the canonical one
+        let json = serde_json::json!({ "v3": V3Slots::pancake_v3() });
+        let layout: StorageLayout = serde_json::from_value(json).unwrap();
+        assert_eq!(layout, StorageLayout::pancake_v3());
+    }
+
     #[test]
     fn test_v4_slots() {
         let pool_id = B256::from([0x42; 32]);
@@ -257,4 +541,34 @@ mod tests {
         assert_ne!(slot0, bitmap);
         assert_ne!(tick, bitmap);
     }
+
+    #[test]
+    fn test_v4_custom_pools_slot() {
+        let pool_id = B256::from([0x42; 32]);
+        let custom = StorageLayout::default().with_v4_pools(9).v4_slots();
+        assert_eq!(StorageLayout::default().v4_slots(), V4Slots::default());
+
+        // The whole chain hashes off keccak256(abi.encode(poolId, 9))
+        let base = keccak256((pool_id, U256::from(9u8)).abi_encode());
+        assert_eq!(custom.base(pool_id), base);
+        assert_ne!(base, v4_base_slot(pool_id));
+        assert_eq!(custom.slot0(pool_id), base);
+        assert_eq!(custom.liquidity(pool_id), add_offset(base, v4::LIQUIDITY_OFFSET));
+        assert_eq!(
+            custom.fee_growth_globals(pool_id),
+            (add_offset(base, 1), add_offset(base, 2))
+        );
+
+        let ticks_mapping = U256::from_be_bytes(*base) + U256::from(v4::TICKS_OFFSET);
+        assert_eq!(
+            custom.tick(pool_id, -100),
+            keccak256((-100i32, ticks_mapping).abi_encode())
+        );
+        let bitmap_mapping = U256::from_be_bytes(*base) + U256::from(v4::TICK_BITMAP_OFFSET);
+        assert_eq!(
+            custom.bitmap(pool_id, 3),
+            keccak256((3i16, bitmap_mapping).abi_encode())
+        );
+        assert_ne!(custom.tick(pool_id, -100), v4_tick_slot(pool_id, -100));
+    }
 }
@@ -1,29 +1,112 @@
 /// Tick math utilities for UniswapV3/V4 pools
 
+use alloy_primitives::{U256, U512};
+use eyre::{eyre, Result};
+
+use crate::types;
+
 /// Minimum and maximum tick values for full range
 pub const MIN_TICK: i32 = -887272;
 pub const MAX_TICK: i32 = 887272;
 
+/// `sqrtPriceX96` at `MIN_TICK` (inclusive lower bound of a valid pool price)
+pub const MIN_SQRT_RATIO: U256 = U256::from_limbs([4295128739, 0, 0, 0]);
+/// `sqrtPriceX96` at `MAX_TICK` (exclusive upper bound of a valid pool price)
+pub const MAX_SQRT_RATIO: U256 =
+    U256::from_limbs([0x5d951d5263988d26, 0xefd1fc6a50648849, 0xfffd8963, 0]);
+
+/// 2^96, the fixed-point scale of `sqrtPriceX96`
+pub(crate) const Q96: U256 = U256::from_limbs([0, 1 << 32, 0, 0]);
+
+/// A raw tick index, as stored in slot0 and used as the tick mapping key
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Tick(pub i32);
+
+/// A tick divided by the pool's tick spacing, as indexed by the tick bitmap
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CompressedTick(pub i32);
+
+/// A tick bitmap word position (the `tickBitmap` mapping key)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct WordPos(pub i16);
+
+impl Tick {
+    /// Compress by `tick_spacing`, rounding toward negative infinity like `TickBitmap`
+    pub fn compress(self, tick_spacing: i32) -> CompressedTick {
+        CompressedTick(self.0.div_euclid(tick_spacing))
+    }
+
+    /// Bitmap word holding this tick
+    pub fn word_pos(self, tick_spacing: i32) -> WordPos {
+        self.compress(tick_spacing).word_pos()
+    }
+}
+
+impl CompressedTick {
+    /// Build from a bitmap word and bit position
+    pub fn from_position(word_pos: WordPos, bit_pos: u8) -> Self {
+        Self(((word_pos.0 as i32) << 8) | bit_pos as i32)
+    }
+
+    /// Expand back to a raw tick
+    pub fn to_tick(self, tick_spacing: i32) -> Tick {
+        Tick(self.0 * tick_spacing)
+    }
+
+    /// Bitmap word holding this tick: `compressed >> 8`
+    pub fn word_pos(self) -> WordPos {
+        WordPos((self.0 >> 8) as i16)
+    }
+
+    /// Bit within the word: `compressed % 256`
+    pub fn bit_pos(self) -> u8 {
+        self.0.rem_euclid(256) as u8
+    }
+}
+
+impl WordPos {
+    /// Word positions covering the raw ticks `[lower, upper]`
+    pub fn range(lower: Tick, upper: Tick, tick_spacing: i32) -> Vec<WordPos> {
+        (lower.word_pos(tick_spacing).0..=upper.word_pos(tick_spacing).0)
+            .map(WordPos)
+            .collect()
+    }
+}
+
 /// Calculate the word position for a given tick
 /// Formula: word_pos = (tick / tickSpacing) >> 8
 pub fn tick_to_word_pos(tick: i32, tick_spacing: i32) -> i16 {
-    let compressed = tick / tick_spacing;
-    (compressed >> 8) as i16
+    Tick(tick).word_pos(tick_spacing).0
 }
 
 /// Calculate the bit position within a word for a given tick
 /// Formula: bit_pos = (tick / tickSpacing) % 256
 pub fn tick_to_bit_pos(tick: i32, tick_spacing: i32) -> u8 {
-    let compressed = tick / tick_spacing;
-    (compressed.rem_euclid(256)) as u8
+    Tick(tick).compress(tick_spacing).bit_pos()
 }
 
 /// Generate list of all word positions that could contain initialized ticks
 /// This covers the full range from MIN_TICK to MAX_TICK
 pub fn generate_word_positions(tick_spacing: i32) -> Vec<i16> {
-    let min_word = tick_to_word_pos(MIN_TICK, tick_spacing);
-    let max_word = tick_to_word_pos(MAX_TICK, tick_spacing);
-    (min_word..=max_word).collect()
+    WordPos::range(Tick(MIN_TICK), Tick(MAX_TICK), tick_spacing)
+        .into_iter()
+        .map(|word| word.0)
+        .collect()
+}
+
+/// Word positions covering the ticks in `[tick_lower, tick_upper]`
+/// A missing bound defaults to MIN_TICK / MAX_TICK, so `(None, None)` equals `generate_word_positions`
+pub fn word_positions_in_range(
+    tick_spacing: i32,
+    tick_lower: Option<i32>,
+    tick_upper: Option<i32>,
+) -> Vec<i16> {
+    let lower = tick_lower.unwrap_or(MIN_TICK).clamp(MIN_TICK, MAX_TICK);
+    let upper = tick_upper.unwrap_or(MAX_TICK).clamp(MIN_TICK, MAX_TICK);
+    WordPos::range(Tick(lower), Tick(upper), tick_spacing)
+        .into_iter()
+        .map(|word| word.0)
+        .collect()
 }
 
 /// Extract initialized tick positions from a bitmap
@@ -39,8 +122,9 @@ pub fn extract_ticks_from_bitmap(
     for bit_pos in 0..128u8 {
         if bitmap & (1u128 << bit_pos) != 0 {
             // Reconstruct tick from word_pos and bit_pos
-            let compressed = ((word_pos as i32) << 8) | (bit_pos as i32);
-            let tick = compressed * tick_spacing;
+            let tick = CompressedTick::from_position(WordPos(word_pos), bit_pos)
+                .to_tick(tick_spacing)
+                .0;
 
             // Validate tick is in valid range
             if tick >= MIN_TICK && tick <= MAX_TICK {
@@ -70,11 +154,12 @@ pub fn extract_ticks_from_bitmap_u256(
 
         for bit_in_byte in 0..8u8 {
             if byte & (1 << bit_in_byte) != 0 {
-                let bit_pos = (byte_idx as u16 * 8) + bit_in_byte as u16;
+                let bit_pos = (byte_idx * 8) as u8 + bit_in_byte;
 
                 // Reconstruct tick
-                let compressed = ((word_pos as i32) << 8) | (bit_pos as i32);
-                let tick = compressed * tick_spacing;
+                let tick = CompressedTick::from_position(WordPos(word_pos), bit_pos)
+                    .to_tick(tick_spacing)
+                    .0;
 
                 // Validate tick range
                 if tick >= MIN_TICK && tick <= MAX_TICK {
@@ -87,6 +172,23 @@ pub fn extract_ticks_from_bitmap_u256(
     ticks
 }
 
+/// Build the bitmap word `word_pos` from a set of initialized ticks; the inverse of
+/// [`extract_ticks_from_bitmap_u256`]
+///
+/// Ticks in other words are ignored, so the full tick list of a pool can be passed
+/// for every word. Ticks that are not a multiple of `tick_spacing` can never be
+/// initialized and are ignored too.
+pub fn bitmap_from_ticks(word_pos: i16, ticks: &[i32], tick_spacing: i32) -> U256 {
+    ticks
+        .iter()
+        .filter(|tick| tick.rem_euclid(tick_spacing) == 0)
+        .map(|tick| Tick(*tick))
+        .filter(|tick| tick.word_pos(tick_spacing) == WordPos(word_pos))
+        .fold(U256::ZERO, |bitmap, tick| {
+            bitmap | (U256::from(1u8) << tick.compress(tick_spacing).bit_pos() as usize)
+        })
+}
+
 /// Calculate a focused range of word positions around a current tick
 /// Useful for querying a subset instead of all possible word positions
 pub fn word_positions_around_tick(
@@ -101,6 +203,230 @@ pub fn word_positions_around_tick(
     (min_word..=max_word).collect()
 }
 
+/// Fee tier (hundredths of a bip) for a canonical Uniswap tick spacing.
+///
+/// The V3 fee is an immutable, not a storage slot, but canonical factories pair
+/// each fee tier with a fixed tick spacing. Returns `None` for nonstandard spacings.
+pub fn fee_from_tick_spacing(tick_spacing: i32) -> Option<u32> {
+    match tick_spacing {
+        1 => Some(100),
+        10 => Some(500),
+        60 => Some(3000),
+        200 => Some(10000),
+        _ => None,
+    }
+}
+
+/// Calculate sqrt(1.0001^tick) * 2^96, matching Uniswap's `TickMath.getSqrtRatioAtTick`
+pub fn get_sqrt_ratio_at_tick(tick: i32) -> Result<U256> {
+    if !(MIN_TICK..=MAX_TICK).contains(&tick) {
+        return Err(eyre!("tick {tick} out of range"));
+    }
+
+    // Precomputed 1/sqrt(1.0001)^(2^i) in Q128.128, one per bit of |tick|
+    const FACTORS: [u128; 19] = [
+        0xfff97272373d413259a46990580e213a,
+        0xfff2e50f5f656932ef12357cf3c7fdcc,
+        0xffe5caca7e10e4e61c3624eaa0941cd0,
+        0xffcb9843d60f6159c9db58835c926644,
+        0xff973b41fa98c081472e6896dfb254c0,
+        0xff2ea16466c96a3843ec78b326b52861,
+        0xfe5dee046a99a2a811c461f1969c3053,
+        0xfcbe86c7900a88aedcffc83b479aa3a4,
+        0xf987a7253ac413176f2b074cf7815e54,
+        0xf3392b0822b70005940c7a398e4b70f3,
+        0xe7159475a2c29b7443b29c7fa6e889d9,
+        0xd097f3bdfd2022b8845ad8f792aa5825,
+        0xa9f746462d870fdf8a65dc1f90e061e5,
+        0x70d869a156d2a1b890bb3df62baf32f7,
+        0x31be135f97d08fd981231505542fcfa6,
+        0x9aa508b5b7a84e1c677de54f3e99bc9,
+        0x5d6af8dedb81196699c329225ee604,
+        0x2216e584f5fa1ea926041bedfe98,
+        0x48a170391f7dc42444e8fa2,
+    ];
+
+    let abs_tick = tick.unsigned_abs();
+
+    let mut ratio = if abs_tick & 0x1 != 0 {
+        U256::from(0xfffcb933bd6fad37aa2d162d1a594001u128)
+    } else {
+        U256::from(1u8) << 128
+    };
+    for (i, factor) in FACTORS.iter().enumerate() {
+        if abs_tick & (0x2 << i) != 0 {
+            ratio = (ratio * U256::from(*factor)) >> 128;
+        }
+    }
+
+    if tick > 0 {
+        ratio = U256::MAX / ratio;
+    }
+
+    // Q128.128 -> Q64.96, rounding up
+    let remainder: U256 = ratio % (U256::from(1u8) << 32);
+    Ok((ratio >> 32) + U256::from(!remainder.is_zero() as u8))
+}
+
+/// Greatest tick whose sqrt price is `<= sqrt_price_x96` (`TickMath.getTickAtSqrtRatio`)
+///
+/// `sqrt_price_x96` must be in `[MIN_SQRT_RATIO, MAX_SQRT_RATIO)`. Found by binary
+/// search over [`get_sqrt_ratio_at_tick`], so it agrees with it exactly.
+pub fn get_tick_at_sqrt_ratio(sqrt_price_x96: U256) -> Result<i32> {
+    if !(MIN_SQRT_RATIO..MAX_SQRT_RATIO).contains(&sqrt_price_x96) {
+        return Err(eyre!("sqrtPriceX96 {sqrt_price_x96} out of range"));
+    }
+
+    let (mut low, mut high) = (MIN_TICK, MAX_TICK);
+    while low < high {
+        let mid = low + (high - low + 1) / 2;
+        if get_sqrt_ratio_at_tick(mid)? <= sqrt_price_x96 {
+            low = mid;
+        } else {
+            high = mid - 1;
+        }
+    }
+    Ok(low)
+}
+
+/// Compute `a * b / denominator` with a 512-bit intermediate (Uniswap `FullMath.mulDiv`)
+pub(crate) fn mul_div(a: U256, b: U256, denominator: U256) -> U256 {
+    let result = U512::from(a) * U512::from(b) / U512::from(denominator);
+    U256::saturating_from(result)
+}
+
+/// Same as [`mul_div`], rounding up (`FullMath.mulDivRoundingUp`)
+pub(crate) fn mul_div_rounding_up(a: U256, b: U256, denominator: U256) -> U256 {
+    let product = U512::from(a) * U512::from(b);
+    let denominator = U512::from(denominator);
+    let rounded: U512 = (product + denominator - U512::from(1u8)) / denominator;
+    U256::saturating_from(rounded)
+}
+
+/// Amount of token0 for `liquidity` between two sqrt prices (`LiquidityAmounts.getAmount0ForLiquidity`)
+pub fn get_amount0_for_liquidity(sqrt_a: U256, sqrt_b: U256, liquidity: u128) -> U256 {
+    let (sqrt_a, sqrt_b) = (sqrt_a.min(sqrt_b), sqrt_a.max(sqrt_b));
+    if sqrt_a.is_zero() {
+        return U256::ZERO;
+    }
+    mul_div(U256::from(liquidity) << 96, sqrt_b - sqrt_a, sqrt_b) / sqrt_a
+}
+
+/// Amount of token1 for `liquidity` between two sqrt prices (`LiquidityAmounts.getAmount1ForLiquidity`)
+pub fn get_amount1_for_liquidity(sqrt_a: U256, sqrt_b: U256, liquidity: u128) -> U256 {
+    let (sqrt_a, sqrt_b) = (sqrt_a.min(sqrt_b), sqrt_a.max(sqrt_b));
+    mul_div(U256::from(liquidity), sqrt_b - sqrt_a, Q96)
+}
+
+/// Token0/token1 amounts held by `liquidity` in `[sqrt_lower, sqrt_upper]` at the current price
+///
+/// Mirrors `LiquidityAmounts.getAmountsForLiquidity`:
+/// - price below the range: all token0
+/// - price inside the range: token0 above the price, token1 below it
+/// - price above the range: all token1
+pub fn get_amounts_for_liquidity(
+    sqrt_price: U256,
+    sqrt_lower: U256,
+    sqrt_upper: U256,
+    liquidity: u128,
+) -> (U256, U256) {
+    let (sqrt_lower, sqrt_upper) = (sqrt_lower.min(sqrt_upper), sqrt_lower.max(sqrt_upper));
+
+    if sqrt_price <= sqrt_lower {
+        (get_amount0_for_liquidity(sqrt_lower, sqrt_upper, liquidity), U256::ZERO)
+    } else if sqrt_price < sqrt_upper {
+        (
+            get_amount0_for_liquidity(sqrt_price, sqrt_upper, liquidity),
+            get_amount1_for_liquidity(sqrt_lower, sqrt_price, liquidity),
+        )
+    } else {
+        (U256::ZERO, get_amount1_for_liquidity(sqrt_lower, sqrt_upper, liquidity))
+    }
+}
+
+/// Active liquidity while the pool's current tick is `query_tick`
+///
+/// Sums `liquidity_net` of every initialized tick `<= query_tick`, matching how the
+/// pool crosses ticks: at tick `T` the range starting at `T` is already active.
+/// `ticks` may be unsorted; a negative running sum (incomplete tick set) yields 0.
+pub fn active_liquidity_at(ticks: &[types::Tick], query_tick: i32) -> u128 {
+    let active = ticks
+        .iter()
+        .filter(|t| t.tick <= query_tick)
+        .fold(0i128, |acc, t| acc.saturating_add(t.liquidity_net));
+    u128::try_from(active).unwrap_or(0)
+}
+
+/// Liquidity step function as `(tick_lower, tick_upper, liquidity)` ranges
+///
+/// One range per pair of consecutive initialized ticks, covering `[tick_lower, tick_upper)`.
+/// Zero-liquidity gaps are included so the ranges are contiguous; liquidity below the
+/// first and above the last initialized tick is zero.
+pub fn liquidity_by_range(ticks: &[types::Tick]) -> Vec<(i32, i32, u128)> {
+    let mut sorted: Vec<&types::Tick> = ticks.iter().collect();
+    sorted.sort_by_key(|t| t.tick);
+
+    let mut active: i128 = 0;
+    sorted
+        .windows(2)
+        .map(|pair| {
+            active = active.saturating_add(pair[0].liquidity_net);
+            (pair[0].tick, pair[1].tick, u128::try_from(active).unwrap_or(0))
+        })
+        .collect()
+}
+
+/// Nearest initialized tick to `from_tick` in the swap direction
+///
+/// With `lte` (price moving down) returns the highest initialized tick `<= from_tick`;
+/// otherwise the lowest initialized tick `> from_tick`, matching
+/// `TickBitmap.nextInitializedTickWithinOneWord` without the one-word limit. A tick
+/// counts as initialized when its `liquidity_gross` is non-zero, so entries collected
+/// with `include_empty` are skipped. `ticks` may be unsorted.
+pub fn nearest_initialized_tick(ticks: &[types::Tick], from_tick: i32, lte: bool) -> Option<i32> {
+    let initialized = ticks.iter().filter(|t| t.liquidity_gross > 0).map(|t| t.tick);
+    if lte {
+        initialized.filter(|&tick| tick <= from_tick).max()
+    } else {
+        initialized.filter(|&tick| tick > from_tick).min()
+    }
+}
+
+/// Fee growth per unit of liquidity inside `[lower, upper]` (`Tick.getFeeGrowthInside`)
+///
+/// `global0`/`global1` are the pool's `feeGrowthGlobal{0,1}X128`. Values are
+/// accumulators that may overflow, so all arithmetic wraps mod 2^256 like the
+/// contract; only differences between two readings are meaningful.
+pub fn fee_growth_inside(
+    global0: U256,
+    global1: U256,
+    lower: &types::Tick,
+    upper: &types::Tick,
+    current_tick: i32,
+) -> (U256, U256) {
+    let (below0, below1) = if current_tick >= lower.tick {
+        (lower.fee_growth_outside_0_x128, lower.fee_growth_outside_1_x128)
+    } else {
+        (
+            global0.wrapping_sub(lower.fee_growth_outside_0_x128),
+            global1.wrapping_sub(lower.fee_growth_outside_1_x128),
+        )
+    };
+    let (above0, above1) = if current_tick < upper.tick {
+        (upper.fee_growth_outside_0_x128, upper.fee_growth_outside_1_x128)
+    } else {
+        (
+            global0.wrapping_sub(upper.fee_growth_outside_0_x128),
+            global1.wrapping_sub(upper.fee_growth_outside_1_x128),
+        )
+    };
+
+    (
+        global0.wrapping_sub(below0).wrapping_sub(above0),
+        global1.wrapping_sub(below1).wrapping_sub(above1),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -125,6 +451,26 @@ mod tests {
         assert_eq!(tick_to_bit_pos(15360, 60), 0);
     }
 
+    #[test]
+    fn test_newtype_conversions() {
+        // Off-spacing negative ticks round down, matching TickBitmap.position
+        assert_eq!(Tick(-1).compress(60), CompressedTick(-1));
+        assert_eq!(Tick(-60).compress(60), CompressedTick(-1));
+        assert_eq!(Tick(-61).compress(60), CompressedTick(-2));
+        assert_eq!(CompressedTick(-1).word_pos(), WordPos(-1));
+        assert_eq!(CompressedTick(-1).bit_pos(), 255);
+
+        let compressed = Tick(-887220).compress(60);
+        let rebuilt = CompressedTick::from_position(compressed.word_pos(), compressed.bit_pos());
+        assert_eq!(rebuilt, compressed);
+        assert_eq!(rebuilt.to_tick(60), Tick(-887220));
+
+        assert_eq!(
+            WordPos::range(Tick(-20000), Tick(20000), 60),
+            vec![WordPos(-2), WordPos(-1), WordPos(0), WordPos(1)]
+        );
+    }
+
     #[test]
     fn test_generate_word_positions() {
         let positions = generate_word_positions(60);
@@ -144,6 +490,107 @@ mod tests {
         assert_eq!(ticks[1], 300); // bit 5 => compressed 5 => tick 300
     }
 
+    #[test]
+    fn test_extract_ticks_across_u128_boundary() {
+        // Bits 127 and 128 straddle the two big-endian halves of the 32-byte word
+        let bitmap: U256 = (U256::from(1u8) << 127) | (U256::from(1u8) << 128);
+        let ticks = extract_ticks_from_bitmap_u256(0, &bitmap.to_be_bytes(), 60);
+        assert_eq!(ticks, vec![127 * 60, 128 * 60]);
+
+        // The u128 version only sees bit 127; both agree on it
+        let ticks = extract_ticks_from_bitmap(-1, 1u128 << 127, 60);
+        assert_eq!(ticks, vec![(-256 + 127) * 60]);
+        assert_eq!(
+            ticks,
+            extract_ticks_from_bitmap_u256(-1, &U256::from(1u128 << 127).to_be_bytes(), 60)
+        );
+    }
+
+    #[test]
+    fn test_bitmap_from_ticks_round_trip() {
+        let ticks = [-887_220, -120, -60, 0, 300, 15_300, 887_220];
+        for word_pos in [-58, -1, 0, 57] {
+            let bitmap = bitmap_from_ticks(word_pos, &ticks, 60);
+            let extracted = extract_ticks_from_bitmap_u256(word_pos, &bitmap.to_be_bytes(), 60);
+            let expected: Vec<i32> = ticks
+                .iter()
+                .copied()
+                .filter(|tick| tick_to_word_pos(*tick, 60) == word_pos)
+                .collect();
+            assert_eq!(extracted, expected, "word {word_pos}");
+        }
+
+        // Word 0 holds ticks 0, 300 and 15300 (bits 0, 5 and 255); -60 is bit 255 of word -1
+        assert_eq!(
+            bitmap_from_ticks(0, &ticks, 60),
+            U256::from(0b100001u8) | (U256::from(1u8) << 255)
+        );
+        assert_eq!(bitmap_from_ticks(-1, &[-60], 60), U256::from(1u8) << 255);
+        // Off-spacing ticks are never initialized
+        assert_eq!(bitmap_from_ticks(0, &[30], 60), U256::ZERO);
+    }
+
+    #[test]
+    fn test_fee_from_tick_spacing() {
+        assert_eq!(fee_from_tick_spacing(1), Some(100));
+        assert_eq!(fee_from_tick_spacing(10), Some(500));
+        assert_eq!(fee_from_tick_spacing(60), Some(3000));
+        assert_eq!(fee_from_tick_spacing(200), Some(10000));
+        assert_eq!(fee_from_tick_spacing(50), None);
+    }
+
+    #[test]
+    fn test_get_sqrt_ratio_at_tick() {
+        assert_eq!(get_sqrt_ratio_at_tick(0).unwrap(), Q96);
+        assert_eq!(get_sqrt_ratio_at_tick(MIN_TICK).unwrap(), MIN_SQRT_RATIO);
+        assert_eq!(get_sqrt_ratio_at_tick(MAX_TICK).unwrap(), MAX_SQRT_RATIO);
+        assert_eq!(
+            MAX_SQRT_RATIO,
+            "1461446703485210103287273052203988822378723970342"
+                .parse::<U256>()
+                .unwrap()
+        );
+        assert!(get_sqrt_ratio_at_tick(MAX_TICK + 1).is_err());
+    }
+
+    #[test]
+    fn test_get_tick_at_sqrt_ratio() {
+        assert_eq!(get_tick_at_sqrt_ratio(Q96).unwrap(), 0);
+        assert_eq!(get_tick_at_sqrt_ratio(Q96 - U256::from(1u8)).unwrap(), -1);
+        assert_eq!(get_tick_at_sqrt_ratio(MIN_SQRT_RATIO).unwrap(), MIN_TICK);
+        assert_eq!(get_tick_at_sqrt_ratio(MAX_SQRT_RATIO - U256::from(1u8)).unwrap(), MAX_TICK - 1);
+        for tick in [-887271, -200_000, -61, 59, 123_456] {
+            let sqrt_price = get_sqrt_ratio_at_tick(tick).unwrap();
+            assert_eq!(get_tick_at_sqrt_ratio(sqrt_price).unwrap(), tick);
+            assert_eq!(get_tick_at_sqrt_ratio(sqrt_price + U256::from(1u8)).unwrap(), tick);
+        }
+        assert!(get_tick_at_sqrt_ratio(MAX_SQRT_RATIO).is_err());
+    }
+
+    #[test]
+    fn test_get_amounts_for_liquidity() {
+        let lower = get_sqrt_ratio_at_tick(-60).unwrap();
+        let upper = get_sqrt_ratio_at_tick(60).unwrap();
+        let liquidity = 1_000_000_000_000_000_000u128;
+
+        // Below the range: only token0
+        let below = get_sqrt_ratio_at_tick(-120).unwrap();
+        let (a0, a1) = get_amounts_for_liquidity(below, lower, upper, liquidity);
+        assert!(a0 > U256::ZERO);
+        assert_eq!(a1, U256::ZERO);
+
+        // Above the range: only token1
+        let above = get_sqrt_ratio_at_tick(120).unwrap();
+        let (a0, a1) = get_amounts_for_liquidity(above, lower, upper, liquidity);
+        assert_eq!(a0, U256::ZERO);
+        assert!(a1 > U256::ZERO);
+
+        // At tick 0 in a symmetric range, both sides hold roughly equal amounts
+        let (a0, a1) = get_amounts_for_liquidity(Q96, lower, upper, liquidity);
+        let diff = if a0 > a1 { a0 - a1 } else { a1 - a0 };
+        assert!(diff < a0 / U256::from(1000));
+    }
+
     #[test]
     fn test_word_positions_around_tick() {
         let positions = word_positions_around_tick(0, 60, 5);
@@ -152,4 +599,128 @@ mod tests {
         assert!(positions.contains(&5));
         assert_eq!(positions.len(), 11); // -5 to +5 inclusive
     }
+
+    #[test]
+    fn test_active_liquidity_at() {
+        let tick = |tick, liquidity_net| types::Tick { tick, liquidity_net, ..Default::default() };
+        // Positions: [-120, 60) with 100 and [0, 120) with 50, given unsorted
+        let ticks = vec![tick(60, -100), tick(-120, 100), tick(120, -50), tick(0, 50)];
+
+        assert_eq!(active_liquidity_at(&ticks, -121), 0);
+        assert_eq!(active_liquidity_at(&ticks, -120), 100);
+        assert_eq!(active_liquidity_at(&ticks, -1), 100);
+        assert_eq!(active_liquidity_at(&ticks, 0), 150);
+        assert_eq!(active_liquidity_at(&ticks, 59), 150);
+        assert_eq!(active_liquidity_at(&ticks, 60), 50);
+        assert_eq!(active_liquidity_at(&ticks, 120), 0);
+
+        assert_eq!(
+            liquidity_by_range(&ticks),
+            vec![(-120, 0, 100), (0, 60, 150), (60, 120, 50)]
+        );
+        assert!(liquidity_by_range(&ticks[..1]).is_empty());
+    }
+
+    #[test]
+    fn test_nearest_initialized_tick() {
+        let tick = |tick, liquidity_gross| types::Tick { tick, liquidity_gross, ..Default::default() };
+        let ticks = vec![tick(60, 10), tick(-120, 10), tick(0, 0), tick(120, 5)];
+
+        assert_eq!(nearest_initialized_tick(&ticks, 60, true), Some(60));
+        assert_eq!(nearest_initialized_tick(&ticks, 59, true), Some(-120));
+        assert_eq!(nearest_initialized_tick(&ticks, -121, true), None);
+        assert_eq!(nearest_initialized_tick(&ticks, 60, false), Some(120));
+        assert_eq!(nearest_initialized_tick(&ticks, -200, false), Some(-120));
+        assert_eq!(nearest_initialized_tick(&ticks, 120, false), None);
+    }
+
+    #[test]
+    fn test_fee_growth_inside() {
+        let tick = |tick, outside0: u64, outside1: u64| types::Tick {
+            tick,
+            fee_growth_outside_0_x128: U256::from(outside0),
+            fee_growth_outside_1_x128: U256::from(outside1),
+            ..Default::default()
+        };
+        let lower = tick(-60, 10, 20);
+        let upper = tick(60, 5, 7);
+        let (g0, g1) = (U256::from(100u64), U256::from(200u64));
+
+        // In range: global - below(lower.outside) - above(upper.outside)
+        assert_eq!(
+            fee_growth_inside(g0, g1, &lower, &upper, 0),
+            (U256::from(85u64), U256::from(173u64))
+        );
+        // Below the range: lower.outside - upper.outside
+        assert_eq!(
+            fee_growth_inside(g0, g1, &lower, &upper, -100),
+            (U256::from(5u64), U256::from(13u64))
+        );
+        // Above the range: upper.outside - lower.outside, which wraps here
+        assert_eq!(
+            fee_growth_inside(g0, g1, &lower, &upper, 60),
+            (U256::MAX - U256::from(4u64), U256::MAX - U256::from(12u64))
+        );
+    }
+
+    #[test]
+    fn test_word_positions_in_range() {
+        assert_eq!(word_positions_in_range(60, None, None), generate_word_positions(60));
+
+        // ticks [-20000, 20000] at spacing 60 => compressed [-333, 333] => words [-2, 1]
+        assert_eq!(word_positions_in_range(60, Some(-20000), Some(20000)), vec![-2, -1, 0, 1]);
+        assert_eq!(word_positions_in_range(60, Some(0), None).first(), Some(&0));
+        assert!(word_positions_in_range(60, Some(100), Some(-100)).is_empty());
+    }
+
+    mod proptests {
+        use super::*;
+        use proptest::{collection::btree_set, prelude::*};
+
+        // Word range keeps `(word_pos << 8) * tick_spacing` within i32 while still
+        // reaching past MIN_TICK/MAX_TICK for the larger spacings
+        fn word_pos() -> impl Strategy<Value = i16> {
+            -3500i16..3500
+        }
+
+        fn tick_spacing() -> impl Strategy<Value = i32> {
+            1i32..=200
+        }
+
+        proptest! {
+            #[test]
+            fn u128_and_u256_extraction_agree(
+                word_pos in word_pos(),
+                bitmap in any::<u128>(),
+                tick_spacing in tick_spacing(),
+            ) {
+                let bytes = U256::from(bitmap).to_be_bytes::<32>();
+                prop_assert_eq!(
+                    extract_ticks_from_bitmap(word_pos, bitmap, tick_spacing),
+                    extract_ticks_from_bitmap_u256(word_pos, &bytes, tick_spacing)
+                );
+            }
+
+            #[test]
+            fn u256_extraction_returns_set_bits(
+                word_pos in word_pos(),
+                bits in btree_set(any::<u8>(), 0..32),
+                tick_spacing in tick_spacing(),
+            ) {
+                let bitmap = bits
+                    .iter()
+                    .fold(U256::ZERO, |acc, bit| acc | (U256::from(1u8) << *bit));
+                let expected: Vec<i32> = bits
+                    .iter()
+                    .map(|bit| ((word_pos as i32) * 256 + *bit as i32) * tick_spacing)
+                    .filter(|tick| (MIN_TICK..=MAX_TICK).contains(tick))
+                    .collect();
+
+                prop_assert_eq!(
+                    extract_ticks_from_bitmap_u256(word_pos, &bitmap.to_be_bytes(), tick_spacing),
+                    expected
+                );
+            }
+        }
+    }
 }
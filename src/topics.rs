@@ -0,0 +1,62 @@
+/// Topic0 hashes of the pool events this crate scans for
+///
+/// Usable directly as `scan_events` topic filters or `scan_events_any` topic sets.
+use alloy_primitives::{b256, B256};
+
+/// `Swap(address,uint256,uint256,uint256,uint256,address)` (Uniswap V2 pair)
+pub const SWAP_V2: B256 =
+    b256!("d78ad95fa46c994b6551d0da85fc275fe613ce37657fb8d5e3d130840159d822");
+
+/// `Sync(uint112,uint112)` (Uniswap V2 pair)
+pub const SYNC_V2: B256 =
+    b256!("1c411e9a96e071241c2f21f7726b17ae89e3cab4c78be50e062b03a9fffbbad1");
+
+/// `Swap(address,address,int256,int256,uint160,uint128,int24)` (Uniswap V3 pool)
+pub const SWAP_V3: B256 =
+    b256!("c42079f94a6350d7e6235f29174924f928cc2ac818eb64fed8004e115fbcca67");
+
+/// `Mint(address,address,int24,int24,uint128,uint256,uint256)` (Uniswap V3 pool)
+pub const MINT_V3: B256 =
+    b256!("7a53080ba414158be7ec69b987b5fb7d07dee101fe85488f0853ae16239d0bde");
+
+/// `Burn(address,int24,int24,uint128,uint256,uint256)` (Uniswap V3 pool)
+pub const BURN_V3: B256 =
+    b256!("0c396cd989a39f4459b5fa1aed6a9a8dcdbc45908acfd67e028cd568da98982c");
+
+/// `Swap(bytes32,address,int128,int128,uint160,uint128,int24,uint24)` (V4 PoolManager)
+pub const SWAP_V4: B256 =
+    b256!("40e9cecb9f5f1f1c5b9c97dec2917b7ee92e57ba5563708daca94dd84ad7112f");
+
+/// `ModifyLiquidity(bytes32,address,int24,int24,int256,bytes32)` (V4 PoolManager)
+pub const MODIFY_LIQUIDITY_V4: B256 =
+    b256!("f208f4912782fd25c7f114ca3723a2d5dd6f3bcc3ac8db5af63baa85f711d5ec");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contracts::{IPoolManagerEvents, IUniswapV2PairEvents, IUniswapV3PoolEvents};
+    use alloy_primitives::keccak256;
+    use alloy_sol_types::SolEvent;
+
+    #[test]
+    fn test_topics_match_signatures() {
+        let cases = [
+            (SWAP_V2, "Swap(address,uint256,uint256,uint256,uint256,address)"),
+            (SYNC_V2, "Sync(uint112,uint112)"),
+            (SWAP_V3, "Swap(address,address,int256,int256,uint160,uint128,int24)"),
+            (MINT_V3, "Mint(address,address,int24,int24,uint128,uint256,uint256)"),
+            (BURN_V3, "Burn(address,int24,int24,uint128,uint256,uint256)"),
+            (SWAP_V4, "Swap(bytes32,address,int128,int128,uint160,uint128,int24,uint24)"),
+            (MODIFY_LIQUIDITY_V4, "ModifyLiquidity(bytes32,address,int24,int24,int256,bytes32)"),
+        ];
+        for (topic, signature) in cases {
+            assert_eq!(topic, keccak256(signature), "{signature}");
+        }
+
+        assert_eq!(SWAP_V2, IUniswapV2PairEvents::Swap::SIGNATURE_HASH);
+        assert_eq!(SYNC_V2, IUniswapV2PairEvents::Sync::SIGNATURE_HASH);
+        assert_eq!(SWAP_V3, IUniswapV3PoolEvents::Swap::SIGNATURE_HASH);
+        assert_eq!(SWAP_V4, IPoolManagerEvents::Swap::SIGNATURE_HASH);
+        assert_eq!(MODIFY_LIQUIDITY_V4, IPoolManagerEvents::ModifyLiquidity::SIGNATURE_HASH);
+    }
+}
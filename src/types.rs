@@ -1,11 +1,13 @@
-use alloy_primitives::{Address, B256, U256};
+use alloy_primitives::{Address, Keccak256, B256, U256};
 use serde::{Deserialize, Serialize};
 
+use crate::storage::{v3_slots_for_factory, StorageLayout, V2ReserveLayout, V3Slots, V4Slots};
+
 /// `BlockNumber` is just u64 in Reth.
 pub type BlockNumber = u64;
 
 /// Pool protocol type.
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Protocol {
     #[serde(alias = "v2", alias = "V2", alias = "uniswapv2")]
@@ -17,7 +19,7 @@ pub enum Protocol {
 }
 
 /// Input configuration for a single pool.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct PoolInput {
     pub address: Address,
     pub protocol: Protocol,
@@ -27,15 +29,76 @@ pub struct PoolInput {
     /// PancakeSwap V3 has different storage slots than Uniswap/SushiSwap V3.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub factory: Option<Address>,
+    /// V4 pool ID. When set, it takes precedence over the positional `v4_pool_ids`
+    /// argument of the collectors, so V4 inputs are self-describing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pool_id: Option<B256>,
+    /// Keep zero-valued bitmap words in the output, so every queried word position
+    /// is present (useful for diffing snapshots). Ticks are still only read for
+    /// non-zero words.
+    #[serde(default)]
+    pub include_empty: bool,
+    /// Lowest tick of interest (V3/V4). Limits the scanned bitmap words and drops
+    /// initialized ticks below it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tick_lower: Option<i32>,
+    /// Highest tick of interest (V3/V4). Limits the scanned bitmap words and drops
+    /// initialized ticks above it.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tick_upper: Option<i32>,
+    /// Reject a slot0 whose `sqrtPriceX96` or tick is outside the valid range with
+    /// `ScrapeError::ImplausibleSlot0` (V3/V4). Catches reads from the wrong address
+    /// or layout; note an uninitialized pool (all-zero slot0) also fails.
+    #[serde(default)]
+    pub validate_slot0: bool,
+    /// Storage layout of a fork: its V3 slots and the V4 PoolManager's `_pools` slot.
+    /// Takes precedence over the V3 layout inferred from `factory`; `None` falls back
+    /// to that inference and the canonical `_pools` slot.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub layout: Option<StorageLayout>,
+    /// Reserve slots of a V2 fork. `None` means Uniswap V2's packed slot 8.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub v2_reserves: Option<V2ReserveLayout>,
+    /// Drop initialized ticks whose `liquidity_gross` is below this threshold from
+    /// the output (V3/V4). Bitmaps are unaffected and the tick-spacing check still
+    /// sees every tick, so this only prunes dust positions from `PoolOutput.ticks`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub min_liquidity_gross: Option<u128>,
+    /// Fee in hundredths of a bip, reported as `PoolOutput.fee`. V3 falls back to the
+    /// fee implied by `tick_spacing`; a V4 fee is only reported when set here (it comes
+    /// from the PoolKey) and never for dynamic-fee pools.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<u32>,
 }
 
-impl PoolInput {
-    pub fn new_v2(address: Address) -> Self {
+/// A V2 pool at the zero address with every option off. Meant for struct-update
+/// syntax (`PoolInput { address, protocol, ..Default::default() }`), so new options
+/// don't break existing literals.
+impl Default for PoolInput {
+    fn default() -> Self {
         Self {
-            address,
+            address: Address::ZERO,
             protocol: Protocol::UniswapV2,
             tick_spacing: None,
             factory: None,
+            pool_id: None,
+            include_empty: false,
+            tick_lower: None,
+            tick_upper: None,
+            validate_slot0: false,
+            layout: None,
+            v2_reserves: None,
+            min_liquidity_gross: None,
+            fee: None,
+        }
+    }
+}
+
+impl PoolInput {
+    pub fn new_v2(address: Address) -> Self {
+        Self {
+            address,
+            ..Default::default()
         }
     }
 
@@ -44,17 +107,15 @@ impl PoolInput {
             address,
             protocol: Protocol::UniswapV3,
             tick_spacing: Some(tick_spacing),
-            factory: None,
+            ..Default::default()
         }
     }
 
     /// Create a V3 pool input with factory address for correct storage layout.
     pub fn new_v3_with_factory(address: Address, tick_spacing: i32, factory: Address) -> Self {
         Self {
-            address,
-            protocol: Protocol::UniswapV3,
-            tick_spacing: Some(tick_spacing),
             factory: Some(factory),
+            ..Self::new_v3(address, tick_spacing)
         }
     }
 
@@ -63,17 +124,156 @@ impl PoolInput {
             address,
             protocol: Protocol::UniswapV4,
             tick_spacing: Some(tick_spacing),
-            factory: None,
+            ..Default::default()
+        }
+    }
+
+    /// V4 pool carrying its own pool ID (no positional `v4_pool_ids` needed).
+    pub fn new_v4_with_id(address: Address, tick_spacing: i32, pool_id: B256) -> Self {
+        Self {
+            pool_id: Some(pool_id),
+            ..Self::new_v4(address, tick_spacing)
+        }
+    }
+
+    /// V4 pool identified by its [`PoolKey`]; the pool ID is derived from the key and
+    /// the key's fee is carried along.
+    pub fn new_v4_from_key(pool_manager: Address, key: &PoolKey) -> Self {
+        Self {
+            fee: Some(key.fee),
+            ..Self::new_v4_with_id(pool_manager, key.tick_spacing, key.pool_id())
+        }
+    }
+
+    /// Use a non-default `_pools` mapping slot for this V4 pool (sets it in `layout`).
+    pub fn with_v4_pools_slot(mut self, pools_slot: u8) -> Self {
+        self.layout = Some(self.storage_layout().with_v4_pools(pools_slot));
+        self
+    }
+
+    /// Only scan and return ticks within `[tick_lower, tick_upper]`.
+    pub fn with_tick_range(mut self, tick_lower: Option<i32>, tick_upper: Option<i32>) -> Self {
+        self.tick_lower = tick_lower;
+        self.tick_upper = tick_upper;
+        self
+    }
+
+    /// Whether `tick` lies within the configured `[tick_lower, tick_upper]` bounds.
+    pub fn tick_in_range(&self, tick: i32) -> bool {
+        self.tick_lower.is_none_or(|lower| tick >= lower)
+            && self.tick_upper.is_none_or(|upper| tick <= upper)
+    }
+
+    /// Reject implausible slot0 values (see `validate_slot0`).
+    pub fn with_validate_slot0(mut self, validate_slot0: bool) -> Self {
+        self.validate_slot0 = validate_slot0;
+        self
+    }
+
+    /// Read this pool with an explicit storage layout.
+    pub fn with_layout(mut self, layout: StorageLayout) -> Self {
+        self.layout = Some(layout);
+        self
+    }
+
+    /// Storage layout for this pool: `layout` if set, otherwise inferred from `factory`.
+    pub fn storage_layout(&self) -> StorageLayout {
+        self.layout
+            .unwrap_or_else(|| StorageLayout::from_v3(v3_slots_for_factory(self.factory)))
+    }
+
+    /// V3 storage slots for this pool (see [`PoolInput::storage_layout`]).
+    pub fn v3_slots(&self) -> V3Slots {
+        self.storage_layout().v3
+    }
+
+    /// V4 storage slots for this pool (see [`PoolInput::storage_layout`]).
+    pub fn v4_slots(&self) -> V4Slots {
+        self.storage_layout().v4_slots()
+    }
+
+    /// Read this V2 pair's reserves from a non-standard layout.
+    pub fn with_v2_reserves(mut self, layout: V2ReserveLayout) -> Self {
+        self.v2_reserves = Some(layout);
+        self
+    }
+
+    /// V2 reserve layout for this pool: `v2_reserves` if set, otherwise Uniswap V2's.
+    pub fn v2_reserve_layout(&self) -> V2ReserveLayout {
+        self.v2_reserves.unwrap_or_default()
+    }
+
+    /// Only return ticks with `liquidity_gross >= min_liquidity_gross`.
+    pub fn with_min_liquidity_gross(mut self, min_liquidity_gross: u128) -> Self {
+        self.min_liquidity_gross = Some(min_liquidity_gross);
+        self
+    }
+
+    /// Whether `tick` meets the configured `min_liquidity_gross` threshold.
+    pub fn keeps_tick(&self, tick: &Tick) -> bool {
+        self.min_liquidity_gross.is_none_or(|min| tick.liquidity_gross >= min)
+    }
+
+    /// Keep zero-valued bitmap words in the output.
+    pub fn with_include_empty(mut self, include_empty: bool) -> Self {
+        self.include_empty = include_empty;
+        self
+    }
+
+    /// Set the pool's fee in hundredths of a bip (see `fee`).
+    pub fn with_fee(mut self, fee: u32) -> Self {
+        self.fee = Some(fee);
+        self
+    }
+
+    /// Fee to report in `PoolOutput.fee`: V3 infers it from the tick spacing when
+    /// unset, V4 only reports a known static fee, V2 has none.
+    pub fn output_fee(&self) -> Option<u32> {
+        match self.protocol {
+            Protocol::UniswapV2 => None,
+            Protocol::UniswapV3 => self
+                .fee
+                .or_else(|| self.tick_spacing.and_then(crate::tick_math::fee_from_tick_spacing)),
+            Protocol::UniswapV4 => self.fee.filter(|fee| *fee != PoolKey::DYNAMIC_FEE_FLAG),
         }
     }
 }
 
+/// A V4 `PoolKey`, which identifies a pool in the PoolManager.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct PoolKey {
+    /// Lower-address currency (`Address::ZERO` for native ETH).
+    pub currency0: Address,
+    pub currency1: Address,
+    /// Fee in hundredths of a bip, or the dynamic-fee flag `0x800000`.
+    pub fee: u32,
+    pub tick_spacing: i32,
+    pub hooks: Address,
+}
+
+impl PoolKey {
+    /// `fee` value marking a pool whose fee is set by its hooks.
+    pub const DYNAMIC_FEE_FLAG: u32 = 0x800000;
+
+    /// `keccak256(abi.encode(key))`; see [`crate::storage::v4_pool_id`].
+    pub fn pool_id(&self) -> B256 {
+        crate::storage::v4_pool_id(
+            self.currency0,
+            self.currency1,
+            self.fee,
+            self.tick_spacing,
+            self.hooks,
+        )
+    }
+}
+
 /// UniswapV3/V4 Slot0 data.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Slot0 {
     /// Raw storage value as hex string for debugging.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub raw_data: Option<String>,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
     pub sqrt_price_x96: U256,
     pub tick: i32,
     pub observation_index: u16,
@@ -83,8 +283,36 @@ pub struct Slot0 {
     pub unlocked: bool,
 }
 
+/// A V3 oracle observation (`observations[index]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Observation {
+    pub index: u16,
+    /// Raw storage value as hex string for debugging.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw_data: Option<String>,
+    pub block_timestamp: u32,
+    pub tick_cumulative: i64,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
+    pub seconds_per_liquidity_cumulative_x128: U256,
+    pub initialized: bool,
+}
+
+/// A V3 liquidity position (`positions[key]`).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Position {
+    /// `keccak256(abi.encodePacked(owner, tickLower, tickUpper))`.
+    pub key: B256,
+    pub liquidity: u128,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
+    pub fee_growth_inside_0_last_x128: U256,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
+    pub fee_growth_inside_1_last_x128: U256,
+    pub tokens_owed_0: u128,
+    pub tokens_owed_1: u128,
+}
+
 /// Tick data for V3/V4 pools.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Tick {
     pub tick: i32,
     /// Raw storage value as hex string for debugging.
@@ -92,23 +320,31 @@ pub struct Tick {
     pub raw_data: Option<String>,
     pub liquidity_gross: u128,
     pub liquidity_net: i128,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
     pub fee_growth_outside_0_x128: U256,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
     pub fee_growth_outside_1_x128: U256,
     pub tick_cumulative_outside: i64,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
     pub seconds_per_liquidity_outside_x128: U256,
     pub seconds_outside: u32,
     pub initialized: bool,
+    /// Every `(slot, value)` read for this tick, in slot order. Only filled by the
+    /// full-tick readers (see [`crate::readers::read_tick_full`]); empty otherwise.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub raw_slots: Vec<(B256, U256)>,
 }
 
 /// Bitmap data for a word position.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Bitmap {
     pub word_pos: i16,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
     pub bitmap: U256,
 }
 
 /// `UniswapV2` reserve data.
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Reserves {
     /// Raw storage value as hex string for debugging.
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -118,6 +354,46 @@ pub struct Reserves {
     pub block_timestamp_last: u32,
 }
 
+/// `UniswapV2` oracle accumulators, enough to compute a TWAP without RPC.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct V2Oracle {
+    /// UQ112x112 price of token0 in token1, summed per second (wraps on overflow).
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
+    pub price0_cumulative_last: U256,
+    /// UQ112x112 price of token1 in token0, summed per second (wraps on overflow).
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
+    pub price1_cumulative_last: U256,
+    /// `reserve0 * reserve1` as of the last liquidity event (zero when the protocol fee is off).
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
+    pub k_last: U256,
+    /// Timestamp the accumulators were last updated (from the reserves slot).
+    pub block_timestamp_last: u32,
+}
+
+/// Account record of a contract (nonce, ETH balance, code hash), e.g. a V2 pair or
+/// a V3 pool.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccountInfo {
+    pub nonce: u64,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
+    pub balance: U256,
+    /// `None` for accounts without code.
+    pub bytecode_hash: Option<B256>,
+}
+
+/// Token amounts locked in one initialized-tick range at the current price.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TickRangeAmounts {
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+    /// Active liquidity within `[tick_lower, tick_upper)`.
+    pub liquidity: u128,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
+    pub amount0: U256,
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
+    pub amount1: U256,
+}
+
 /// Complete output data for a single pool.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoolOutput {
@@ -133,12 +409,27 @@ pub struct PoolOutput {
     /// Liquidity (only for V3/V4 pools).
     #[serde(skip_serializing_if = "Option::is_none")]
     pub liquidity: Option<u128>,
-    /// Tick data (only for V3/V4 pools).
+    /// The liquidity slot had bits set above bit 128; `liquidity` holds only the
+    /// lower 128 bits (suggests a wrong slot or a nonstandard packing).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub liquidity_upper_bits_set: bool,
+    /// Fee tier in hundredths of a bip (see [`PoolInput::output_fee`]): V3 falls back
+    /// to the canonical tick spacing, V4 needs a known static fee; `None` otherwise.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fee: Option<u32>,
+    /// Tick data (only for V3/V4 pools), sorted ascending by `tick`.
     pub ticks: Vec<Tick>,
-    /// Bitmap data (only for V3/V4 pools).
+    /// Bitmap data (only for V3/V4 pools), sorted ascending by `word_pos`.
     pub bitmaps: Vec<Bitmap>,
 }
 
+/// Put ticks and bitmaps in the documented output order, so snapshots of the same
+/// state serialize identically.
+fn sort_ticks_and_bitmaps(ticks: &mut [Tick], bitmaps: &mut [Bitmap]) {
+    ticks.sort_by_key(|t| t.tick);
+    bitmaps.sort_by_key(|b| b.word_pos);
+}
+
 impl PoolOutput {
     pub fn new_v2(address: Address, reserves: Reserves) -> Self {
         Self {
@@ -148,6 +439,8 @@ impl PoolOutput {
             reserves: Some(reserves),
             slot0: None,
             liquidity: None,
+            liquidity_upper_bits_set: false,
+            fee: None,
             ticks: Vec::new(),
             bitmaps: Vec::new(),
         }
@@ -157,9 +450,10 @@ impl PoolOutput {
         address: Address,
         slot0: Slot0,
         liquidity: u128,
-        ticks: Vec<Tick>,
-        bitmaps: Vec<Bitmap>,
+        mut ticks: Vec<Tick>,
+        mut bitmaps: Vec<Bitmap>,
     ) -> Self {
+        sort_ticks_and_bitmaps(&mut ticks, &mut bitmaps);
         Self {
             address,
             protocol: Protocol::UniswapV3,
@@ -167,6 +461,8 @@ impl PoolOutput {
             reserves: None,
             slot0: Some(slot0),
             liquidity: Some(liquidity),
+            liquidity_upper_bits_set: false,
+            fee: None,
             ticks,
             bitmaps,
         }
@@ -177,9 +473,10 @@ impl PoolOutput {
         pool_id: B256,
         slot0: Slot0,
         liquidity: u128,
-        ticks: Vec<Tick>,
-        bitmaps: Vec<Bitmap>,
+        mut ticks: Vec<Tick>,
+        mut bitmaps: Vec<Bitmap>,
     ) -> Self {
+        sort_ticks_and_bitmaps(&mut ticks, &mut bitmaps);
         Self {
             address,
             protocol: Protocol::UniswapV4,
@@ -187,10 +484,162 @@ impl PoolOutput {
             reserves: None,
             slot0: Some(slot0),
             liquidity: Some(liquidity),
+            liquidity_upper_bits_set: false,
+            fee: None,
             ticks,
             bitmaps,
         }
     }
+
+    /// Record that the liquidity slot had upper bits set.
+    pub fn with_liquidity_upper_bits_set(mut self, upper_bits_set: bool) -> Self {
+        self.liquidity_upper_bits_set = upper_bits_set;
+        self
+    }
+
+    /// Set the fee tier.
+    pub fn with_fee(mut self, fee: Option<u32>) -> Self {
+        self.fee = fee;
+        self
+    }
+
+    /// Bytes this output owns on the heap (tick/bitmap vectors, raw hex strings and
+    /// raw slots), excluding `size_of::<PoolOutput>()` itself.
+    pub fn heap_size(&self) -> usize {
+        let raw_len = |raw: &Option<String>| raw.as_ref().map_or(0, String::capacity);
+
+        self.ticks.capacity() * std::mem::size_of::<Tick>()
+            + self.ticks.iter().map(|t| raw_len(&t.raw_data)).sum::<usize>()
+            + self
+                .ticks
+                .iter()
+                .map(|t| t.raw_slots.capacity() * std::mem::size_of::<(B256, U256)>())
+                .sum::<usize>()
+            + self.bitmaps.capacity() * std::mem::size_of::<Bitmap>()
+            + self.slot0.as_ref().map_or(0, |s| raw_len(&s.raw_data))
+            + self.reserves.as_ref().map_or(0, |r| raw_len(&r.raw_data))
+    }
+
+    /// Keccak-256 of the decoded pool state, for cheap snapshot equality checks.
+    ///
+    /// Ticks and bitmaps are hashed in sorted order, so the result does not depend on
+    /// how the vectors were ordered. The debug `raw_data` hex strings are left out;
+    /// every decoded field (and each tick's `raw_slots`) is included with a fixed-width
+    /// big-endian encoding, so equal hashes mean equal state.
+    pub fn content_hash(&self) -> B256 {
+        let mut hasher = Keccak256::new();
+        let flag = |hasher: &mut Keccak256, present: bool| hasher.update([present as u8]);
+
+        hasher.update(self.address);
+        hasher.update([match self.protocol {
+            Protocol::UniswapV2 => 2u8,
+            Protocol::UniswapV3 => 3,
+            Protocol::UniswapV4 => 4,
+        }]);
+
+        flag(&mut hasher, self.pool_id.is_some());
+        if let Some(pool_id) = self.pool_id {
+            hasher.update(pool_id);
+        }
+
+        flag(&mut hasher, self.reserves.is_some());
+        if let Some(reserves) = &self.reserves {
+            hasher.update(reserves.reserve0.to_be_bytes());
+            hasher.update(reserves.reserve1.to_be_bytes());
+            hasher.update(reserves.block_timestamp_last.to_be_bytes());
+        }
+
+        flag(&mut hasher, self.slot0.is_some());
+        if let Some(slot0) = &self.slot0 {
+            hasher.update(slot0.sqrt_price_x96.to_be_bytes::<32>());
+            hasher.update(slot0.tick.to_be_bytes());
+            hasher.update(slot0.observation_index.to_be_bytes());
+            hasher.update(slot0.observation_cardinality.to_be_bytes());
+            hasher.update(slot0.observation_cardinality_next.to_be_bytes());
+            hasher.update([slot0.fee_protocol, slot0.unlocked as u8]);
+        }
+
+        flag(&mut hasher, self.liquidity.is_some());
+        if let Some(liquidity) = self.liquidity {
+            hasher.update(liquidity.to_be_bytes());
+        }
+        hasher.update([self.liquidity_upper_bits_set as u8]);
+
+        flag(&mut hasher, self.fee.is_some());
+        if let Some(fee) = self.fee {
+            hasher.update(fee.to_be_bytes());
+        }
+
+        let mut ticks: Vec<&Tick> = self.ticks.iter().collect();
+        ticks.sort_by_key(|t| t.tick);
+        hasher.update((ticks.len() as u64).to_be_bytes());
+        for tick in ticks {
+            hasher.update(tick.tick.to_be_bytes());
+            hasher.update(tick.liquidity_gross.to_be_bytes());
+            hasher.update(tick.liquidity_net.to_be_bytes());
+            hasher.update(tick.fee_growth_outside_0_x128.to_be_bytes::<32>());
+            hasher.update(tick.fee_growth_outside_1_x128.to_be_bytes::<32>());
+            hasher.update(tick.tick_cumulative_outside.to_be_bytes());
+            hasher.update(tick.seconds_per_liquidity_outside_x128.to_be_bytes::<32>());
+            hasher.update(tick.seconds_outside.to_be_bytes());
+            hasher.update([tick.initialized as u8]);
+            hasher.update((tick.raw_slots.len() as u64).to_be_bytes());
+            for (slot, value) in &tick.raw_slots {
+                hasher.update(slot);
+                hasher.update(value.to_be_bytes::<32>());
+            }
+        }
+
+        let mut bitmaps: Vec<&Bitmap> = self.bitmaps.iter().collect();
+        bitmaps.sort_by_key(|b| b.word_pos);
+        hasher.update((bitmaps.len() as u64).to_be_bytes());
+        for bitmap in bitmaps {
+            hasher.update(bitmap.word_pos.to_be_bytes());
+            hasher.update(bitmap.bitmap.to_be_bytes::<32>());
+        }
+
+        hasher.finalize()
+    }
+
+    /// Hash of a whole snapshot: the sorted [`PoolOutput::content_hash`]es of `pools`.
+    ///
+    /// Independent of pool order, so two runs over the same pools compare equal even
+    /// if they were collected in a different order.
+    pub fn snapshot_hash(pools: &[PoolOutput]) -> B256 {
+        let mut hashes: Vec<B256> = pools.iter().map(PoolOutput::content_hash).collect();
+        hashes.sort_unstable();
+
+        let mut hasher = Keccak256::new();
+        hasher.update((hashes.len() as u64).to_be_bytes());
+        for hash in hashes {
+            hasher.update(hash);
+        }
+        hasher.finalize()
+    }
+
+    /// Minimal swap state: price, tick, active liquidity and each initialized tick's
+    /// `liquidity_net`. V2 pools (no slot0) give zero price, tick and liquidity.
+    pub fn to_compact(&self) -> CompactPool {
+        let slot0 = self.slot0.as_ref();
+        CompactPool {
+            sqrt_price_x96: slot0.map_or(U256::ZERO, |slot0| slot0.sqrt_price_x96),
+            tick: slot0.map_or(0, |slot0| slot0.tick),
+            liquidity: self.liquidity.unwrap_or_default(),
+            ticks: self.ticks.iter().map(|tick| (tick.tick, tick.liquidity_net)).collect(),
+        }
+    }
+}
+
+/// V3/V4 pool state reduced to what a swap simulation needs (see
+/// [`PoolOutput::to_compact`]).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CompactPool {
+    #[cfg_attr(feature = "u256_decimal", serde(with = "crate::serde_helpers::serialize_u256_as_decimal"))]
+    pub sqrt_price_x96: U256,
+    pub tick: i32,
+    pub liquidity: u128,
+    /// `(tick, liquidity_net)` per initialized tick, ascending by tick.
+    pub ticks: Vec<(i32, i128)>,
 }
 
 /// Historical pool output with block number.
@@ -201,4 +650,195 @@ pub struct HistoricalPoolOutput {
     pub pool_data: PoolOutput,
     /// Block number where this state was queried.
     pub block_number: BlockNumber,
+    /// Timestamp of `block_number` (unix seconds), if its header is available.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub block_timestamp: Option<u64>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_layout_overrides_factory() {
+        let pancake = crate::storage::factories::PANCAKESWAP_V3;
+        let pool = PoolInput::new_v3_with_factory(Address::ZERO, 60, pancake);
+        assert_eq!(pool.v3_slots(), V3Slots::pancake_v3());
+
+        let pool = pool.with_layout(StorageLayout::canonical_v3());
+        assert_eq!(pool.v3_slots(), V3Slots::canonical_v3());
+    }
+
+    #[test]
+    fn test_v4_pools_slot_lives_in_layout() {
+        let pool = PoolInput::new_v4(Address::ZERO, 60);
+        assert_eq!(pool.layout, None);
+        assert_eq!(pool.v4_slots(), V4Slots::default());
+
+        let pool = pool.with_v4_pools_slot(9);
+        assert_eq!(pool.layout, Some(StorageLayout::canonical_v3().with_v4_pools(9)));
+        assert_eq!(pool.v4_slots(), V4Slots { pools: 9 });
+
+        // Keeps the V3 layout inferred from the factory
+        let pancake = crate::storage::factories::PANCAKESWAP_V3;
+        let pool = PoolInput::new_v3_with_factory(Address::ZERO, 60, pancake).with_v4_pools_slot(9);
+        assert_eq!(pool.v3_slots(), V3Slots::pancake_v3());
+    }
+
+    #[test]
+    fn test_new_v4_from_key() {
+        let key = PoolKey {
+            currency0: Address::ZERO,
+            currency1: Address::repeat_byte(0xa0),
+            fee: 3000,
+            tick_spacing: 60,
+            hooks: Address::ZERO,
+        };
+        let pool = PoolInput::new_v4_from_key(Address::repeat_byte(0x04), &key);
+        assert_eq!(pool.pool_id, Some(key.pool_id()));
+        assert_eq!(pool.tick_spacing, Some(60));
+        assert_ne!(key.pool_id(), PoolKey { fee: 500, ..key }.pool_id());
+
+        assert_eq!(pool.fee, Some(3000));
+        assert_eq!(pool.output_fee(), Some(3000));
+        let dynamic = PoolKey { fee: PoolKey::DYNAMIC_FEE_FLAG, ..key };
+        let pool = PoolInput::new_v4_from_key(Address::repeat_byte(0x04), &dynamic);
+        assert_eq!(pool.fee, Some(PoolKey::DYNAMIC_FEE_FLAG));
+        assert_eq!(pool.output_fee(), None);
+    }
+
+    #[test]
+    fn test_output_fee() {
+        // V4 fees are not tied to tick spacing, so nothing is inferred.
+        assert_eq!(PoolInput::new_v4(Address::ZERO, 60).output_fee(), None);
+        assert_eq!(PoolInput::new_v4(Address::ZERO, 60).with_fee(2500).output_fee(), Some(2500));
+        assert_eq!(PoolInput::new_v3(Address::ZERO, 60).output_fee(), Some(3000));
+        assert_eq!(PoolInput::new_v3(Address::ZERO, 60).with_fee(2500).output_fee(), Some(2500));
+        assert_eq!(PoolInput::new_v2(Address::ZERO).output_fee(), None);
+    }
+
+    #[test]
+    fn test_min_liquidity_gross() {
+        let tick = |liquidity_gross| Tick { liquidity_gross, ..Default::default() };
+        let pool = PoolInput::new_v3(Address::ZERO, 60);
+        assert!(pool.keeps_tick(&tick(0)));
+
+        let pool = pool.with_min_liquidity_gross(100);
+        assert!(!pool.keeps_tick(&tick(99)));
+        assert!(pool.keeps_tick(&tick(100)));
+    }
+
+    #[test]
+    fn test_to_compact() {
+        let tick = |tick, liquidity_net: i128| Tick {
+            tick,
+            liquidity_gross: liquidity_net.unsigned_abs(),
+            liquidity_net,
+            fee_growth_outside_0_x128: U256::from(7u8),
+            ..Default::default()
+        };
+        let sqrt_price_x96 = U256::from(1u128 << 96);
+        let slot0 = Slot0 { sqrt_price_x96, tick: 5, ..Default::default() };
+        let pool = PoolOutput::new_v3(
+            Address::ZERO,
+            slot0,
+            1_000,
+            vec![tick(60, -400), tick(-60, 400)],
+            Vec::new(),
+        );
+
+        assert_eq!(
+            pool.to_compact(),
+            CompactPool {
+                sqrt_price_x96,
+                tick: 5,
+                liquidity: 1_000,
+                ticks: vec![(-60, 400), (60, -400)],
+            }
+        );
+        let v2 = PoolOutput::new_v2(Address::ZERO, Reserves::default());
+        assert_eq!(v2.to_compact(), CompactPool::default());
+    }
+
+    #[test]
+    fn test_new_v3_sorts_ticks_and_bitmaps() {
+        let tick = |tick| Tick { tick, ..Default::default() };
+        let bitmap = |word_pos| Bitmap { word_pos, bitmap: U256::from(1u8) };
+
+        let pool = PoolOutput::new_v3(
+            Address::ZERO,
+            Slot0::default(),
+            0,
+            vec![tick(60), tick(-120), tick(0)],
+            vec![bitmap(1), bitmap(-2), bitmap(0)],
+        );
+
+        let ticks: Vec<i32> = pool.ticks.iter().map(|t| t.tick).collect();
+        let words: Vec<i16> = pool.bitmaps.iter().map(|b| b.word_pos).collect();
+        assert_eq!(ticks, vec![-120, 0, 60]);
+        assert_eq!(words, vec![-2, 0, 1]);
+    }
+
+    #[test]
+    fn test_heap_size() {
+        let tick = Tick { raw_data: Some("0x".to_string() + &"0".repeat(64)), ..Default::default() };
+        let bitmap = Bitmap { word_pos: 0, bitmap: U256::from(1u8) };
+
+        let empty = PoolOutput::new_v3(Address::ZERO, Slot0::default(), 0, Vec::new(), Vec::new());
+        assert_eq!(empty.heap_size(), 0);
+
+        let pool = PoolOutput::new_v3(
+            Address::ZERO,
+            Slot0::default(),
+            0,
+            vec![tick.clone(), tick],
+            vec![bitmap],
+        );
+        assert_eq!(
+            pool.heap_size(),
+            2 * (std::mem::size_of::<Tick>() + 66) + std::mem::size_of::<Bitmap>()
+        );
+    }
+
+    #[test]
+    fn test_content_hash_ignores_order_and_raw_data() {
+        let tick = |tick: i32| Tick { tick, liquidity_gross: 1, ..Default::default() };
+        let bitmap = |word_pos: i16| Bitmap { word_pos, bitmap: U256::from(1u8) };
+        let pool = PoolOutput::new_v3(
+            Address::ZERO,
+            Slot0::default(),
+            7,
+            vec![tick(-60), tick(60)],
+            vec![bitmap(-1), bitmap(0)],
+        );
+
+        let mut shuffled = pool.clone();
+        shuffled.ticks.reverse();
+        shuffled.bitmaps.reverse();
+        shuffled.ticks[0].raw_data = Some("0x01".to_string());
+        assert_eq!(pool.content_hash(), shuffled.content_hash());
+
+        let mut changed = pool.clone();
+        changed.ticks[1].liquidity_net = -1;
+        assert_ne!(pool.content_hash(), changed.content_hash());
+        let mut changed = pool.clone();
+        changed.liquidity = None;
+        assert_ne!(pool.content_hash(), changed.content_hash());
+
+        let v2 = PoolOutput::new_v2(Address::ZERO, Reserves::default());
+        assert_eq!(
+            PoolOutput::snapshot_hash(&[pool.clone(), v2.clone()]),
+            PoolOutput::snapshot_hash(&[v2.clone(), pool.clone()])
+        );
+        assert_ne!(PoolOutput::snapshot_hash(&[pool]), PoolOutput::snapshot_hash(&[v2]));
+    }
+
+    #[cfg(feature = "u256_decimal")]
+    #[test]
+    fn test_u256_fields_serialize_as_decimal() {
+        let slot0 = Slot0 { sqrt_price_x96: U256::from(1u8) << 96, ..Default::default() };
+        let json = serde_json::to_value(&slot0).unwrap();
+        assert_eq!(json["sqrt_price_x96"], "79228162514264337593543950336");
+        assert_eq!(serde_json::from_value::<Slot0>(json).unwrap(), slot0);
+    }
 }
@@ -0,0 +1,127 @@
+/// Consistency checks for collected pool data.
+///
+/// These are hard invariants of a well-formed V3/V4 pool; a violation points to a
+/// decoding bug, a corrupted slot or a partial read (e.g. a missing bitmap word).
+use alloy_primitives::I256;
+use serde::{Deserialize, Serialize};
+
+use crate::types::PoolOutput;
+
+/// A single violated tick invariant.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum TickAnomaly {
+    /// `liquidity_net` does not sum to zero across all initialized ticks
+    NonZeroNetSum { sum: I256 },
+    /// An initialized tick has zero `liquidity_gross`
+    ZeroLiquidityGross { tick: i32 },
+}
+
+impl std::fmt::Display for TickAnomaly {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NonZeroNetSum { sum } => {
+                write!(f, "liquidity_net sums to {sum} instead of 0")
+            }
+            Self::ZeroLiquidityGross { tick } => {
+                write!(f, "initialized tick {tick} has zero liquidity_gross")
+            }
+        }
+    }
+}
+
+/// Verify the tick invariants of a collected pool.
+///
+/// - the sum of `liquidity_net` over all ticks is zero
+/// - no initialized tick has zero `liquidity_gross`
+///
+/// Pools without ticks (V2, slot0-only reads) trivially pass. Returns every
+/// anomaly found rather than stopping at the first one.
+pub fn check_tick_invariants(pool: &PoolOutput) -> Result<(), Vec<TickAnomaly>> {
+    let mut anomalies = Vec::new();
+
+    let sum = pool
+        .ticks
+        .iter()
+        .fold(I256::ZERO, |acc, t| acc + I256::unchecked_from(t.liquidity_net));
+    if !sum.is_zero() {
+        anomalies.push(TickAnomaly::NonZeroNetSum { sum });
+    }
+
+    anomalies.extend(
+        pool.ticks
+            .iter()
+            .filter(|t| t.initialized && t.liquidity_gross == 0)
+            .map(|t| TickAnomaly::ZeroLiquidityGross { tick: t.tick }),
+    );
+
+    if anomalies.is_empty() {
+        Ok(())
+    } else {
+        Err(anomalies)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{Slot0, Tick};
+    use alloy_primitives::{Address, U256};
+
+    fn tick(tick: i32, gross: u128, net: i128) -> Tick {
+        Tick {
+            tick,
+            raw_data: None,
+            liquidity_gross: gross,
+            liquidity_net: net,
+            fee_growth_outside_0_x128: U256::ZERO,
+            fee_growth_outside_1_x128: U256::ZERO,
+            tick_cumulative_outside: 0,
+            seconds_per_liquidity_outside_x128: U256::ZERO,
+            seconds_outside: 0,
+            initialized: true,
+            raw_slots: Vec::new(),
+        }
+    }
+
+    fn pool(ticks: Vec<Tick>) -> PoolOutput {
+        let slot0 = Slot0 {
+            raw_data: None,
+            sqrt_price_x96: U256::from(1u8) << 96,
+            tick: 0,
+            observation_index: 0,
+            observation_cardinality: 1,
+            observation_cardinality_next: 1,
+            fee_protocol: 0,
+            unlocked: true,
+        };
+        PoolOutput::new_v3(Address::ZERO, slot0, 0, ticks, vec![])
+    }
+
+    #[test]
+    fn test_well_formed_pool_passes() {
+        let p = pool(vec![tick(-120, 300, 100), tick(-60, 200, 200), tick(60, 300, -300)]);
+        assert_eq!(check_tick_invariants(&p), Ok(()));
+    }
+
+    #[test]
+    fn test_detects_anomalies() {
+        // Missing upper tick and a zero-gross initialized tick
+        let p = pool(vec![tick(-60, 500, 500), tick(0, 0, 0)]);
+        let anomalies = check_tick_invariants(&p).unwrap_err();
+        assert_eq!(
+            anomalies,
+            vec![
+                TickAnomaly::NonZeroNetSum { sum: I256::unchecked_from(500) },
+                TickAnomaly::ZeroLiquidityGross { tick: 0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_net_sum_does_not_overflow() {
+        let p = pool(vec![tick(-60, u128::MAX, i128::MAX), tick(0, u128::MAX, i128::MAX)]);
+        let anomalies = check_tick_invariants(&p).unwrap_err();
+        assert!(matches!(anomalies[0], TickAnomaly::NonZeroNetSum { sum } if sum.is_positive()));
+    }
+}